@@ -0,0 +1,108 @@
+use arch_program::pubkey::Pubkey;
+
+use crate::{error::TokenError, get_mint_info, get_token_balance, logger::emit, math, total_holdings};
+
+/// What [`verify_accounting`] found for one mint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountingReport {
+    pub mint_pubkey: Pubkey,
+    /// Supply as stored on the mint.
+    pub supply: u64,
+    /// Sum of the balances of the accounts checked.
+    pub sum_balances: u64,
+    pub burned: u64,
+    /// Everything ever minted, as tracked by the caller; a mint only stores
+    /// its current supply, so this can't be read back.
+    pub minted: Option<u64>,
+}
+
+impl AccountingReport {
+    /// Supply not found in any of the accounts checked; negative if the
+    /// accounts hold more than the supply.
+    pub fn unaccounted(&self) -> i128 {
+        self.supply as i128 - self.sum_balances as i128
+    }
+
+    /// Tracked mints missing from `supply + burned`; negative if more was
+    /// burned or is in circulation than was minted. `None` without a
+    /// tracked figure.
+    pub fn unaccounted_minted(&self) -> Option<i128> {
+        self.minted.map(|minted| minted as i128 - self.supply as i128 - self.burned as i128)
+    }
+
+    /// Whether the accounts hold exactly the supply and, when a minted
+    /// figure was given, `supply + burned` matches it.
+    pub fn is_balanced(&self) -> bool {
+        self.unaccounted() == 0 && self.unaccounted_minted().unwrap_or(0) == 0
+    }
+}
+
+/// Sums the balances of `accounts` (each counted once) and compares them to
+/// `mint_pubkey`'s supply. A discrepancy usually means an account is missing
+/// from the list; it's printed and reported, not returned as an error. With a
+/// tracked `minted` total, `supply + burned` is checked against it the same
+/// way, which catches burns that went unrecorded. Fails
+/// with [`TokenError::WrongMint`](crate::error::TokenError::WrongMint) if an
+/// account belongs to another mint.
+pub fn verify_accounting(
+    mint_pubkey: Pubkey,
+    accounts: &[Pubkey],
+    burned: u64,
+    minted: Option<u64>,
+) -> Result<AccountingReport, Box<dyn std::error::Error>> {
+    let mut unique = accounts.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    let sum_balances = total_holdings(&unique, mint_pubkey)?;
+    let supply = get_mint_info(mint_pubkey)?.supply;
+
+    let report = AccountingReport { mint_pubkey, supply, sum_balances, burned, minted };
+    if let Some(minted) = minted {
+        let accounted = math::checked_add(supply, burned)?;
+        if accounted != minted {
+            emit!(
+                "⚠️ {} minted but supply plus burns is {} ({} unaccounted)",
+                minted,
+                accounted,
+                minted as i128 - accounted as i128
+            );
+        }
+    }
+    if report.unaccounted() == 0 {
+        emit!("✅ {} accounts hold the full supply of {}", unique.len(), supply);
+    } else {
+        emit!(
+            "⚠️ Supply is {} but {} accounts hold {} ({} unaccounted)",
+            supply,
+            unique.len(),
+            sum_balances,
+            report.unaccounted()
+        );
+    }
+    Ok(report)
+}
+
+/// Reads every account in `expected` and returns each with its actual
+/// balance, in order, if all of them match. Otherwise fails with
+/// [`TokenError::BalanceMismatches`] listing every account that doesn't, so
+/// one run shows everything an airdrop got wrong.
+pub fn verify_balances(expected: &[(Pubkey, u64)]) -> Result<Vec<(Pubkey, u64)>, Box<dyn std::error::Error>> {
+    let actual = expected
+        .iter()
+        .map(|(account, _)| Ok((*account, get_token_balance(*account)?)))
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+    let mismatches: Vec<(Pubkey, u64, u64)> = expected
+        .iter()
+        .zip(&actual)
+        .filter(|((_, want), (_, have))| want != have)
+        .map(|((account, want), (_, have))| (*account, *want, *have))
+        .collect();
+    if !mismatches.is_empty() {
+        return Err(TokenError::BalanceMismatches { mismatches }.into());
+    }
+
+    emit!("✅ All {} balances match", actual.len());
+    Ok(actual)
+}
@@ -0,0 +1,129 @@
+use std::{collections::BTreeMap, fmt, sync::Mutex};
+
+use arch_program::pubkey::Pubkey;
+use arch_sdk::ArchRpcClient;
+
+use crate::{error::TokenError, get_mint_info, get_token_balance, read_mint};
+
+/// A raw token amount paired with its mint's decimals. `Display` renders the
+/// human value with trailing zeros trimmed, e.g. `1_500_000_000` at 9 decimals
+/// prints as `1.5` and whole-number mints print without a decimal point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenAmount {
+    pub raw: u64,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Parses a human amount such as `"1.5"` into exact base units. Only the
+    /// strict form `^\d+(\.\d+)?$` is accepted: no signs, thousands
+    /// separators, exponents or bare leading/trailing points. Inputs with more
+    /// fractional digits than `decimals` are rejected rather than rounded, so
+    /// no value is silently dropped.
+    pub fn parse(ui_amount: &str, decimals: u8) -> Result<Self, TokenError> {
+        let invalid = |reason: &str| TokenError::InvalidAmount {
+            input: ui_amount.to_string(),
+            reason: reason.to_string(),
+        };
+
+        if ui_amount.is_empty() {
+            return Err(invalid("empty amount"));
+        }
+        if ui_amount.starts_with(['+', '-']) {
+            return Err(invalid("signs are not allowed"));
+        }
+        if ui_amount.contains([',', '_', ' ']) {
+            return Err(invalid("thousands separators are not allowed"));
+        }
+        if ui_amount.contains(['e', 'E']) {
+            return Err(invalid("scientific notation is not allowed"));
+        }
+
+        let (whole, fraction) = match ui_amount.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (ui_amount, None),
+        };
+        let is_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        if !is_digits(whole) || !fraction.is_none_or(is_digits) {
+            return Err(invalid("expected digits with an optional decimal point and fraction"));
+        }
+        let fraction = fraction.unwrap_or("");
+        if fraction.trim_end_matches('0').len() > decimals as usize {
+            return Err(invalid(&format!("more than {} decimal places", decimals)));
+        }
+
+        let raw = apl_token::try_ui_amount_into_amount(ui_amount.to_string(), decimals)
+            .map_err(|_| invalid("too large"))?;
+        Ok(Self::new(raw, decimals))
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&apl_token::amount_to_ui_amount_string_trimmed(self.raw, self.decimals))
+    }
+}
+
+/// Decimals per mint, read once and reused: a mint's decimals never change,
+/// so formatting many balances of one mint needn't re-read it each time. The
+/// UI amount helpers consult the process-wide [`decimals_cache`].
+#[derive(Debug, Default)]
+pub struct DecimalsCache {
+    decimals: Mutex<BTreeMap<Pubkey, u8>>,
+}
+
+static DECIMALS: DecimalsCache = DecimalsCache::new();
+
+/// The cache every UI amount helper in this crate reads through.
+pub fn decimals_cache() -> &'static DecimalsCache {
+    &DECIMALS
+}
+
+impl DecimalsCache {
+    pub const fn new() -> Self {
+        Self { decimals: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Decimals of `mint_pubkey`, read from the node on first use.
+    pub fn decimals(&self, client: &ArchRpcClient, mint_pubkey: Pubkey) -> Result<u8, Box<dyn std::error::Error>> {
+        self.decimals_with(mint_pubkey, |mint_pubkey| Ok(read_mint(client, mint_pubkey)?.decimals))
+    }
+
+    /// Decimals of `mint_pubkey`, calling `fetch` only if they aren't cached
+    /// yet. A failed fetch caches nothing.
+    pub fn decimals_with(
+        &self,
+        mint_pubkey: Pubkey,
+        fetch: impl FnOnce(Pubkey) -> Result<u8, Box<dyn std::error::Error>>,
+    ) -> Result<u8, Box<dyn std::error::Error>> {
+        if let Some(decimals) = self.decimals.lock().unwrap().get(&mint_pubkey) {
+            return Ok(*decimals);
+        }
+        let decimals = fetch(mint_pubkey)?;
+        self.decimals.lock().unwrap().insert(mint_pubkey, decimals);
+        Ok(decimals)
+    }
+
+    /// Forgets `mint_pubkey`, so its next lookup reads the node again.
+    pub fn invalidate(&self, mint_pubkey: Pubkey) {
+        self.decimals.lock().unwrap().remove(&mint_pubkey);
+    }
+
+    /// Forgets every mint.
+    pub fn clear(&self) {
+        self.decimals.lock().unwrap().clear();
+    }
+}
+
+/// Balance of `token_account`, scaled by the decimals of `mint_pubkey`.
+pub fn get_token_amount(
+    token_account: arch_program::pubkey::Pubkey,
+    mint_pubkey: arch_program::pubkey::Pubkey,
+) -> Result<TokenAmount, Box<dyn std::error::Error>> {
+    let decimals = decimals_cache().decimals_with(mint_pubkey, |mint_pubkey| Ok(get_mint_info(mint_pubkey)?.decimals))?;
+    Ok(TokenAmount::new(get_token_balance(token_account)?, decimals))
+}
@@ -0,0 +1,163 @@
+use std::ops::Range;
+
+use arch_program::{instruction::Instruction, pubkey::Pubkey, sanitized::ArchMessage};
+use arch_sdk::{ArchRpcClient, RuntimeTransaction, RUNTIME_TX_SIZE_LIMIT};
+
+use crate::{
+    error::{program_failure, TokenError},
+    receipts::{self, TransactionReceipt},
+};
+
+/// Outcome of a batch send where each transaction stands on its own.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchResult {
+    /// Transaction ids of the transactions that were processed.
+    pub successes: Vec<String>,
+    /// Position in the batch and reason for each transaction that wasn't.
+    pub failures: Vec<(usize, TokenError)>,
+}
+
+impl BatchResult {
+    /// True when every transaction in the batch went through.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Why the transaction carrying `group` failed, if it did, given the
+    /// group ranges [`chunk_instruction_groups`] returned for the batch.
+    pub fn group_failure(&self, ranges: &[Range<usize>], group: usize) -> Option<&TokenError> {
+        let tx = ranges.iter().position(|range| range.contains(&group))?;
+        self.failures.iter().find(|(failed, _)| *failed == tx).map(|(_, reason)| reason)
+    }
+}
+
+/// Most account keys one message can address; instructions index them by `u8`.
+const MAX_ACCOUNT_KEYS: usize = u8::MAX as usize + 1;
+
+/// Serialized size of a transaction carrying `message` once it's signed:
+/// version, signature count and one 64-byte signature per required signer.
+fn signed_size(message: &ArchMessage) -> usize {
+    4 + 1 + 64 * message.header.num_required_signatures as usize + message.serialize().len()
+}
+
+/// Whether a transaction carrying `message` fits the runtime's transaction
+/// size and account key limits once signed.
+pub fn fits_in_transaction(message: &ArchMessage) -> bool {
+    check_fits(message).is_ok()
+}
+
+fn check_fits(message: &ArchMessage) -> Result<(), TokenError> {
+    let size = signed_size(message);
+    if size > RUNTIME_TX_SIZE_LIMIT {
+        return Err(TokenError::TransactionTooLarge { size, max: RUNTIME_TX_SIZE_LIMIT });
+    }
+    if message.account_keys.len() > MAX_ACCOUNT_KEYS {
+        return Err(TokenError::TooManyAccountKeys { count: message.account_keys.len(), max: MAX_ACCOUNT_KEYS });
+    }
+    Ok(())
+}
+
+/// Builds the message for `instructions`, failing with the measured and
+/// maximum size if it wouldn't fit in a transaction.
+pub fn build_message_checked(
+    instructions: &[Instruction],
+    payer: Pubkey,
+    recent_blockhash: &str,
+) -> Result<ArchMessage, TokenError> {
+    let message = ArchMessage::new(instructions, Some(payer), recent_blockhash.to_string());
+    check_fits(&message)?;
+    Ok(message)
+}
+
+/// Packs `instructions`, in order, into as few messages paid by `payer` as
+/// will each fit the runtime's transaction size and account key limits.
+/// Fails if a single instruction is too large to fit on its own.
+pub fn chunk_instructions(
+    instructions: Vec<Instruction>,
+    payer: Pubkey,
+    recent_blockhash: &str,
+) -> Result<Vec<ArchMessage>, TokenError> {
+    let groups = instructions.into_iter().map(|instruction| vec![instruction]).collect();
+    Ok(chunk_instruction_groups(groups, Some(payer), recent_blockhash)?
+        .into_iter()
+        .map(|(message, _)| message)
+        .collect())
+}
+
+/// Like [`chunk_instructions`], for groups of instructions that must land in
+/// the same transaction, such as an account's create and initialize. Whole
+/// groups are packed in order, each message paired with the range of groups
+/// it carries. Without a `payer`, each message is paid by the first signer
+/// of its first instruction. Fails if a single group is too large to fit on
+/// its own.
+pub fn chunk_instruction_groups(
+    groups: Vec<Vec<Instruction>>,
+    payer: Option<Pubkey>,
+    recent_blockhash: &str,
+) -> Result<Vec<(ArchMessage, Range<usize>)>, TokenError> {
+    let message_for = |instructions: &[Instruction]| {
+        let payer = payer.or_else(|| {
+            instructions.first()?.accounts.iter().find(|meta| meta.is_signer).map(|meta| meta.pubkey)
+        });
+        ArchMessage::new(instructions, payer, recent_blockhash.to_string())
+    };
+
+    let count = groups.len();
+    let mut messages = Vec::new();
+    let mut start = 0;
+    let mut current: Vec<Instruction> = Vec::new();
+    for (i, group) in groups.into_iter().enumerate() {
+        let previous_len = current.len();
+        current.extend(group);
+        if i > start && !fits_in_transaction(&message_for(&current)) {
+            let overflow = current.split_off(previous_len);
+            let message = message_for(&current);
+            check_fits(&message)?;
+            messages.push((message, start..i));
+            start = i;
+            current = overflow;
+        }
+    }
+    if start < count {
+        let message = message_for(&current);
+        check_fits(&message)?;
+        messages.push((message, start..count));
+    }
+
+    Ok(messages)
+}
+
+/// Submits every transaction and waits for each, recording successes and
+/// failures individually instead of stopping at the first error. A
+/// transaction the program rejects fails with [`TokenError::ProgramFailed`].
+pub fn send_batch(client: &ArchRpcClient, transactions: Vec<RuntimeTransaction>) -> BatchResult {
+    let mut result = BatchResult::default();
+
+    // Submit everything first so the node can process them together
+    let mut pending = Vec::with_capacity(transactions.len());
+    for (i, transaction) in transactions.into_iter().enumerate() {
+        match client.send_transaction(transaction) {
+            Ok(txid) => {
+                receipts::record_submitted(&txid);
+                pending.push((i, txid));
+            }
+            Err(e) => result.failures.push((i, TokenError::TransactionFailed { reason: e.to_string() })),
+        }
+    }
+
+    for (i, txid) in pending {
+        match client.wait_for_processed_transaction(&txid) {
+            Ok(processed_tx) => {
+                receipts::record(TransactionReceipt::from_processed("send batch", &processed_tx));
+                match program_failure("send batch", &processed_tx) {
+                    None => result.successes.push(txid),
+                    Some(failure) => result.failures.push((i, failure)),
+                }
+            }
+            Err(e) => result.failures.push((i, TokenError::TransactionFailed { reason: e.to_string() })),
+        }
+    }
+
+    result.failures.sort_by_key(|(i, _)| *i);
+    result
+}
@@ -0,0 +1,160 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use arch_program::{instruction::Instruction, pubkey::Pubkey, sanitized::ArchMessage};
+use arch_sdk::{build_and_sign_transaction, ArchRpcClient, Status};
+use bitcoin::key::Keypair;
+use serde::{Deserialize, Serialize};
+
+use crate::{arch_pubkey, burns, create_token_account, create_token_mint_with_options, logger::emit, network::NetworkKind, poll};
+
+/// Throughput and latency of one operation's batch in [`run_bench`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpReport {
+    pub operation: String,
+    pub attempted: usize,
+    pub succeeded: usize,
+    /// Wall time from submitting the batch to the last transaction settling.
+    pub elapsed_ms: f64,
+    /// Successful transactions per second over `elapsed_ms`.
+    pub ops_per_sec: f64,
+    /// Submission-to-settlement latency of the successful transactions, to
+    /// within one poll interval.
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+}
+
+impl OpReport {
+    /// Report for `attempted` transactions of `operation` sent in `elapsed`,
+    /// where `latencies` are those of the ones that succeeded.
+    pub fn new(operation: &str, attempted: usize, mut latencies: Vec<Duration>, elapsed: Duration) -> Self {
+        latencies.sort();
+        let ms = |duration: Duration| duration.as_secs_f64() * 1_000.0;
+        Self {
+            operation: operation.to_string(),
+            attempted,
+            succeeded: latencies.len(),
+            elapsed_ms: ms(elapsed),
+            ops_per_sec: if elapsed.is_zero() { 0.0 } else { latencies.len() as f64 / elapsed.as_secs_f64() },
+            p50_ms: ms(percentile(&latencies, 50.0)),
+            p95_ms: ms(percentile(&latencies, 95.0)),
+        }
+    }
+}
+
+/// What [`run_bench`] measured, one entry per operation in the order run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub count: usize,
+    pub operations: Vec<OpReport>,
+}
+
+/// Nearest-rank `p`th percentile of `sorted`, which must be in ascending
+/// order. Zero when there's nothing to rank.
+pub fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Performance tool, not part of the normal lifecycle. Creates a mint whose
+/// faucet-funded authority pays for everything, then sends `count` mints,
+/// `count` transfers and `count` burns, each kind as one concurrent batch
+/// through [`poll::send_and_poll`] signed for `network`, and reports each
+/// batch's throughput and latency. Failed transactions count against
+/// throughput but not latency. Successful burns are recorded in
+/// [`burns::session`] like any other burn, and every settled transaction as a
+/// receipt, so `--receipts` covers bench runs.
+pub fn run_bench(client: &ArchRpcClient, count: usize, network: NetworkKind) -> Result<BenchReport, Box<dyn std::error::Error>> {
+    let (authority_keypair, mint_pubkey) = create_token_mint_with_options(client, 0, None)?;
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    let source = create_token_account(client, mint_pubkey, authority_keypair)?;
+    let destination = create_token_account(client, mint_pubkey, authority_keypair)?;
+
+    // Amounts differ per transaction so no two messages in a batch are
+    // identical; minting twice what's moved leaves enough for the burns.
+    let amount = |i: usize| i as u64 + 1;
+    let mut operations = Vec::with_capacity(3);
+    for operation in ["mint", "transfer", "burn"] {
+        let instructions = (0..count)
+            .map(|i| match operation {
+                "mint" => apl_token::instruction::mint_to(&apl_token::id(), &mint_pubkey, &source, &authority_pubkey, &[], 2 * amount(i)),
+                "transfer" => apl_token::instruction::transfer(&apl_token::id(), &source, &destination, &authority_pubkey, &[], amount(i)),
+                _ => apl_token::instruction::burn(&apl_token::id(), &source, &mint_pubkey, &authority_pubkey, &[], amount(i)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (report, succeeded) = time_batch(client, operation, instructions, authority_pubkey, authority_keypair, network)?;
+        if operation == "burn" {
+            burns::session().record(mint_pubkey, succeeded.iter().map(|&i| amount(i)).sum());
+        }
+        emit!(
+            "⏱️ {}: {}/{} in {:.0}ms ({:.2} tx/s, p50 {:.0}ms, p95 {:.0}ms)",
+            report.operation,
+            report.succeeded,
+            report.attempted,
+            report.elapsed_ms,
+            report.ops_per_sec,
+            report.p50_ms,
+            report.p95_ms
+        );
+        operations.push(report);
+    }
+
+    Ok(BenchReport { count, operations })
+}
+
+/// Sends each instruction as its own transaction, all at once, and times them.
+/// Also returns the indices of the instructions whose transactions succeeded.
+fn time_batch(
+    client: &ArchRpcClient,
+    operation: &str,
+    instructions: Vec<Instruction>,
+    payer: Pubkey,
+    payer_keypair: Keypair,
+    network: NetworkKind,
+) -> Result<(OpReport, Vec<usize>), Box<dyn std::error::Error>> {
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+    let transactions: Vec<_> = instructions
+        .iter()
+        .map(|instruction| {
+            build_and_sign_transaction(
+                ArchMessage::new(std::slice::from_ref(instruction), Some(payer), recent_blockhash.clone()),
+                vec![payer_keypair],
+                network.to_bitcoin_network(),
+            )
+        })
+        .collect();
+    let attempted = transactions.len();
+
+    let started = Instant::now();
+    let mut submitted: HashMap<String, Instant> = HashMap::new();
+    let mut settled: HashMap<String, Duration> = HashMap::new();
+    let processed_txs = poll::send_and_poll(client, &format!("bench {}", operation), transactions, |txid, state| match state {
+        poll::TxState::Pending => {
+            submitted.insert(txid.to_string(), Instant::now());
+        }
+        poll::TxState::Processed | poll::TxState::Failed(_) => {
+            let since = submitted.get(txid).copied().unwrap_or(started);
+            settled.insert(txid.to_string(), since.elapsed());
+        }
+        _ => {}
+    })?;
+    let elapsed = started.elapsed();
+
+    // Every transaction has settled, so these line up with `instructions`
+    let succeeded: Vec<usize> = processed_txs
+        .iter()
+        .enumerate()
+        .filter(|(_, processed_tx)| processed_tx.status == Status::Processed)
+        .map(|(i, _)| i)
+        .collect();
+    let latencies = succeeded
+        .iter()
+        .filter_map(|&i| settled.get(&processed_txs[i].runtime_transaction.txid()).copied())
+        .collect();
+    Ok((OpReport::new(operation, attempted, latencies, elapsed), succeeded))
+}
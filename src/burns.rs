@@ -0,0 +1,63 @@
+use std::{collections::BTreeMap, fs, path::Path, sync::Mutex};
+
+use arch_program::pubkey::Pubkey;
+
+use crate::{get_mint_info, pubkey_serde};
+
+/// Running total of tokens burned per mint. A mint only stores its current
+/// supply, so burns are otherwise invisible once they've happened. The burn
+/// helpers record into the process-wide [`session`] tracker; it can be saved
+/// and reloaded to carry totals across runs.
+#[derive(Debug, Default)]
+pub struct BurnTracker {
+    burned: Mutex<BTreeMap<Pubkey, u64>>,
+}
+
+static SESSION: BurnTracker = BurnTracker::new();
+
+/// The tracker every burn helper in this crate records into.
+pub fn session() -> &'static BurnTracker {
+    &SESSION
+}
+
+impl BurnTracker {
+    pub const fn new() -> Self {
+        Self { burned: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn record(&self, mint_pubkey: Pubkey, amount: u64) {
+        let mut burned = self.burned.lock().unwrap();
+        let total = burned.entry(mint_pubkey).or_default();
+        *total = total.saturating_add(amount);
+    }
+
+    /// Total recorded as burned for `mint_pubkey`.
+    pub fn burned(&self, mint_pubkey: Pubkey) -> u64 {
+        self.burned.lock().unwrap().get(&mint_pubkey).copied().unwrap_or(0)
+    }
+
+    /// Writes the totals to `path` as a JSON object of mint to amount.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut json = Vec::new();
+        pubkey_serde::map::serialize(&*self.burned.lock().unwrap(), &mut serde_json::Serializer::pretty(&mut json))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Adds the totals saved at `path` to this tracker's.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let totals: BTreeMap<Pubkey, u64> = pubkey_serde::map::deserialize(&mut serde_json::Deserializer::from_str(&contents))?;
+        for (mint, amount) in totals {
+            self.record(mint, amount);
+        }
+        Ok(())
+    }
+}
+
+/// Current supply of `mint_pubkey` alongside the total the session tracker
+/// has seen burned, as `(circulating, burned)`.
+pub fn circulating_and_burned(mint_pubkey: Pubkey) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let circulating = get_mint_info(mint_pubkey)?.supply;
+    Ok((circulating, session().burned(mint_pubkey)))
+}
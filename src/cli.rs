@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use arch_program::pubkey::Pubkey;
+
+use crate::{logger::LogFormat, parse_pubkey};
+
+/// What the binary was asked to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// No subcommand: run the full token lifecycle demo.
+    Lifecycle,
+    /// `ata --owner <pubkey> --mint <pubkey>`: print the owner's associated
+    /// token address for the mint, whether or not it exists.
+    Ata { owner: Pubkey, mint: Pubkey },
+    /// `--list-ops`: print every supported operation with its signers.
+    ListOps,
+    /// `watch --account <pubkey> [--interval <secs>]`: poll a token account's
+    /// balance and print each change until interrupted.
+    Watch { account: Pubkey, interval: Duration },
+    /// `verify-mint --mint <pubkey> --config <path>`: compare a mint with the
+    /// TOML config describing it, exiting non-zero on any difference.
+    VerifyMint { mint: Pubkey, config: String },
+    /// `bench [--count <n>]`: time `n` mints, transfers and burns and print
+    /// a JSON [`BenchReport`](crate::bench::BenchReport).
+    Bench { count: usize },
+}
+
+/// Poll interval for `watch` when `--interval` isn't given.
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Transactions per operation for `bench` when `--count` isn't given.
+const DEFAULT_BENCH_COUNT: usize = 20;
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    pub command: Command,
+    /// `--full-pubkeys`: log full pubkeys instead of shortened ones.
+    pub full_pubkeys: bool,
+    /// `--receipts <path>`: append a JSON line per transaction sent to `path`.
+    pub receipts_path: Option<String>,
+    /// `--label <text>`: tag every receipt from this run with `text`.
+    pub label: Option<String>,
+    /// `--log-format <emoji|plain|json>`: how output is rendered. When absent
+    /// the environment decides; see [`LOG_FORMAT_ENV`](crate::logger::LOG_FORMAT_ENV).
+    pub log_format: Option<LogFormat>,
+}
+
+/// Parses the arguments after the program name.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Cli, Box<dyn std::error::Error>> {
+    let mut args: Vec<String> = args.into_iter().collect();
+
+    let full_pubkeys = args.iter().any(|arg| arg == "--full-pubkeys");
+    args.retain(|arg| arg != "--full-pubkeys");
+
+    let receipts_path = take_flag_value(&mut args, "--receipts", "path")?;
+    let label = take_flag_value(&mut args, "--label", "text")?;
+    let log_format = take_flag_value(&mut args, "--log-format", "format")?
+        .map(|format| format.parse::<LogFormat>())
+        .transpose()?;
+
+    let command = match args.first().map(String::as_str) {
+        None => Command::Lifecycle,
+        Some("--list-ops") => Command::ListOps,
+        Some("watch") => Command::Watch {
+            account: pubkey_flag(&args, "--account")?,
+            interval: match flag_value(&args, "--interval") {
+                Some(secs) => match secs.parse::<u64>() {
+                    Ok(secs) if secs > 0 => Duration::from_secs(secs),
+                    _ => return Err(format!("Invalid --interval '{}': expected a whole number of seconds", secs).into()),
+                },
+                None => DEFAULT_WATCH_INTERVAL,
+            },
+        },
+        Some("bench") => Command::Bench {
+            count: match flag_value(&args, "--count") {
+                Some(count) => match count.parse::<usize>() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("Invalid --count '{}': expected a positive whole number", count).into()),
+                },
+                None => DEFAULT_BENCH_COUNT,
+            },
+        },
+        Some("verify-mint") => Command::VerifyMint {
+            mint: pubkey_flag(&args, "--mint")?,
+            config: flag_value(&args, "--config").ok_or("Missing --config <path>")?.to_string(),
+        },
+        Some("ata") => Command::Ata {
+            owner: pubkey_flag(&args, "--owner")?,
+            mint: pubkey_flag(&args, "--mint")?,
+        },
+        Some(other) => return Err(format!("Unknown command '{}'", other).into()),
+    };
+
+    Ok(Cli { command, full_pubkeys, receipts_path, label, log_format })
+}
+
+/// Removes a global `flag <value>` pair from `args`, returning the value.
+fn take_flag_value(args: &mut Vec<String>, flag: &str, what: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let Some(i) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    let value = args.get(i + 1).cloned().ok_or_else(|| format!("Missing {} <{}>", flag, what))?;
+    args.drain(i..=i + 1);
+    Ok(Some(value))
+}
+
+/// The value following `flag`, if present.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn pubkey_flag(args: &[String], flag: &str) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let value = flag_value(args, flag).ok_or_else(|| format!("Missing {} <pubkey>", flag))?;
+    parse_pubkey(value)
+}
@@ -0,0 +1,351 @@
+use std::{
+    ops::Deref,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use arch_program::{instruction::Instruction, pubkey::Pubkey, sanitized::ArchMessage};
+use arch_sdk::{build_and_sign_transaction, ArchRpcClient, ProcessedTransaction, RollbackStatus, RuntimeTransaction, Status};
+use bitcoin::key::Keypair;
+
+use crate::{
+    display,
+    error::{classify_failure, program_failure, FailureKind, TokenError},
+    logger::emit,
+    network::NetworkKind,
+    poll::{self, fresh_blockhash, MAX_BLOCKHASH_AGE},
+    receipts::{self, TransactionReceipt},
+};
+
+/// How settled a transaction must be before [`TokenClient::send`] returns.
+/// The 0.5 node only reports a processed status and whether the transaction
+/// was later rolled back, so the deeper levels are measured in blocks built
+/// on top after processing, during which it must stay processed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfirmationLevel {
+    #[default]
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl ConfirmationLevel {
+    /// Blocks that must follow the transaction's processing.
+    pub fn blocks_required(self) -> u64 {
+        match self {
+            ConfirmationLevel::Processed => 0,
+            ConfirmationLevel::Confirmed => 1,
+            ConfirmationLevel::Finalized => 6,
+        }
+    }
+}
+
+/// How long [`TokenClient::send`] waits for a level beyond processed.
+const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Delay between confirmation polls.
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// An [`ArchRpcClient`] that can reuse a recent block hash across transactions
+/// built close together, saving one RPC round trip per transaction in batch
+/// work. Without a TTL it behaves exactly like the plain client. Derefs to
+/// [`ArchRpcClient`], so it can be handed to every other helper as well.
+///
+/// Two identical instructions signed under the same cached hash produce the
+/// same transaction id, so repeated identical operations should not share one.
+pub struct TokenClient {
+    rpc: ArchRpcClient,
+    network: NetworkKind,
+    blockhash_ttl: Option<Duration>,
+    cached_blockhash: Mutex<Option<(String, Instant)>>,
+    confirmation_timeout: Duration,
+}
+
+impl TokenClient {
+    pub fn new(rpc: ArchRpcClient) -> Self {
+        Self {
+            rpc,
+            network: NetworkKind::default(),
+            blockhash_ttl: None,
+            cached_blockhash: Mutex::new(None),
+            confirmation_timeout: DEFAULT_CONFIRMATION_TIMEOUT,
+        }
+    }
+
+    /// Reuse a fetched block hash for up to `ttl`.
+    pub fn with_blockhash_cache(rpc: ArchRpcClient, ttl: Duration) -> Self {
+        Self {
+            blockhash_ttl: Some(ttl),
+            ..Self::new(rpc)
+        }
+    }
+
+    /// Sign transactions for `network` instead of the default regtest.
+    pub fn with_network(self, network: NetworkKind) -> Self {
+        Self { network, ..self }
+    }
+
+    /// Give up waiting for [`ConfirmationLevel::Confirmed`] or deeper after
+    /// `timeout` instead of the default ten minutes.
+    pub fn with_confirmation_timeout(self, timeout: Duration) -> Self {
+        Self { confirmation_timeout: timeout, ..self }
+    }
+
+    pub fn network(&self) -> NetworkKind {
+        self.network
+    }
+
+    pub fn rpc(&self) -> &ArchRpcClient {
+        &self.rpc
+    }
+
+    /// Runs `call` against `node_address` with a client built for this call
+    /// only, or against this client's node when it's `None`, e.g. to compare
+    /// what two nodes report. Only helpers that go through the client they're
+    /// handed follow the override: reads such as
+    /// [`get_token_balance`](crate::get_token_balance) always use the
+    /// default node. The temporary client has no block hash cache.
+    pub fn on_node<T>(&self, node_address: Option<&str>, call: impl FnOnce(&ArchRpcClient) -> T) -> T {
+        match node_address {
+            Some(address) => call(&ArchRpcClient::new(address)),
+            None => call(&self.rpc),
+        }
+    }
+
+    /// Tops up `keypair`'s account from this client's network faucet, as
+    /// [`fund_account_with_faucet`](crate::fund_account_with_faucet) does.
+    /// Fails with [`TokenError::NoFaucet`] on mainnet, where accounts must be
+    /// funded manually.
+    pub fn fund_with_faucet(&self, keypair: &Keypair, lamports: u64) -> Result<u64, Box<dyn std::error::Error>> {
+        crate::fund_account_with_faucet_on(&self.rpc, keypair, lamports, self.network)
+    }
+
+    /// Like [`fund_with_faucet`](Self::fund_with_faucet), for this network's
+    /// default faucet amount.
+    pub fn fund_with_faucet_default(&self, keypair: &Keypair) -> Result<u64, Box<dyn std::error::Error>> {
+        crate::fund_account_with_faucet_default_on(&self.rpc, keypair, self.network)
+    }
+
+    /// The cached block hash if it's still fresh, otherwise a newly fetched one.
+    pub fn recent_blockhash(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let Some(ttl) = self.blockhash_ttl else {
+            return fresh_blockhash(&self.rpc, MAX_BLOCKHASH_AGE);
+        };
+
+        let mut cached = self.cached_blockhash.lock().unwrap();
+        if let Some((hash, fetched_at)) = cached.as_ref() {
+            if fetched_at.elapsed() < ttl {
+                return Ok(hash.clone());
+            }
+        }
+
+        let hash = fresh_blockhash(&self.rpc, MAX_BLOCKHASH_AGE)?;
+        *cached = Some((hash.clone(), Instant::now()));
+        Ok(hash)
+    }
+
+    /// Drop the cached block hash so the next transaction fetches a fresh one.
+    pub fn invalidate_blockhash(&self) {
+        *self.cached_blockhash.lock().unwrap() = None;
+    }
+
+    /// Signs and sends `instructions` as one transaction and waits for it. If
+    /// its block hash expired, whether the node rejects it or reports it
+    /// failed, the cached hash is dropped and the transaction rebuilt and
+    /// resent under the same policy as every other send, see
+    /// [`poll::send_with_blockhash_retry`]. A transaction the program rejects
+    /// comes back as [`TokenError::ProgramFailed`].
+    pub fn send_ixs(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: Vec<Keypair>,
+        action: &str,
+    ) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+        let mut retrying = false;
+        poll::send_with_blockhash_retry(
+            || {
+                // Only asked again once the last hash has expired
+                if retrying {
+                    self.invalidate_blockhash();
+                }
+                retrying = true;
+                self.recent_blockhash()
+            },
+            |recent_blockhash| self.try_send(instructions, payer, signers.clone(), action, recent_blockhash),
+        )
+    }
+
+    /// Like [`send_ixs`](Self::send_ixs), but after processing keeps polling
+    /// until `level` is reached. Fails if the transaction is rolled back or
+    /// the confirmation timeout passes first.
+    pub fn send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: Vec<Keypair>,
+        action: &str,
+        level: ConfirmationLevel,
+    ) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+        let processed_tx = self.send_ixs(instructions, payer, signers, action)?;
+        if level == ConfirmationLevel::Processed {
+            return Ok(processed_tx);
+        }
+
+        let txid = processed_tx.runtime_transaction.txid();
+        let target_height = self.rpc.get_block_count()? + level.blocks_required();
+        let deadline = Instant::now() + self.confirmation_timeout;
+        loop {
+            let latest = self
+                .rpc
+                .get_processed_transaction(&txid)?
+                .ok_or_else(|| TokenError::TransactionFailed { reason: format!("{} disappeared from the node", txid) })?;
+            if let RollbackStatus::Rolledback(reason) = &latest.rollback_status {
+                return Err(TokenError::TransactionFailed { reason: format!("{} was rolled back: {}", txid, reason) }.into());
+            }
+            if latest.status != Status::Processed {
+                return Err(TokenError::TransactionFailed { reason: format!("{}: {:?}", txid, latest.status) }.into());
+            }
+            if self.rpc.get_block_count()? >= target_height {
+                return Ok(latest);
+            }
+            if Instant::now() >= deadline {
+                return Err(format!("Timed out waiting for {} to reach {:?}", txid, level).into());
+            }
+            std::thread::sleep(CONFIRMATION_POLL_INTERVAL);
+        }
+    }
+
+    /// Signs `instructions` and submits them as one transaction without
+    /// waiting; see [`poll::send_no_wait`](crate::poll::send_no_wait). The
+    /// returned txid must be polled to learn the outcome.
+    pub fn send_no_wait(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: Vec<Keypair>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let transaction = self.build_transaction(instructions, payer, signers)?;
+        let mut txids = poll::send_no_wait(&self.rpc, vec![transaction])?;
+        Ok(txids.remove(0))
+    }
+
+    fn build_transaction(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: Vec<Keypair>,
+    ) -> Result<RuntimeTransaction, Box<dyn std::error::Error>> {
+        Ok(self.sign(instructions, payer, signers, self.recent_blockhash()?))
+    }
+
+    fn sign(&self, instructions: &[Instruction], payer: &Pubkey, signers: Vec<Keypair>, recent_blockhash: String) -> RuntimeTransaction {
+        build_and_sign_transaction(
+            ArchMessage::new(instructions, Some(*payer), recent_blockhash),
+            signers,
+            self.network.to_bitcoin_network(),
+        )
+    }
+
+    fn try_send(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: Vec<Keypair>,
+        action: &str,
+        recent_blockhash: String,
+    ) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+        let transaction = self.sign(instructions, payer, signers, recent_blockhash);
+
+        let processed_tx = poll::send_once(&self.rpc, transaction)?;
+        // An expired hash can also surface as a failed status; hand it back to be retried
+        if classify_failure(&processed_tx) == Some(FailureKind::ExpiredBlockhash) {
+            return Err(format!("Transaction {} failed on an expired blockhash", processed_tx.txid()).into());
+        }
+        receipts::record(TransactionReceipt::from_processed(action, &processed_tx));
+        display::print_summary(std::slice::from_ref(&processed_tx));
+        if let Some(failure) = program_failure(action, &processed_tx) {
+            return Err(failure.into());
+        }
+        Ok(processed_tx)
+    }
+
+    pub fn mint_tokens(
+        &self,
+        mint_pubkey: &Pubkey,
+        account_pubkey: &Pubkey,
+        authority_pubkey: &Pubkey,
+        authority_keypair: Keypair,
+        amount: u64,
+    ) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+        let mint_ix = apl_token::instruction::mint_to(
+            &apl_token::id(),
+            mint_pubkey,
+            account_pubkey,
+            authority_pubkey,
+            &[],
+            amount,
+        )?;
+
+        let processed_tx = self.send_ixs(&[mint_ix], authority_pubkey, vec![authority_keypair], "mint tokens")?;
+        emit!("🪙 Minted {} tokens", amount);
+        Ok(processed_tx)
+    }
+
+    pub fn transfer_tokens(
+        &self,
+        from_account: &Pubkey,
+        to_account: &Pubkey,
+        owner_pubkey: &Pubkey,
+        owner_keypair: Keypair,
+        amount: u64,
+    ) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+        if from_account == to_account {
+            return Err(TokenError::SelfTransfer { account: *from_account }.into());
+        }
+
+        let transfer_ix = apl_token::instruction::transfer(
+            &apl_token::id(),
+            from_account,
+            to_account,
+            owner_pubkey,
+            &[],
+            amount,
+        )?;
+
+        let processed_tx = self.send_ixs(&[transfer_ix], owner_pubkey, vec![owner_keypair], "transfer tokens")?;
+        emit!("📤 Transferred {} tokens", amount);
+        Ok(processed_tx)
+    }
+
+    pub fn burn_tokens(
+        &self,
+        token_account: &Pubkey,
+        mint_pubkey: &Pubkey,
+        owner_pubkey: &Pubkey,
+        owner_keypair: Keypair,
+        amount: u64,
+    ) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+        let burn_ix = apl_token::instruction::burn(
+            &apl_token::id(),
+            token_account,
+            mint_pubkey,
+            owner_pubkey,
+            &[],
+            amount,
+        )?;
+
+        let processed_tx = self.send_ixs(&[burn_ix], owner_pubkey, vec![owner_keypair], "burn tokens")?;
+        crate::burns::session().record(*mint_pubkey, amount);
+        emit!("🔥 Burned {} tokens", amount);
+        Ok(processed_tx)
+    }
+}
+
+impl Deref for TokenClient {
+    type Target = ArchRpcClient;
+
+    fn deref(&self) -> &ArchRpcClient {
+        &self.rpc
+    }
+}
@@ -0,0 +1,96 @@
+use arch_program::{pubkey::Pubkey, system_program::SYSTEM_PROGRAM_ID};
+use arch_sdk::RuntimeTransaction;
+
+/// APL token instruction names, indexed by the tag in the first data byte.
+const TOKEN_INSTRUCTIONS: [&str; 23] = [
+    "InitializeMint",
+    "InitializeAccount",
+    "InitializeMultisig",
+    "Transfer",
+    "Approve",
+    "Revoke",
+    "SetAuthority",
+    "MintTo",
+    "Burn",
+    "CloseAccount",
+    "FreezeAccount",
+    "ThawAccount",
+    "TransferChecked",
+    "ApproveChecked",
+    "MintToChecked",
+    "BurnChecked",
+    "InitializeAccount2",
+    "InitializeAccount3",
+    "InitializeMint2",
+    "GetAccountDataSize",
+    "InitializeImmutableOwner",
+    "AmountToUiAmount",
+    "UiAmountToAmount",
+];
+
+/// System instruction names, indexed by their bincode variant (a LE `u32`).
+const SYSTEM_INSTRUCTIONS: [&str; 10] = [
+    "CreateAccount",
+    "CreateAccountWithAnchor",
+    "Assign",
+    "Anchor",
+    "Transfer",
+    "Allocate",
+    "AdvanceNonceAccount",
+    "WithdrawNonceAccount",
+    "InitializeNonceAccount",
+    "AuthorizeNonceAccount",
+];
+
+/// One line per instruction in `transaction`: its index, the program and a
+/// best guess at the instruction type, followed by the accounts with their
+/// signer (`s`) and writable (`w`) flags. Debugging aid only.
+pub fn describe_transaction(transaction: &RuntimeTransaction) -> Vec<String> {
+    let message = &transaction.message;
+
+    message
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(i, instruction)| {
+            let program_id = message.get_account_key(instruction.program_id_index as usize);
+            let name = program_id.map_or("unknown program".to_string(), |id| instruction_name(id, &instruction.data));
+
+            let accounts: Vec<String> = instruction
+                .accounts
+                .iter()
+                .map(|index| {
+                    let index = *index as usize;
+                    let key = message.get_account_key(index).map_or("?".to_string(), Pubkey::to_string);
+                    let signer = if message.is_signer(index) { "s" } else { "" };
+                    let writable = if message.is_writable_index(index) { "w" } else { "" };
+                    format!("{}[{}{}]", key, signer, writable)
+                })
+                .collect();
+
+            format!(
+                "#{} {} program={} accounts=[{}] data={}B",
+                i,
+                name,
+                program_id.map_or("?".to_string(), Pubkey::to_string),
+                accounts.join(", "),
+                instruction.data.len()
+            )
+        })
+        .collect()
+}
+
+fn instruction_name(program_id: &Pubkey, data: &[u8]) -> String {
+    if *program_id == apl_token::id() {
+        let name = data.first().and_then(|tag| TOKEN_INSTRUCTIONS.get(*tag as usize));
+        return format!("token::{}", name.unwrap_or(&"Unknown"));
+    }
+    if *program_id == SYSTEM_PROGRAM_ID {
+        let name = data
+            .get(..4)
+            .map(|tag| u32::from_le_bytes([tag[0], tag[1], tag[2], tag[3]]))
+            .and_then(|tag| SYSTEM_INSTRUCTIONS.get(tag as usize));
+        return format!("system::{}", name.unwrap_or(&"Unknown"));
+    }
+    format!("unknown(tag {:?})", data.first())
+}
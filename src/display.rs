@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use arch_program::pubkey::Pubkey;
+use arch_sdk::{ProcessedTransaction, RollbackStatus, Status};
+
+use crate::logger::emit;
+
+/// Hex characters kept from each end by [`short_pubkey`].
+const SHORT_PUBKEY_CHARS: usize = 4;
+
+static FULL_PUBKEYS: AtomicBool = AtomicBool::new(false);
+
+/// Abbreviates `pubkey` to its first and last few hex characters, e.g.
+/// `ab12…yz89`, for readable log lines.
+pub fn short_pubkey(pubkey: &Pubkey) -> String {
+    let hex = pubkey.to_string();
+    format!("{}…{}", &hex[..SHORT_PUBKEY_CHARS], &hex[hex.len() - SHORT_PUBKEY_CHARS..])
+}
+
+/// Print full pubkeys in log lines instead of shortened ones (`--full-pubkeys`).
+pub fn set_full_pubkeys(full: bool) {
+    FULL_PUBKEYS.store(full, Ordering::Relaxed);
+}
+
+/// `pubkey` as it should appear in log output: shortened unless
+/// [`set_full_pubkeys`] was switched on.
+pub fn log_pubkey(pubkey: &Pubkey) -> String {
+    if FULL_PUBKEYS.load(Ordering::Relaxed) {
+        pubkey.to_string()
+    } else {
+        short_pubkey(pubkey)
+    }
+}
+
+/// One line per transaction with its signature and outcome: processed,
+/// failed with the node's reason, or rolled back after processing.
+pub fn summarize_processed(txs: &[ProcessedTransaction]) -> String {
+    txs.iter()
+        .map(|tx| {
+            let txid = tx.runtime_transaction.txid();
+            match (&tx.status, &tx.rollback_status) {
+                (_, RollbackStatus::Rolledback(reason)) => format!("↩️ {} rolled back: {}", txid, reason),
+                (Status::Processed, _) => format!("✅ {} processed", txid),
+                (Status::Failed(reason), _) => format!("❌ {} failed: {}", txid, reason),
+                (status, _) => format!("⏳ {} {:?}", txid, status),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prints [`summarize_processed`] for `txs`.
+pub fn print_summary(txs: &[ProcessedTransaction]) {
+    emit!("{}", summarize_processed(txs));
+}
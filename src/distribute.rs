@@ -0,0 +1,380 @@
+use std::{fmt, path::Path};
+
+use arch_program::pubkey::Pubkey;
+use arch_sdk::{build_and_sign_transaction, ArchRpcClient};
+use bitcoin::key::Keypair;
+
+use crate::{
+    amount::TokenAmount,
+    batch::{chunk_instruction_groups, chunk_instructions, send_batch, BatchResult},
+    error::TokenError,
+    logger::emit,
+    network::NetworkKind,
+    poll,
+    arch_pubkey, create_token_program_account, ensure_payer_balance, estimate_batch_rent, get_token_account_size,
+    get_token_balance, math, parse_pubkey, rent_exempt_minimum,
+};
+
+/// Splits `total` across `weights` proportionally, rounding each share down.
+/// Whatever is lost to rounding goes to the first weight, so the shares always
+/// sum to exactly `total`.
+pub fn split_proportional(total: u64, weights: &[u32]) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+    if total_weight == 0 {
+        return Err("Cannot split an amount across zero total weight".into());
+    }
+
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|weight| (total as u128 * *weight as u128 / total_weight) as u64)
+        .collect();
+    // Each share is rounded down, so the shares never sum past `total`
+    let remainder = math::checked_sub(total, shares.iter().sum())?;
+    shares[0] = math::checked_add(shares[0], remainder)?;
+
+    Ok(shares)
+}
+
+/// Rejects a recipient listed twice with [`TokenError::DuplicateRecipient`],
+/// or with `merge` set, folds repeats into the first entry by summing their
+/// weights. Order of first appearance is kept.
+pub fn dedupe_recipients(recipients: &[(Pubkey, u32)], merge: bool) -> Result<Vec<(Pubkey, u32)>, TokenError> {
+    let mut deduped: Vec<(Pubkey, u32)> = Vec::with_capacity(recipients.len());
+    for (recipient, weight) in recipients {
+        match deduped.iter_mut().find(|(seen, _)| seen == recipient) {
+            None => deduped.push((*recipient, *weight)),
+            Some((_, total)) if merge => {
+                *total = total.checked_add(*weight).ok_or_else(|| TokenError::InvalidAmount {
+                    input: recipient.to_string(),
+                    reason: "merged weight overflows u32".to_string(),
+                })?;
+            }
+            Some(_) => return Err(TokenError::DuplicateRecipient { recipient: *recipient }),
+        }
+    }
+    Ok(deduped)
+}
+
+/// Knobs for [`mint_proportional_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct DistributeOptions {
+    /// Sum the weights of recipients listed more than once instead of
+    /// rejecting the list, which is the default to avoid double-crediting.
+    pub merge_duplicates: bool,
+    /// Network to sign for.
+    pub network: NetworkKind,
+}
+
+/// Mints `total` to `recipients` (token accounts) in proportion to their
+/// weights, batching the mints. Each batch transaction succeeds or fails on
+/// its own; the result says which ones didn't go through. A recipient listed
+/// twice is rejected; see [`mint_proportional_with_options`] to merge instead.
+pub fn mint_proportional(
+    client: &ArchRpcClient,
+    mint_pubkey: &Pubkey,
+    authority_keypair: Keypair,
+    total: u64,
+    recipients: &[(Pubkey, u32)],
+) -> Result<BatchResult, Box<dyn std::error::Error>> {
+    mint_proportional_with_options(client, mint_pubkey, authority_keypair, total, recipients, &DistributeOptions::default())
+}
+
+pub fn mint_proportional_with_options(
+    client: &ArchRpcClient,
+    mint_pubkey: &Pubkey,
+    authority_keypair: Keypair,
+    total: u64,
+    recipients: &[(Pubkey, u32)],
+    options: &DistributeOptions,
+) -> Result<BatchResult, Box<dyn std::error::Error>> {
+    let recipients = dedupe_recipients(recipients, options.merge_duplicates)?;
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    let weights: Vec<u32> = recipients.iter().map(|(_, weight)| *weight).collect();
+    let shares = split_proportional(total, &weights)?;
+
+    let allocations: Vec<(Pubkey, u64)> = recipients
+        .iter()
+        .zip(shares)
+        .map(|((account, _), amount)| (*account, amount))
+        .filter(|(_, amount)| *amount > 0)
+        .collect();
+
+    let instructions = allocations
+        .iter()
+        .map(|(account, amount)| {
+            apl_token::instruction::mint_to(&apl_token::id(), mint_pubkey, account, &authority_pubkey, &[], *amount)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+    let transactions = chunk_instructions(instructions, authority_pubkey, &recent_blockhash)?
+        .into_iter()
+        .map(|message| build_and_sign_transaction(message, vec![authority_keypair], options.network.to_bitcoin_network()))
+        .collect();
+
+    let result = send_batch(client, transactions);
+    emit!(
+        "🪙 Minted {} tokens across {} recipients ({} of {} transactions failed)",
+        total,
+        allocations.len(),
+        result.failures.len(),
+        result.failures.len() + result.successes.len()
+    );
+    Ok(result)
+}
+
+/// A new account from [`split_balance`] and the share it received, or why
+/// its transaction failed.
+pub type SplitResult = (Pubkey, Result<u64, TokenError>);
+
+/// Moves the whole balance of `source_account` into `n` new token accounts
+/// owned by `owner_keypair`, in even shares with the remainder going to the
+/// first. Each transaction creates and funds as many accounts as fit, so one
+/// failed transaction fails every account in it while the others stay
+/// funded; the result pairs each account, in share order, with its share or
+/// that failure. Signs for `network`.
+pub fn split_balance(
+    client: &ArchRpcClient,
+    source_account: &Pubkey,
+    owner_pubkey: &Pubkey,
+    owner_keypair: Keypair,
+    mint_pubkey: &Pubkey,
+    n: usize,
+    network: NetworkKind,
+) -> Result<Vec<SplitResult>, Box<dyn std::error::Error>> {
+    if n == 0 {
+        return Err("Cannot split a balance into zero accounts".into());
+    }
+
+    let balance = get_token_balance(*source_account)?;
+    let shares = split_proportional(balance, &vec![1; n])?;
+    let account_size = get_token_account_size(client, *mint_pubkey)?;
+    let rent = rent_exempt_minimum(client, account_size as usize)?;
+    ensure_payer_balance(*owner_pubkey, estimate_batch_rent(client, n, account_size as usize)?)?;
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+
+    let mut new_accounts = Vec::with_capacity(n);
+    let mut groups = Vec::with_capacity(n);
+    for share in &shares {
+        let (account_keypair, account_pubkey, _) = arch_sdk::generate_new_keypair(network.to_bitcoin_network());
+        groups.push(vec![
+            create_token_program_account(client, owner_pubkey, &account_pubkey, rent, account_size)?,
+            apl_token::instruction::initialize_account(&apl_token::id(), &account_pubkey, mint_pubkey, owner_pubkey)?,
+            apl_token::instruction::transfer(&apl_token::id(), source_account, &account_pubkey, owner_pubkey, &[], *share)?,
+        ]);
+        new_accounts.push((account_keypair, account_pubkey));
+    }
+
+    let chunks = chunk_instruction_groups(groups, Some(*owner_pubkey), &recent_blockhash)?;
+    let (transactions, ranges): (Vec<_>, Vec<_>) = chunks
+        .into_iter()
+        .map(|(message, range)| {
+            let signers = std::iter::once(owner_keypair)
+                .chain(new_accounts[range.clone()].iter().map(|(keypair, _)| *keypair))
+                .collect();
+            (build_and_sign_transaction(message, signers, network.to_bitcoin_network()), range)
+        })
+        .unzip();
+
+    let batch = send_batch(client, transactions);
+    let results: Vec<_> = new_accounts
+        .into_iter()
+        .zip(shares)
+        .enumerate()
+        .map(|(i, ((_, account), share))| match batch.group_failure(&ranges, i) {
+            Some(reason) => (account, Err(reason.clone())),
+            None => (account, Ok(share)),
+        })
+        .collect();
+
+    emit!(
+        "✂️ Split {} tokens across {} of {} accounts",
+        balance,
+        results.iter().filter(|(_, result)| result.is_ok()).count(),
+        n
+    );
+    Ok(results)
+}
+
+/// An owner and either their new token account or why it wasn't set up.
+pub type OnboardResult = (Pubkey, Result<Pubkey, TokenError>);
+
+/// Creates a token account for each of `owners` and mints `amount` into it,
+/// with `payer_keypair` covering rent. As many owners as fit share a
+/// transaction, so one failed transaction fails every owner in it; the result
+/// pairs each owner, in order, with their new token account or that failure.
+/// Signs for `network`. Fails up front with
+/// [`TokenError::InsufficientLamports`] if the payer can't cover every
+/// account's rent.
+pub fn onboard_users(
+    client: &ArchRpcClient,
+    mint_pubkey: &Pubkey,
+    authority_keypair: Keypair,
+    payer_keypair: Keypair,
+    owners: &[Pubkey],
+    amount: u64,
+    network: NetworkKind,
+) -> Result<Vec<OnboardResult>, Box<dyn std::error::Error>> {
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+    let account_size = get_token_account_size(client, *mint_pubkey)?;
+    let rent = rent_exempt_minimum(client, account_size as usize)?;
+    ensure_payer_balance(payer_pubkey, estimate_batch_rent(client, owners.len(), account_size as usize)?)?;
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+
+    let mut new_accounts = Vec::with_capacity(owners.len());
+    let mut groups = Vec::with_capacity(owners.len());
+    for owner in owners {
+        let (account_keypair, account_pubkey, _) = arch_sdk::generate_new_keypair(network.to_bitcoin_network());
+        groups.push(vec![
+            create_token_program_account(client, &payer_pubkey, &account_pubkey, rent, account_size)?,
+            apl_token::instruction::initialize_account(&apl_token::id(), &account_pubkey, mint_pubkey, owner)?,
+            apl_token::instruction::mint_to(&apl_token::id(), mint_pubkey, &account_pubkey, &authority_pubkey, &[], amount)?,
+        ]);
+        new_accounts.push((account_keypair, account_pubkey));
+    }
+
+    let mut payers = vec![payer_keypair];
+    if authority_pubkey != payer_pubkey {
+        payers.push(authority_keypair);
+    }
+    let chunks = chunk_instruction_groups(groups, Some(payer_pubkey), &recent_blockhash)?;
+    let (transactions, ranges): (Vec<_>, Vec<_>) = chunks
+        .into_iter()
+        .map(|(message, range)| {
+            let signers = payers
+                .iter()
+                .copied()
+                .chain(new_accounts[range.clone()].iter().map(|(keypair, _)| *keypair))
+                .collect();
+            (build_and_sign_transaction(message, signers, network.to_bitcoin_network()), range)
+        })
+        .unzip();
+
+    let batch = send_batch(client, transactions);
+    let results: Vec<_> = owners
+        .iter()
+        .zip(new_accounts)
+        .enumerate()
+        .map(|(i, (owner, (_, account)))| match batch.group_failure(&ranges, i) {
+            Some(reason) => (*owner, Err(reason.clone())),
+            None => (*owner, Ok(account)),
+        })
+        .collect();
+
+    emit!(
+        "👋 Onboarded {} of {} users with {} tokens each",
+        results.iter().filter(|(_, result)| result.is_ok()).count(),
+        owners.len(),
+        amount
+    );
+    Ok(results)
+}
+
+/// An airdrop list that passed [`validate_airdrop_csv`]: recipients and
+/// their amounts in base units, in file order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropPlan {
+    pub decimals: u8,
+    pub recipients: Vec<(Pubkey, u64)>,
+    /// Sum of every recipient's amount.
+    pub total: u64,
+}
+
+/// One problem [`validate_airdrop_csv`] found, at 1-based `line` of the
+/// file, or `None` for problems with the file as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvError {
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.reason),
+            None => f.write_str(&self.reason),
+        }
+    }
+}
+
+/// Pre-flight for an airdrop: reads `pubkey,amount` rows from the CSV at
+/// `path`, with amounts as UI amounts for `mint_decimals`. Blank lines,
+/// `#` comments and a leading `pubkey,amount` header are skipped. Every
+/// malformed row, invalid pubkey or amount, zero amount and repeated
+/// recipient is reported with its line number, along with a total over
+/// `intended_total` base units; the plan is only returned if there are none.
+pub fn validate_airdrop_csv(
+    path: impl AsRef<Path>,
+    mint_decimals: u8,
+    intended_total: u64,
+) -> Result<AirdropPlan, Vec<CsvError>> {
+    let contents = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+        vec![CsvError { line: None, reason: format!("Failed to read {}: {}", path.as_ref().display(), e) }]
+    })?;
+    parse_airdrop_csv(&contents, mint_decimals, intended_total)
+}
+
+/// [`validate_airdrop_csv`] on CSV text already in memory.
+pub fn parse_airdrop_csv(contents: &str, mint_decimals: u8, intended_total: u64) -> Result<AirdropPlan, Vec<CsvError>> {
+    let mut errors = Vec::new();
+    let mut recipients: Vec<(Pubkey, u64)> = Vec::new();
+    let mut first_seen: Vec<usize> = Vec::new();
+    let mut total = Some(0u64);
+
+    for (i, row) in contents.lines().enumerate() {
+        let line = i + 1;
+        let row = row.trim();
+        if row.is_empty() || row.starts_with('#') || (line == 1 && row.eq_ignore_ascii_case("pubkey,amount")) {
+            continue;
+        }
+        let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+        let [pubkey, amount] = fields[..] else {
+            let reason = format!("expected 'pubkey,amount', got {} fields", fields.len());
+            errors.push(CsvError { line: Some(line), reason });
+            continue;
+        };
+        let pubkey = parse_pubkey(pubkey).map_err(|e| e.to_string());
+        let amount = match TokenAmount::parse(amount, mint_decimals) {
+            Ok(amount) if amount.raw == 0 => Err("amount must be positive".to_string()),
+            Ok(amount) => Ok(amount.raw),
+            Err(e) => Err(e.to_string()),
+        };
+        let (pubkey, amount) = match (pubkey, amount) {
+            (Ok(pubkey), Ok(amount)) => (pubkey, amount),
+            (pubkey, amount) => {
+                let reasons = [pubkey.err(), amount.err()].into_iter().flatten();
+                errors.extend(reasons.map(|reason| CsvError { line: Some(line), reason }));
+                continue;
+            }
+        };
+
+        if let Some(position) = recipients.iter().position(|(seen, _)| *seen == pubkey) {
+            errors.push(CsvError {
+                line: Some(line),
+                reason: format!("{} is already listed on line {}", pubkey, first_seen[position]),
+            });
+            continue;
+        }
+        total = total.and_then(|total| total.checked_add(amount));
+        recipients.push((pubkey, amount));
+        first_seen.push(line);
+    }
+
+    match total {
+        Some(total) if total > intended_total => errors.push(CsvError {
+            line: None,
+            reason: format!("amounts add up to {} base units, over the intended {}", total, intended_total),
+        }),
+        None => errors.push(CsvError { line: None, reason: "amounts overflow u64".to_string() }),
+        Some(_) => {}
+    }
+    if recipients.is_empty() && errors.is_empty() {
+        errors.push(CsvError { line: None, reason: "no recipients".to_string() });
+    }
+
+    match (errors.is_empty(), total) {
+        (true, Some(total)) => Ok(AirdropPlan { decimals: mint_decimals, recipients, total }),
+        _ => Err(errors),
+    }
+}
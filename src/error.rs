@@ -0,0 +1,356 @@
+use std::fmt;
+
+use arch_program::pubkey::Pubkey;
+use arch_sdk::{ProcessedTransaction, Status};
+use num_traits::FromPrimitive;
+
+use crate::network::NetworkKind;
+
+/// Errors raised by this crate's own checks, plus on-chain failures it could
+/// attribute. RPC errors pass through as-is. Helpers still return
+/// `Box<dyn Error>`; downcast to match on these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// Source and destination of a transfer are the same account.
+    SelfTransfer { account: Pubkey },
+    /// An account's data doesn't decode as the expected type: it belongs to
+    /// another program, is uninitialized, or has the wrong size.
+    Decode { account: Pubkey, expected: &'static str },
+    /// The signer passed for a privileged operation isn't the account's
+    /// current `role` ("mint authority", "freeze authority", ...).
+    WrongAuthority {
+        account: Pubkey,
+        role: &'static str,
+        expected: Option<Pubkey>,
+        provided: Pubkey,
+    },
+    /// Minting `amount` would push the mint's supply past `u64::MAX`.
+    SupplyOverflow { mint: Pubkey, supply: u64, amount: u64 },
+    /// A transaction was rejected by the node or failed on-chain.
+    TransactionFailed { reason: String },
+    /// The faucet couldn't bring `account` up to `requested` lamports.
+    FaucetFailed {
+        account: Pubkey,
+        requested: u64,
+        balance: u64,
+        attempts: u32,
+        reason: String,
+    },
+    /// A UI amount string couldn't be turned into exact base units.
+    InvalidAmount { input: String, reason: String },
+    /// The node's copy of transaction `txid` has a different message hash
+    /// from the one signed locally.
+    Mismatch { txid: String, expected: String, actual: String },
+    /// `recipient` appears more than once in a distribution list.
+    DuplicateRecipient { recipient: Pubkey },
+    /// `account` already holds an initialized token account for `mint`.
+    AlreadyInitialized { account: Pubkey, mint: Pubkey },
+    /// A signed transaction would be `size` bytes, over the runtime's `max`.
+    TransactionTooLarge { size: usize, max: usize },
+    /// A message addresses `count` account keys, more than the `max` its
+    /// instructions can index.
+    TooManyAccountKeys { count: usize, max: usize },
+    /// `account` holds `actual` tokens after an operation that should have
+    /// left it with `expected` (`None` if the expected value over- or underflows).
+    UnexpectedBalance { account: Pubkey, expected: Option<u64>, actual: u64 },
+    /// Faucet funding was requested for `account` on a network without one.
+    NoFaucet { network: NetworkKind, account: Pubkey },
+    /// `mint`'s supply went from `before` to `after` across an operation
+    /// that should only move tokens.
+    SupplyChanged { mint: Pubkey, before: u64, after: u64 },
+    /// Token account `account` holds `actual` rather than the `expected` mint.
+    WrongMint { account: Pubkey, expected: Pubkey, actual: Pubkey },
+    /// `mint`'s `supply` is already past the `target` it was asked to reach.
+    SupplyAboveTarget { mint: Pubkey, supply: u64, target: u64 },
+    /// Amount arithmetic over- or underflowed `u64`; `expression` is the
+    /// operation that failed, e.g. `"5 - 7"`.
+    AmountOverflow { expression: String },
+    /// An account would be created with `provided` lamports, short of the
+    /// `required` rent-exempt minimum for its size.
+    InsufficientRent { provided: u64, required: u64 },
+    /// Two token accounts expected to share a mint hold different ones:
+    /// `account` holds `actual` while its counterpart holds `expected`.
+    MintMismatch { account: Pubkey, expected: Pubkey, actual: Pubkey },
+    /// `account` holds `balance`, less than the `amount` an operation takes.
+    InsufficientBalance { account: Pubkey, balance: u64, amount: u64 },
+    /// `account` is owned by `owner` rather than the token program, so it
+    /// can't hold a mint or token account.
+    WrongProgram { account: Pubkey, owner: Pubkey },
+    /// `account` is frozen by its mint's freeze authority and can't move tokens.
+    AccountFrozen { account: Pubkey },
+    /// The transaction for `action` was processed with a failed status.
+    /// `code` is the program's custom error code when the node reported one,
+    /// and `reason` the token program's description of it, or otherwise the
+    /// node's raw failure message.
+    ProgramFailed { action: String, code: Option<u32>, reason: String },
+    /// `mint` has `have` decimals where a flow assumed `expected`.
+    UnexpectedDecimals { mint: Pubkey, have: u8, expected: u8 },
+    /// `payer` holds `balance` lamports, short of the `required` a batch
+    /// will spend.
+    InsufficientLamports { payer: Pubkey, balance: u64, required: u64 },
+    /// `account` holds `got` bytes of data where its expected type takes
+    /// `expected`: it's some other kind of account, not a corrupt one.
+    WrongAccountSize { account: Pubkey, got: usize, expected: usize },
+    /// Accounts whose balance isn't what a check expected, as
+    /// `(account, expected, actual)`.
+    BalanceMismatches { mismatches: Vec<(Pubkey, u64, u64)> },
+    /// Recipient amounts add up to `allocated` where a launch plans a total
+    /// supply of `total_supply`.
+    AllocationMismatch { total_supply: u64, allocated: u64 },
+    /// `signer` was passed to sign for `multisig` but isn't one of its signers.
+    NotMultisigSigner { multisig: Pubkey, signer: Pubkey },
+    /// `provided` distinct signers were passed for `multisig`, short of the
+    /// `required` threshold.
+    NotEnoughSigners { multisig: Pubkey, required: u8, provided: usize },
+    /// State as of `block_hash` was asked for, but the node only serves the
+    /// latest state, which is at `best_block_hash`.
+    HistoricalStateUnavailable { block_hash: String, best_block_hash: String },
+    /// `withdraw` lamports were to leave `account`, which only holds
+    /// `balance`.
+    WithdrawExceedsBalance { account: Pubkey, balance: u64, withdraw: u64 },
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenError::SelfTransfer { account } => {
+                write!(f, "Refusing to transfer from {} to itself", account)
+            }
+            TokenError::Decode { account, expected } => {
+                write!(f, "Account {} is not a valid {}", account, expected)
+            }
+            TokenError::WrongAuthority { account, role, expected: Some(expected), provided } => {
+                write!(f, "{} is not the {} of {} (expected {})", provided, role, account, expected)
+            }
+            TokenError::WrongAuthority { account, role, expected: None, provided } => {
+                write!(f, "{} is not the {} of {}: it has none", provided, role, account)
+            }
+            TokenError::SupplyOverflow { mint, supply, amount } => {
+                write!(f, "Minting {} to {} would overflow its supply of {}", amount, mint, supply)
+            }
+            TokenError::TransactionFailed { reason } => write!(f, "Transaction failed: {}", reason),
+            TokenError::FaucetFailed { account, requested, balance, attempts, reason } => write!(
+                f,
+                "Faucet failed to fund {} with {} lamports (has {}) after {} attempts: {}",
+                account, requested, balance, attempts, reason
+            ),
+            TokenError::InvalidAmount { input, reason } => write!(f, "Invalid amount '{}': {}", input, reason),
+            TokenError::Mismatch { txid, expected, actual } => write!(
+                f,
+                "Transaction {} on the node doesn't match what was signed: message hash {} != {}",
+                txid, actual, expected
+            ),
+            TokenError::DuplicateRecipient { recipient } => {
+                write!(f, "Recipient {} is listed more than once", recipient)
+            }
+            TokenError::AlreadyInitialized { account, mint } => {
+                write!(f, "Account {} is already an initialized token account for {}", account, mint)
+            }
+            TokenError::TransactionTooLarge { size, max } => {
+                write!(f, "Transaction is {} bytes, over the {} byte limit", size, max)
+            }
+            TokenError::TooManyAccountKeys { count, max } => {
+                write!(f, "Message uses {} account keys, over the limit of {}", count, max)
+            }
+            TokenError::UnexpectedBalance { account, expected: Some(expected), actual } => {
+                write!(f, "Account {} holds {} tokens, expected {}", account, actual, expected)
+            }
+            TokenError::UnexpectedBalance { account, expected: None, actual } => {
+                write!(f, "Account {} holds {} tokens, expected balance is out of range", account, actual)
+            }
+            TokenError::NoFaucet { network, account } => write!(
+                f,
+                "There is no faucet on {}; fund {} manually before retrying",
+                network, account
+            ),
+            TokenError::SupplyChanged { mint, before, after } => {
+                write!(f, "Supply of {} changed from {} to {} during a transfer", mint, before, after)
+            }
+            TokenError::WrongMint { account, expected, actual } => {
+                write!(f, "Account {} holds mint {}, expected {}", account, actual, expected)
+            }
+            TokenError::SupplyAboveTarget { mint, supply, target } => {
+                write!(f, "Supply of {} is already {}, above the target of {}", mint, supply, target)
+            }
+            TokenError::AmountOverflow { expression } => write!(f, "Amount arithmetic out of range: {}", expression),
+            TokenError::InsufficientRent { provided, required } => write!(
+                f,
+                "Account would hold {} lamports, below the rent-exempt minimum of {}",
+                provided, required
+            ),
+            TokenError::MintMismatch { account, expected, actual } => write!(
+                f,
+                "Account {} holds mint {}, but its counterpart holds {}",
+                account, actual, expected
+            ),
+            TokenError::InsufficientBalance { account, balance, amount } => {
+                write!(f, "Account {} holds {}, not enough for {}", account, balance, amount)
+            }
+            TokenError::WrongProgram { account, owner } => {
+                write!(f, "Account {} is owned by {}, not the token program", account, owner)
+            }
+            TokenError::AccountFrozen { account } => {
+                write!(f, "Account {} is frozen; thaw it before moving tokens", account)
+            }
+            TokenError::ProgramFailed { action, code: Some(code), reason } => {
+                write!(f, "Failed to {}: {:#x}: {}", action, code, reason)
+            }
+            TokenError::ProgramFailed { action, code: None, reason } => write!(f, "Failed to {}: {}", action, reason),
+            TokenError::UnexpectedDecimals { mint, have, expected } => {
+                write!(f, "Mint {} has {} decimals, expected {}", mint, have, expected)
+            }
+            TokenError::InsufficientLamports { payer, balance, required } => write!(
+                f,
+                "Payer {} holds {} lamports but needs {}; fund it with {} more first",
+                payer,
+                balance,
+                required,
+                required.saturating_sub(*balance)
+            ),
+            TokenError::WrongAccountSize { account, got, expected } => {
+                write!(f, "Account {} holds {} bytes of data, expected {}", account, got, expected)
+            }
+            TokenError::BalanceMismatches { mismatches } => {
+                write!(f, "{} balances don't match:", mismatches.len())?;
+                for (account, expected, actual) in mismatches {
+                    write!(f, "\n  {}: expected {}, holds {}", account, expected, actual)?;
+                }
+                Ok(())
+            }
+            TokenError::AllocationMismatch { total_supply, allocated } => write!(
+                f,
+                "Recipient amounts add up to {} but the total supply is {}",
+                allocated, total_supply
+            ),
+            TokenError::NotMultisigSigner { multisig, signer } => {
+                write!(f, "{} is not a signer of multisig {}", signer, multisig)
+            }
+            TokenError::NotEnoughSigners { multisig, required, provided } => write!(
+                f,
+                "Multisig {} needs {} signers but only {} were given",
+                multisig, required, provided
+            ),
+            TokenError::HistoricalStateUnavailable { block_hash, best_block_hash } => write!(
+                f,
+                "Node only serves state at its best block {}, not at block {}",
+                best_block_hash, block_hash
+            ),
+            TokenError::WithdrawExceedsBalance { account, balance, withdraw } => write!(
+                f,
+                "Cannot withdraw {} lamports from {}, which holds only {}",
+                withdraw, account, balance
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// [`TokenError::ProgramFailed`] for `processed_tx` if it failed, looking for
+/// a custom program error code in the failure message and then the logs.
+/// `None` if it was processed.
+pub fn program_failure(action: &str, processed_tx: &ProcessedTransaction) -> Option<TokenError> {
+    let reason = match &processed_tx.status {
+        Status::Processed => return None,
+        Status::Failed(reason) => reason.clone(),
+        status => format!("unexpected status {:?}", status),
+    };
+
+    let code = std::iter::once(reason.as_str())
+        .chain(processed_tx.logs.iter().map(String::as_str))
+        .find_map(custom_error_code);
+    let reason = match code.and_then(apl_token::error::TokenError::from_u32) {
+        Some(token_error) => token_error.to_string(),
+        None => reason,
+    };
+    Some(TokenError::ProgramFailed { action: action.to_string(), code, reason })
+}
+
+/// Why a transaction failed, coarse enough to decide whether to retry. See
+/// [`classify_failure`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureKind {
+    /// The source account or payer doesn't hold enough.
+    InsufficientFunds,
+    /// A required signature is missing or the signer isn't the account's
+    /// owner or authority.
+    InvalidAuthority,
+    AccountFrozen,
+    /// The block hash expired before the transaction ran.
+    ExpiredBlockhash,
+    /// Anything else, with the failure message.
+    Unknown(String),
+}
+
+impl FailureKind {
+    /// Whether sending the same instructions again can succeed without
+    /// anything else changing: only a rebuilt transaction with a new block
+    /// hash can.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, FailureKind::ExpiredBlockhash)
+    }
+}
+
+impl TokenError {
+    /// The [`FailureKind`] this error amounts to, for errors describing a
+    /// failed transaction or one the node would fail; `None` for the rest.
+    pub fn failure_kind(&self) -> Option<FailureKind> {
+        match self {
+            TokenError::ProgramFailed { code, reason, .. } => Some(classify(*code, [reason.as_str()])),
+            TokenError::TransactionFailed { reason } => Some(classify(None, [reason.as_str()])),
+            TokenError::AccountFrozen { .. } => Some(FailureKind::AccountFrozen),
+            TokenError::InsufficientBalance { .. }
+            | TokenError::InsufficientLamports { .. }
+            | TokenError::WithdrawExceedsBalance { .. } => Some(FailureKind::InsufficientFunds),
+            TokenError::WrongAuthority { .. } => Some(FailureKind::InvalidAuthority),
+            _ => None,
+        }
+    }
+}
+
+/// Classifies a failed `processed_tx` from its program error code, failure
+/// message and logs. `None` if it was processed.
+pub fn classify_failure(processed_tx: &ProcessedTransaction) -> Option<FailureKind> {
+    let reason = match &processed_tx.status {
+        Status::Processed => return None,
+        Status::Failed(reason) => reason.as_str(),
+        Status::Queued => "transaction still queued",
+    };
+    let messages = || std::iter::once(reason).chain(processed_tx.logs.iter().map(String::as_str));
+    Some(classify(messages().find_map(custom_error_code), messages()))
+}
+
+/// A token program error code decides; without one, the messages are
+/// searched for the phrases the runtime and token program use.
+fn classify<'a>(code: Option<u32>, messages: impl IntoIterator<Item = &'a str>) -> FailureKind {
+    match code.and_then(apl_token::error::TokenError::from_u32) {
+        Some(apl_token::error::TokenError::InsufficientFunds) => return FailureKind::InsufficientFunds,
+        Some(apl_token::error::TokenError::OwnerMismatch) => return FailureKind::InvalidAuthority,
+        Some(apl_token::error::TokenError::AccountFrozen) => return FailureKind::AccountFrozen,
+        _ => {}
+    }
+
+    let messages: Vec<&str> = messages.into_iter().collect();
+    let lowercase = messages.join("\n").to_lowercase();
+    if crate::poll::is_blockhash_error(&lowercase) {
+        FailureKind::ExpiredBlockhash
+    } else if lowercase.contains("insufficient funds") || lowercase.contains("insufficient lamports") {
+        FailureKind::InsufficientFunds
+    } else if lowercase.contains("missing required signature")
+        || lowercase.contains("missing signature")
+        || lowercase.contains("owner does not match")
+    {
+        FailureKind::InvalidAuthority
+    } else if lowercase.contains("account is frozen") {
+        FailureKind::AccountFrozen
+    } else {
+        FailureKind::Unknown(messages.first().copied().unwrap_or_default().to_string())
+    }
+}
+
+/// The code in a `custom program error: 0x1` message.
+fn custom_error_code(message: &str) -> Option<u32> {
+    let (_, rest) = message.split_once("custom program error: 0x")?;
+    let hex: String = rest.chars().take_while(char::is_ascii_hexdigit).collect();
+    u32::from_str_radix(&hex, 16).ok()
+}
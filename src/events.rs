@@ -0,0 +1,59 @@
+use apl_token::instruction::TokenInstruction;
+use arch_program::pubkey::Pubkey;
+use arch_sdk::ProcessedTransaction;
+use serde::{Deserialize, Serialize};
+
+/// A token transfer as an indexer sees it: everything about it, taken from
+/// the signed transaction rather than re-read from chain state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferEvent {
+    #[serde(with = "crate::pubkey_serde")]
+    pub from: Pubkey,
+    #[serde(with = "crate::pubkey_serde")]
+    pub to: Pubkey,
+    #[serde(with = "crate::pubkey_serde")]
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// The transaction id.
+    pub signature: String,
+    /// Block hash the transaction was signed against.
+    pub block_hash: String,
+}
+
+impl TransferEvent {
+    /// The event for the first `Transfer` or `TransferChecked` in
+    /// `processed_tx`, or `None` if it has neither. A plain `Transfer`
+    /// doesn't name its mint, so the caller supplies `mint`; a
+    /// `TransferChecked` names its own, which wins.
+    pub fn from_processed(mint: Pubkey, processed_tx: &ProcessedTransaction) -> Option<Self> {
+        let message = &processed_tx.runtime_transaction.message;
+        let account = |instruction: &arch_program::sanitized::SanitizedInstruction, position: usize| {
+            instruction
+                .accounts
+                .get(position)
+                .and_then(|index| message.get_account_key(*index as usize))
+                .copied()
+        };
+
+        message.instructions.iter().find_map(|instruction| {
+            if message.get_account_key(instruction.program_id_index as usize) != Some(&apl_token::id()) {
+                return None;
+            }
+            let (from, mint, to, amount) = match TokenInstruction::unpack(&instruction.data).ok()? {
+                TokenInstruction::Transfer { amount } => (account(instruction, 0)?, mint, account(instruction, 1)?, amount),
+                TokenInstruction::TransferChecked { amount, .. } => {
+                    (account(instruction, 0)?, account(instruction, 1)?, account(instruction, 2)?, amount)
+                }
+                _ => return None,
+            };
+            Some(Self {
+                from,
+                to,
+                mint,
+                amount,
+                signature: processed_tx.runtime_transaction.txid(),
+                block_hash: message.recent_blockhash.clone(),
+            })
+        })
+    }
+}
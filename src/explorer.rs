@@ -0,0 +1,14 @@
+use arch_program::pubkey::Pubkey;
+use arch_test_sdk::constants::{get_explorer_address_url, get_explorer_tx_url};
+use bitcoin::Network;
+
+/// Link to a transaction on the explorer the Arch SDK configures for `network`.
+pub fn explorer_url_for_tx(txid: &str, network: Network) -> String {
+    get_explorer_tx_url(network, txid)
+}
+
+/// Link to an account, addressed by its hex pubkey, on the explorer the Arch
+/// SDK configures for `network`.
+pub fn explorer_url_for_account(pubkey: &Pubkey, network: Network) -> String {
+    get_explorer_address_url(network, &pubkey.to_string())
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+use apl_token::state::{Account, AccountState, Mint};
+use arch_program::pubkey::Pubkey;
+use arch_sdk::ArchRpcClient;
+
+use crate::{amount::TokenAmount, error::TokenError, read_mint, unpack_token_account};
+
+/// Everything about a mint and a set of its token accounts, for debugging.
+/// `Display` renders it as an indented, human-readable dump.
+#[derive(Debug, Clone)]
+pub struct InspectReport {
+    pub mint_pubkey: Pubkey,
+    pub mint: Mint,
+    pub accounts: Vec<(Pubkey, Account)>,
+}
+
+/// Reads `mint_pubkey` and each of `accounts`. Fails with
+/// [`TokenError::WrongMint`] on the first account that doesn't hold
+/// `mint_pubkey`, so the dump never mixes in another mint's balances.
+pub fn inspect(
+    client: &ArchRpcClient,
+    mint_pubkey: Pubkey,
+    accounts: &[Pubkey],
+) -> Result<InspectReport, Box<dyn std::error::Error>> {
+    let mint = read_mint(client, mint_pubkey)?;
+
+    let accounts = accounts
+        .iter()
+        .map(|pubkey| {
+            let account = unpack_token_account(*pubkey, &client.read_account_info(*pubkey)?.data)?;
+            if account.mint != mint_pubkey {
+                return Err(TokenError::WrongMint { account: *pubkey, expected: mint_pubkey, actual: account.mint }.into());
+            }
+            Ok((*pubkey, account))
+        })
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+    Ok(InspectReport { mint_pubkey, mint, accounts })
+}
+
+impl fmt::Display for InspectReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = self.mint.decimals;
+        writeln!(f, "Mint {}", self.mint_pubkey)?;
+        writeln!(f, "  decimals:         {}", decimals)?;
+        writeln!(f, "  supply:           {} ({} raw)", TokenAmount::new(self.mint.supply, decimals), self.mint.supply)?;
+        writeln!(f, "  mint authority:   {}", describe_authority(self.mint.mint_authority.into()))?;
+        writeln!(f, "  freeze authority: {}", describe_authority(self.mint.freeze_authority.into()))?;
+        writeln!(f, "Accounts ({})", self.accounts.len())?;
+
+        for (pubkey, account) in &self.accounts {
+            writeln!(f, "  {}", pubkey)?;
+            writeln!(f, "    owner:    {}", account.owner)?;
+            writeln!(f, "    balance:  {}", TokenAmount::new(account.amount, decimals))?;
+            let delegate: Option<Pubkey> = account.delegate.into();
+            match delegate {
+                Some(delegate) => writeln!(
+                    f,
+                    "    delegate: {} ({})",
+                    delegate,
+                    TokenAmount::new(account.delegated_amount, decimals)
+                )?,
+                None => writeln!(f, "    delegate: none")?,
+            }
+            let state = match account.state {
+                AccountState::Uninitialized => "uninitialized",
+                AccountState::Initialized => "initialized",
+                AccountState::Frozen => "frozen",
+            };
+            writeln!(f, "    state:    {}", state)?;
+        }
+        Ok(())
+    }
+}
+
+fn describe_authority(authority: Option<Pubkey>) -> String {
+    authority.map_or("none".to_string(), |authority| authority.to_string())
+}
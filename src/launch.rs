@@ -0,0 +1,103 @@
+use std::fmt;
+
+use apl_token::state::Account;
+use arch_program::{program_pack::Pack, pubkey::Pubkey};
+use arch_sdk::ArchRpcClient;
+use bitcoin::key::Keypair;
+
+use crate::{
+    amount::TokenAmount,
+    arch_pubkey, create_token_account3, create_token_mint_with_options,
+    display::log_pubkey,
+    ensure_payer_balance, estimate_batch_rent,
+    error::TokenError,
+    logger::emit,
+    math, mint_tokens,
+};
+
+/// Everything a launch will do: one mint, then for each recipient a token
+/// account and its initial amount. Built by [`plan_launch`] without touching
+/// the node; print it for review, then hand it to [`execute_plan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchPlan {
+    pub decimals: u8,
+    pub total_supply: u64,
+    /// Recipient owners and the base units each receives, in order.
+    pub recipients: Vec<(Pubkey, u64)>,
+}
+
+impl fmt::Display for LaunchPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Launch plan: {} tokens to {} recipients\n  1. Create mint with {} decimals",
+            TokenAmount::new(self.total_supply, self.decimals),
+            self.recipients.len(),
+            self.decimals
+        )?;
+        for (i, (owner, amount)) in self.recipients.iter().enumerate() {
+            write!(
+                f,
+                "\n  {}. Create token account for {} and mint {} tokens",
+                i + 2,
+                log_pubkey(owner),
+                TokenAmount::new(*amount, self.decimals)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// What [`execute_plan`] created.
+#[derive(Debug, Clone)]
+pub struct LaunchResult {
+    pub authority_keypair: Keypair,
+    pub mint_pubkey: Pubkey,
+    /// Each recipient owner with its token account, in plan order.
+    pub accounts: Vec<(Pubkey, Pubkey)>,
+}
+
+/// Plans a launch minting `total_supply` across `recipients`. Fails with
+/// [`TokenError::DuplicateRecipient`] if an owner is listed twice, or
+/// [`TokenError::AllocationMismatch`] unless the amounts add up to exactly
+/// `total_supply`.
+pub fn plan_launch(decimals: u8, total_supply: u64, recipients: &[(Pubkey, u64)]) -> Result<LaunchPlan, TokenError> {
+    let mut allocated = 0u64;
+    for (i, (owner, amount)) in recipients.iter().enumerate() {
+        if recipients[..i].iter().any(|(seen, _)| seen == owner) {
+            return Err(TokenError::DuplicateRecipient { recipient: *owner });
+        }
+        allocated = math::checked_add(allocated, *amount)?;
+    }
+    if allocated != total_supply {
+        return Err(TokenError::AllocationMismatch { total_supply, allocated });
+    }
+
+    Ok(LaunchPlan { decimals, total_supply, recipients: recipients.to_vec() })
+}
+
+/// Carries out `plan`: creates the mint under a new faucet-funded authority,
+/// which also pays for every recipient's token account, then mints each
+/// recipient's amount. The mint is new, so every account is created fresh.
+pub fn execute_plan(client: &ArchRpcClient, plan: &LaunchPlan) -> Result<LaunchResult, Box<dyn std::error::Error>> {
+    let (authority_keypair, mint_pubkey) = create_token_mint_with_options(client, plan.decimals, None)?;
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    ensure_payer_balance(authority_pubkey, estimate_batch_rent(client, plan.recipients.len(), Account::LEN)?)?;
+
+    let mut accounts = Vec::with_capacity(plan.recipients.len());
+    for (owner, amount) in &plan.recipients {
+        let token_account = create_token_account3(client, &mint_pubkey, owner, authority_keypair)?;
+        if *amount > 0 {
+            mint_tokens(client, &mint_pubkey, &token_account, &authority_pubkey, authority_keypair, *amount)?;
+        }
+        accounts.push((*owner, token_account));
+    }
+
+    emit!(
+        "🚀 Launched {} with {} tokens across {} recipients",
+        log_pubkey(&mint_pubkey),
+        TokenAmount::new(plan.total_supply, plan.decimals),
+        accounts.len()
+    );
+    Ok(LaunchResult { authority_keypair, mint_pubkey, accounts })
+}
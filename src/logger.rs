@@ -0,0 +1,115 @@
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// How user-facing output is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Lines as written, emoji included.
+    #[default]
+    Emoji,
+    /// Lines with their leading emoji stripped.
+    Plain,
+    /// One JSON object per line: `{"icon": "🪙", "message": "Minted 5 tokens"}`.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "emoji" => Ok(LogFormat::Emoji),
+            "plain" => Ok(LogFormat::Plain),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("Unknown log format '{}': expected emoji, plain or json", other)),
+        }
+    }
+}
+
+/// Environment variable read for the format when `--log-format` isn't given.
+pub const LOG_FORMAT_ENV: &str = "TOKEN_LOG_FORMAT";
+
+static FORMAT: AtomicU8 = AtomicU8::new(LogFormat::Emoji as u8);
+
+/// Routes every user-facing line through the process-wide [`LogFormat`].
+/// Use the [`emit!`] macro rather than calling it directly.
+pub struct Logger;
+
+impl Logger {
+    pub fn set_format(format: LogFormat) {
+        FORMAT.store(format as u8, Ordering::Relaxed);
+    }
+
+    pub fn format() -> LogFormat {
+        match FORMAT.load(Ordering::Relaxed) {
+            x if x == LogFormat::Plain as u8 => LogFormat::Plain,
+            x if x == LogFormat::Json as u8 => LogFormat::Json,
+            _ => LogFormat::Emoji,
+        }
+    }
+
+    /// Prints `text` to stdout in the current format, one event per line.
+    pub fn info(text: &str) {
+        for line in render(Self::format(), text) {
+            println!("{}", line);
+        }
+    }
+
+    /// Like [`info`](Self::info), but to stderr.
+    pub fn error(text: &str) {
+        for line in render(Self::format(), text) {
+            eprintln!("{}", line);
+        }
+    }
+}
+
+/// `text` as the lines [`Logger`] would print in `format`. Blank lines are
+/// spacing only and are dropped in JSON.
+pub fn render(format: LogFormat, text: &str) -> Vec<String> {
+    match format {
+        LogFormat::Emoji => vec![text.to_string()],
+        LogFormat::Plain => vec![text.lines().map(|line| split_icon(line).1).collect::<Vec<_>>().join("\n")],
+        LogFormat::Json => text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (icon, message) = split_icon(line);
+                let icon = (!icon.is_empty()).then_some(icon);
+                serde_json::json!({ "icon": icon, "message": message }).to_string()
+            })
+            .collect(),
+    }
+}
+
+/// Splits a line's leading emoji (and the space after it) from the message.
+/// The emoji is the first whitespace-delimited word when it's non-ASCII with
+/// no letters or digits, so messages opening with "Übertrag" stay whole. A
+/// line without one comes back whole, indentation included.
+fn split_icon(line: &str) -> (&str, &str) {
+    let trimmed = line.trim_start();
+    let icon = trimmed.split_whitespace().next().unwrap_or("");
+    if icon.is_empty() || icon.is_ascii() || icon.chars().any(char::is_alphanumeric) {
+        return ("", line);
+    }
+    let message = &trimmed[icon.len()..];
+    (icon, message.strip_prefix(' ').unwrap_or(message))
+}
+
+/// `println!` through the [`Logger`].
+macro_rules! emit {
+    ($($arg:tt)*) => {
+        $crate::logger::Logger::info(&format!($($arg)*))
+    };
+}
+
+/// `eprintln!` through the [`Logger`].
+macro_rules! emit_error {
+    ($($arg:tt)*) => {
+        $crate::logger::Logger::error(&format!($($arg)*))
+    };
+}
+
+pub(crate) use emit;
+pub(crate) use emit_error;
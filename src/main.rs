@@ -1,126 +1,802 @@
-use apl_token::state::{Mint, Account};
-use arch_program::{program_pack::Pack, sanitized::ArchMessage};
-use arch_sdk::{build_and_sign_transaction, generate_new_keypair, ArchRpcClient, Status};
+use apl_token::{instruction::AuthorityType, state::{Mint, Account, Multisig}};
+use arch_program::{instruction::Instruction, program_pack::Pack, sanitized::ArchMessage};
+use arch_sdk::{build_and_sign_transaction, generate_new_keypair, ArchRpcClient, ProcessedTransaction};
 use arch_test_sdk::{
-    constants::{ BITCOIN_NETWORK,NODE1_ADDRESS},
-    helper::{create_and_fund_account_with_faucet, read_account_info, send_transactions_and_wait},
+    constants::NODE1_ADDRESS,
+    helper::{read_account_info, try_read_account_info},
 };
+use amount::TokenAmount;
+use display::log_pubkey;
+use error::TokenError;
 use log::info;
-// const BITCOIN_NETWORK: Network = Network::Testnet;
+use logger::{emit, emit_error};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging
     env_logger::init();
     info!("🚀 Starting test_token program...");
 
+    let cli = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            emit_error!("❌ {}", e);
+            std::process::exit(2);
+        }
+    };
+    let log_format = match (cli.log_format, std::env::var(logger::LOG_FORMAT_ENV)) {
+        (Some(format), _) => format,
+        (None, Ok(value)) => value.parse().unwrap_or_else(|e| {
+            emit_error!("❌ {}: {}", logger::LOG_FORMAT_ENV, e);
+            std::process::exit(2);
+        }),
+        (None, Err(_)) => logger::LogFormat::default(),
+    };
+    logger::Logger::set_format(log_format);
+    display::set_full_pubkeys(cli.full_pubkeys);
+    receipts::set_label(cli.label.as_deref());
+
+    // On Ctrl-C, say what was already sent so an interrupted batch can be reconciled
+    let receipts_path = cli.receipts_path.clone();
+    ctrlc::set_handler(move || {
+        let submitted = receipts::submitted();
+        emit_error!("\n⚠️ Interrupted with {} transactions submitted:", submitted.len());
+        for (txid, settled) in &submitted {
+            emit_error!("  {} ({})", txid, if *settled { "settled" } else { "in flight" });
+        }
+        if let Some(path) = &receipts_path {
+            if let Err(e) = receipts::append_jsonl(path, &receipts::take()) {
+                emit_error!("❌ Failed to write receipts to {}: {}", path, e);
+            }
+        }
+        std::process::exit(130);
+    })?;
+
+    // Read-only commands that don't need the node
+    match &cli.command {
+        cli::Command::Ata { owner, mint } => {
+            emit!("{}", derive_ata(owner, mint));
+            return Ok(());
+        }
+        cli::Command::ListOps => {
+            for op in ops::TokenOp::ALL {
+                emit!("{:<16}{} (signers: {})", op, op.description(), op.required_signers().join(", "));
+            }
+            return Ok(());
+        }
+        cli::Command::Lifecycle | cli::Command::Watch { .. } | cli::Command::VerifyMint { .. } | cli::Command::Bench { .. } => {}
+    }
+
     let client = ArchRpcClient::new(NODE1_ADDRESS);
-    println!("📡 Connected to node: {}", NODE1_ADDRESS);
-    println!("🌐 Using network: {:?}", BITCOIN_NETWORK);
+    emit!("📡 Connected to node: {}", NODE1_ADDRESS);
+    emit!("🌐 Using network: {}", network::NetworkKind::default());
+
+    // Bail out early with a clear message rather than failing mid-lifecycle
+    match check_connection(&client) {
+        Ok(health) => emit!("⛓️ Node synced to block {} ({})", health.block_height, health.best_block_hash),
+        Err(e) => {
+            emit_error!("❌ Node at {} is not reachable: {}", NODE1_ADDRESS, e);
+            std::process::exit(1);
+        }
+    }
+
+    if let cli::Command::Watch { account, interval } = cli.command {
+        return watch_balance(account, interval);
+    }
+    if let cli::Command::VerifyMint { mint, config } = &cli.command {
+        if !mint_config::verify_mint_config(*mint, config)?.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let cli::Command::Bench { count } = cli.command {
+        let report = bench::run_bench(&client, count, network::NetworkKind::default());
+        if let Some(path) = &cli.receipts_path {
+            receipts::append_jsonl(path, &receipts::take())?;
+        }
+        emit!("{}", serde_json::to_string_pretty(&report?)?);
+        return Ok(());
+    }
 
     // Run the complete token lifecycle
-    run_token_lifecycle(&client)?;
+    let result = run_token_lifecycle(&client);
+    if let Some(path) = &cli.receipts_path {
+        receipts::append_jsonl(path, &receipts::take())?;
+    }
+    result?;
 
-    println!("🎉 Token lifecycle completed successfully!");
+    emit!("🎉 Token lifecycle completed successfully!");
     Ok(())
 }
 
-pub fn run_token_lifecycle(client: &ArchRpcClient) -> Result<(), Box<dyn std::error::Error>> {
+pub fn run_token_lifecycle(client: &ArchRpcClient) -> Result<LifecycleResult, Box<dyn std::error::Error>> {
+    run_token_lifecycle_with_options(client, &LifecycleOptions::default())
+}
+
+/// Knobs for [`run_token_lifecycle_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleOptions {
+    /// Time each step and report it in [`LifecycleResult::step_timings`].
+    pub record_timings: bool,
+    /// Sign the lifecycle's transactions against the node's current block
+    /// hash and return them in [`LifecycleResult::bundle`] instead of sending
+    /// anything. See [`bundle_lifecycle`].
+    pub bundle: bool,
+    /// Network to fund from and sign for.
+    pub network: network::NetworkKind,
+}
+
+/// How long one lifecycle step took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepTiming {
+    pub step: &'static str,
+    pub duration: std::time::Duration,
+}
+
+/// What a lifecycle run produced.
+#[derive(Debug, Clone)]
+pub struct LifecycleResult {
+    pub token_mint: arch_program::pubkey::Pubkey,
+    pub user1_token_account: arch_program::pubkey::Pubkey,
+    pub user2_token_account: arch_program::pubkey::Pubkey,
+    /// Mint authority; still holds minting rights after the run.
+    pub authority_keypair: bitcoin::key::Keypair,
+    /// Owner of `user1_token_account`, so the account can be used further.
+    pub user1_keypair: bitcoin::key::Keypair,
+    /// Owner of `user2_token_account`.
+    pub user2_keypair: bitcoin::key::Keypair,
+    /// Empty unless timings were requested.
+    pub step_timings: Vec<StepTiming>,
+    /// Serialized signed transactions, in send order; empty unless a bundle
+    /// was requested.
+    pub bundle: Vec<Vec<u8>>,
+}
+
+/// Records step durations when enabled; otherwise never reads the clock.
+struct StepTimer {
+    enabled: bool,
+    current: Option<(&'static str, std::time::Instant)>,
+    timings: Vec<StepTiming>,
+}
+
+impl StepTimer {
+    fn new(enabled: bool) -> Self {
+        Self { enabled, current: None, timings: Vec::new() }
+    }
+
+    /// Ends the running step, if any, and starts timing `step`.
+    fn start(&mut self, step: &'static str) {
+        if !self.enabled {
+            return;
+        }
+        self.finish_current();
+        self.current = Some((step, std::time::Instant::now()));
+    }
+
+    fn finish(mut self) -> Vec<StepTiming> {
+        self.finish_current();
+        self.timings
+    }
+
+    fn finish_current(&mut self) {
+        if let Some((step, started)) = self.current.take() {
+            self.timings.push(StepTiming { step, duration: started.elapsed() });
+        }
+    }
+}
+
+/// Decimals of the lifecycle's mint. Its amounts are written in base units of
+/// such a token, so it's fixed rather than taken from the network defaults.
+const LIFECYCLE_DECIMALS: u8 = 9;
+
+/// Minted to user1: 1,000 tokens.
+const LIFECYCLE_MINT_AMOUNT: u64 = 1_000_000_000;
+
+/// Moved from user1 to user2: 500 tokens.
+const LIFECYCLE_TRANSFER_AMOUNT: u64 = 500_000_000;
+
+/// Burned from user2: 100 tokens.
+const LIFECYCLE_BURN_AMOUNT: u64 = 100_000_000;
+
+pub fn run_token_lifecycle_with_options(
+    client: &ArchRpcClient,
+    options: &LifecycleOptions,
+) -> Result<LifecycleResult, Box<dyn std::error::Error>> {
+    if options.bundle {
+        return bundle_lifecycle(poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?, options.network);
+    }
+
+    let mut timer = StepTimer::new(options.record_timings);
+    let keys = LifecycleKeys::generate();
+    let [token_mint_pubkey, user1_token_account, user2_token_account] =
+        [keys.mint, keys.account1, keys.account2].map(|keypair| arch_pubkey(&keypair));
+    let mut steps = lifecycle_steps(&keys, |data_len| rent_exempt_minimum(client, data_len))?.into_iter();
+    let mut send_next_step = || -> Result<(), Box<dyn std::error::Error>> {
+        let step = steps.next().ok_or("The lifecycle has no steps left to send")?;
+        send_ixs_on(client, &step.instructions, &step.payer, step.signers, step.action, options.network)?;
+        Ok(())
+    };
+
     // Step 1: Create token mint
-    println!("\n📋 Step 1: Creating token mint...");
-    let (authority_keypair, token_mint_pubkey) = create_token_mint(client)?;
+    emit!("\n📋 Step 1: Creating token mint...");
+    timer.start("create token mint");
+    fund_account_with_faucet_default_on(client, &keys.authority, options.network)?;
+    send_next_step()?;
+    emit!("🎉 Token mint created: {}", log_pubkey(&token_mint_pubkey));
     
     // Step 2: Create user accounts
-    println!("\n👥 Step 2: Creating user accounts...");
-    let (user1_keypair, user1_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
-    let (user2_keypair, user2_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
-    
-    create_and_fund_account_with_faucet(&user1_keypair, BITCOIN_NETWORK);
-    create_and_fund_account_with_faucet(&user2_keypair, BITCOIN_NETWORK);
+    emit!("\n👥 Step 2: Creating user accounts...");
+    timer.start("create user accounts");
+    fund_account_with_faucet_default_on(client, &keys.user1, options.network)?;
+    fund_account_with_faucet_default_on(client, &keys.user2, options.network)?;
 
-    // Step 3: Create token accounts
-    println!("\n💳 Step 3: Creating token accounts...");
-    let user1_token_account = create_token_account(client, token_mint_pubkey, user1_keypair)?;
-    let user2_token_account = create_token_account(client, token_mint_pubkey, user2_keypair)?;
+    // Step 3: Create the recipient's token account; user1's comes with the mint
+    emit!("\n💳 Step 3: Creating token accounts...");
+    timer.start("create token accounts");
+    send_next_step()?;
+    emit!("💳 Token account created: {}", log_pubkey(&user2_token_account));
 
     // Step 4: Mint initial tokens
-    println!("\n🪙 Step 4: Minting initial supply...");
-    let authority_pubkey = arch_program::pubkey::Pubkey::from_slice(
-        &authority_keypair.x_only_public_key().0.serialize()
-    );
-    mint_tokens(client, &token_mint_pubkey, &user1_token_account, &authority_pubkey, authority_keypair, 1_000_000_000)?; // 1,000 tokens (9 decimals)
+    emit!("\n🪙 Step 4: Minting initial supply...");
+    timer.start("mint initial supply");
+    send_next_step()?;
+    emit!("🪙 Minted {} tokens to new account {}", LIFECYCLE_MINT_AMOUNT, log_pubkey(&user1_token_account));
 
     // Step 5: Check balance
-    println!("\n💰 Step 5: Checking balances...");
+    emit!("\n💰 Step 5: Checking balances...");
+    timer.start("check balance");
     let user1_balance = get_token_balance(user1_token_account)?;
-    println!("User1 balance: {} tokens", user1_balance as f64 / 1_000_000_000.0);
+    emit!("User1 balance: {} tokens", TokenAmount::new(user1_balance, LIFECYCLE_DECIMALS));
 
     // Step 6: Transfer tokens
-    println!("\n📤 Step 6: Transferring tokens...");
-    transfer_tokens(client, &user1_token_account, &user2_token_account, &user1_pubkey, user1_keypair, 500_000_000)?; // 500 tokens
+    emit!("\n📤 Step 6: Transferring tokens...");
+    timer.start("transfer tokens");
+    send_next_step()?;
+    emit!("📤 Transferred {} tokens", LIFECYCLE_TRANSFER_AMOUNT);
 
     // Step 7: Check final balances
-    println!("\n🏁 Step 7: Final balances...");
+    emit!("\n🏁 Step 7: Final balances...");
+    timer.start("final balances");
     let user1_final = get_token_balance(user1_token_account)?;
     let user2_final = get_token_balance(user2_token_account)?;
     
-    println!("User1 final balance: {} tokens", user1_final as f64 / 1_000_000_000.0);
-    println!("User2 final balance: {} tokens", user2_final as f64 / 1_000_000_000.0);
+    emit!("User1 final balance: {} tokens", TokenAmount::new(user1_final, LIFECYCLE_DECIMALS));
+    emit!("User2 final balance: {} tokens", TokenAmount::new(user2_final, LIFECYCLE_DECIMALS));
 
     // Step 8: Demonstrate burning tokens
-    println!("\n🔥 Step 8: Burning some tokens...");
-    burn_tokens(client, &user2_token_account, &token_mint_pubkey, &user2_pubkey, user2_keypair, 100_000_000)?; // Burn 100 tokens
+    emit!("\n🔥 Step 8: Burning some tokens...");
+    timer.start("burn tokens");
+    send_next_step()?;
+    burns::session().record(token_mint_pubkey, LIFECYCLE_BURN_AMOUNT);
+    emit!("🔥 Burned {} tokens", LIFECYCLE_BURN_AMOUNT);
     
     let user2_after_burn = get_token_balance(user2_token_account)?;
-    println!("User2 balance after burn: {} tokens", user2_after_burn as f64 / 1_000_000_000.0);
+    emit!("User2 balance after burn: {} tokens", TokenAmount::new(user2_after_burn, LIFECYCLE_DECIMALS));
+
+    let step_timings = timer.finish();
+    for timing in &step_timings {
+        emit!("⏱️ {}: {:?}", timing.step, timing.duration);
+    }
+
+    Ok(LifecycleResult {
+        token_mint: token_mint_pubkey,
+        user1_token_account,
+        user2_token_account,
+        authority_keypair: keys.authority,
+        user1_keypair: keys.user1,
+        user2_keypair: keys.user2,
+        step_timings,
+        bundle: Vec::new(),
+    })
+}
+
+/// Fresh keys for one lifecycle run: the mint authority, the mint, both users
+/// and their token accounts.
+struct LifecycleKeys {
+    authority: bitcoin::key::Keypair,
+    mint: bitcoin::key::Keypair,
+    user1: bitcoin::key::Keypair,
+    user2: bitcoin::key::Keypair,
+    account1: bitcoin::key::Keypair,
+    account2: bitcoin::key::Keypair,
+}
+
+impl LifecycleKeys {
+    fn generate() -> Self {
+        let [authority, mint, user1, user2, account1, account2] =
+            std::array::from_fn(|_| generate_new_keypair(default_network()).0);
+        Self { authority, mint, user1, user2, account1, account2 }
+    }
+}
+
+/// One lifecycle transaction, unsigned.
+struct LifecycleStep {
+    action: &'static str,
+    instructions: Vec<Instruction>,
+    payer: arch_program::pubkey::Pubkey,
+    signers: Vec<bitcoin::key::Keypair>,
+}
+
+/// Every transaction the lifecycle sends, in order, with `rent` giving the
+/// lamports for an account of a given size. The live run sends these and
+/// [`sign_lifecycle`] signs them, so a bundle can't drift from a run.
+fn lifecycle_steps(
+    keys: &LifecycleKeys,
+    rent: impl Fn(usize) -> Result<u64, Box<dyn std::error::Error>>,
+) -> Result<Vec<LifecycleStep>, Box<dyn std::error::Error>> {
+    let build = |step: &str, ix: Result<Instruction, arch_program::program_error::ProgramError>| {
+        ix.map_err(|e| format!("Failed to build {} instruction: {}", step, e))
+    };
+    let [authority, mint, user1, user2, account1, account2] =
+        [keys.authority, keys.mint, keys.user1, keys.user2, keys.account1, keys.account2].map(|keypair| arch_pubkey(&keypair));
+    let create_account = |payer, account, space: usize| -> Result<Instruction, Box<dyn std::error::Error>> {
+        Ok(arch_program::system_instruction::create_account(&payer, &account, rent(space)?, space as u64, &apl_token::id()))
+    };
+
+    Ok(vec![
+        LifecycleStep {
+            action: "create token mint",
+            instructions: vec![
+                create_account(authority, mint, Mint::LEN)?,
+                build("initialize mint", apl_token::instruction::initialize_mint(&apl_token::id(), &mint, &authority, None, LIFECYCLE_DECIMALS))?,
+            ],
+            payer: authority,
+            signers: vec![keys.authority, keys.mint],
+        },
+        LifecycleStep {
+            action: "create token account",
+            instructions: vec![
+                create_account(user2, account2, Account::LEN)?,
+                build("initialize account", apl_token::instruction::initialize_account(&apl_token::id(), &account2, &mint, &user2))?,
+            ],
+            payer: user2,
+            signers: vec![keys.user2, keys.account2],
+        },
+        LifecycleStep {
+            action: "create account and mint tokens",
+            instructions: vec![
+                create_account(user1, account1, Account::LEN)?,
+                build("initialize account", apl_token::instruction::initialize_account(&apl_token::id(), &account1, &mint, &user1))?,
+                build("mint_to", apl_token::instruction::mint_to(&apl_token::id(), &mint, &account1, &authority, &[], LIFECYCLE_MINT_AMOUNT))?,
+            ],
+            payer: user1,
+            signers: vec![keys.user1, keys.account1, keys.authority],
+        },
+        LifecycleStep {
+            action: "transfer tokens",
+            instructions: vec![build(
+                "transfer",
+                apl_token::instruction::transfer(&apl_token::id(), &account1, &account2, &user1, &[], LIFECYCLE_TRANSFER_AMOUNT),
+            )?],
+            payer: user1,
+            signers: vec![keys.user1],
+        },
+        LifecycleStep {
+            action: "burn tokens",
+            instructions: vec![build(
+                "burn",
+                apl_token::instruction::burn(&apl_token::id(), &account2, &mint, &user2, &[], LIFECYCLE_BURN_AMOUNT),
+            )?],
+            payer: user2,
+            signers: vec![keys.user2],
+        },
+    ])
+}
+
+/// Builds, signs and sanitizes every transaction [`run_token_lifecycle`]
+/// sends, using throwaway keys and a placeholder blockhash, without touching
+/// the node. Catches instruction-construction regressions in CI without a
+/// faucet. Fails on the first instruction or transaction that doesn't build.
+pub fn validate_lifecycle() -> Result<(), Box<dyn std::error::Error>> {
+    use arch_program::sanitize::Sanitize as _;
+
+    for (step, transaction) in sign_lifecycle("00".repeat(32), network::NetworkKind::default())?.transactions {
+        transaction
+            .sanitize()
+            .map_err(|e| format!("Invalid {} transaction: {:?}", step, e))?;
+        transaction
+            .check_tx_size_limit()
+            .map_err(|e| format!("Invalid {} transaction: {}", step, e))?;
+    }
 
     Ok(())
 }
 
+/// The lifecycle's transactions with fresh keys and `recent_blockhash`,
+/// signed for `network`, in the order [`run_token_lifecycle`] sends them, as
+/// a [`LifecycleResult`] whose `bundle` holds each one serialized. Nothing
+/// is sent: replaying the bundle also needs the payers funded first, which
+/// the faucet does outside of it.
+pub fn bundle_lifecycle(
+    recent_blockhash: String,
+    network: network::NetworkKind,
+) -> Result<LifecycleResult, Box<dyn std::error::Error>> {
+    let signed = sign_lifecycle(recent_blockhash, network)?;
+    Ok(LifecycleResult {
+        token_mint: signed.mint,
+        user1_token_account: signed.user1_token_account,
+        user2_token_account: signed.user2_token_account,
+        authority_keypair: signed.authority_keypair,
+        user1_keypair: signed.user1_keypair,
+        user2_keypair: signed.user2_keypair,
+        step_timings: Vec::new(),
+        bundle: signed.transactions.iter().map(|(_, transaction)| transaction.serialize()).collect(),
+    })
+}
+
+/// Keys and signed transactions for one offline lifecycle run.
+struct SignedLifecycle {
+    mint: arch_program::pubkey::Pubkey,
+    user1_token_account: arch_program::pubkey::Pubkey,
+    user2_token_account: arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+    user1_keypair: bitcoin::key::Keypair,
+    user2_keypair: bitcoin::key::Keypair,
+    transactions: Vec<(&'static str, arch_sdk::RuntimeTransaction)>,
+}
+
+fn sign_lifecycle(
+    recent_blockhash: String,
+    network: network::NetworkKind,
+) -> Result<SignedLifecycle, Box<dyn std::error::Error>> {
+    let keys = LifecycleKeys::generate();
+    // Offline, so use the minimum rent-exempt balance the node would report
+    let steps = lifecycle_steps(&keys, |_| Ok(arch_program::account::MIN_ACCOUNT_LAMPORTS))?;
+    let transactions = steps
+        .into_iter()
+        .map(|step| {
+            let message = ArchMessage::new(&step.instructions, Some(step.payer), recent_blockhash.clone());
+            (step.action, build_and_sign_transaction(message, step.signers, network.to_bitcoin_network()))
+        })
+        .collect();
+
+    Ok(SignedLifecycle {
+        mint: arch_pubkey(&keys.mint),
+        user1_token_account: arch_pubkey(&keys.account1),
+        user2_token_account: arch_pubkey(&keys.account2),
+        authority_keypair: keys.authority,
+        user1_keypair: keys.user1,
+        user2_keypair: keys.user2,
+        transactions,
+    })
+}
+
 pub fn create_token_mint(client: &ArchRpcClient) -> Result<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey), Box<dyn std::error::Error>> {
+    create_token_mint_with_options(client, network::NetworkKind::default().defaults().decimals, None)
+}
+
+pub fn create_token_mint_with_options(
+    client: &ArchRpcClient,
+    decimals: u8,
+    freeze_authority: Option<&arch_program::pubkey::Pubkey>,
+) -> Result<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey), Box<dyn std::error::Error>> {
     // 1. Create mint authority (you control the token supply)
-    let (authority_keypair, authority_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
-    create_and_fund_account_with_faucet(&authority_keypair, BITCOIN_NETWORK);
+    let (authority_keypair, _, _) = generate_new_keypair(default_network());
+    fund_account_with_faucet_default(client, &authority_keypair)?;
 
     // 2. Create mint account
-    let (token_mint_keypair, token_mint_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+    let (token_mint_keypair, token_mint_pubkey, _) = generate_new_keypair(default_network());
+
+    initialize_new_mint(client, &authority_keypair, token_mint_keypair, decimals, freeze_authority, false)?;
+
+    Ok((authority_keypair, token_mint_pubkey))
+}
+
+/// Creates a mint, a token account owned by its authority, and mints
+/// `initial_supply` into that account. Covers steps 1–4 of the lifecycle for
+/// demos and tests that just need a funded token. Returns the authority
+/// keypair, the mint, and the authority's token account.
+pub fn bootstrap_token(
+    client: &ArchRpcClient,
+    decimals: u8,
+    initial_supply: u64,
+) -> Result<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey, arch_program::pubkey::Pubkey), Box<dyn std::error::Error>> {
+    let (authority_keypair, token_mint_pubkey) = create_token_mint_with_options(client, decimals, None)?;
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+
+    let authority_token_account = create_token_account(client, token_mint_pubkey, authority_keypair)?;
+    if initial_supply > 0 {
+        mint_tokens(client, &token_mint_pubkey, &authority_token_account, &authority_pubkey, authority_keypair, initial_supply)?;
+    }
+
+    Ok((authority_keypair, token_mint_pubkey, authority_token_account))
+}
+
+/// Like [`create_token_mint_with_options`], but also creates a token account
+/// for `recipient_owner` and mints `initial_supply` into it, in one
+/// transaction paid by the new authority. The authority keeps minting rights.
+/// Returns the authority keypair, the mint, and the recipient's token account.
+pub fn create_token_mint_with_supply(
+    client: &ArchRpcClient,
+    decimals: u8,
+    initial_supply: u64,
+    recipient_owner: &arch_program::pubkey::Pubkey,
+) -> Result<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey, arch_program::pubkey::Pubkey), Box<dyn std::error::Error>> {
+    let (authority_keypair, mint_pubkey) = create_token_mint_with_options(client, decimals, None)?;
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    let (token_account_keypair, token_account, _) = generate_new_keypair(default_network());
+    let account_size = get_token_account_size(client, mint_pubkey)?;
+
+    let mut instructions = vec![
+        create_token_program_account(
+            client,
+            &authority_pubkey,
+            &token_account,
+            rent_exempt_minimum(client, account_size as usize)?,
+            account_size,
+        )?,
+        apl_token::instruction::initialize_account(&apl_token::id(), &token_account, &mint_pubkey, recipient_owner)?,
+    ];
+    if initial_supply > 0 {
+        instructions.push(apl_token::instruction::mint_to(
+            &apl_token::id(),
+            &mint_pubkey,
+            &token_account,
+            &authority_pubkey,
+            &[],
+            initial_supply,
+        )?);
+    }
+    send_ixs(
+        client,
+        &instructions,
+        &authority_pubkey,
+        vec![authority_keypair, token_account_keypair],
+        "create mint with supply",
+    )?;
+
+    emit!(
+        "🪙 Created {} with an initial supply of {}",
+        log_pubkey(&mint_pubkey),
+        TokenAmount::new(initial_supply, decimals)
+    );
+    Ok((authority_keypair, mint_pubkey, token_account))
+}
+
+/// Outcome of [`launch_fixed_supply`].
+#[derive(Debug, Clone)]
+pub struct LaunchResult {
+    pub mint_pubkey: arch_program::pubkey::Pubkey,
+    /// Recipient's token account, holding the whole supply.
+    pub token_account: arch_program::pubkey::Pubkey,
+    pub supply: u64,
+    /// Read back from the mint after launch; always `None` on success.
+    pub mint_authority: Option<arch_program::pubkey::Pubkey>,
+    /// The former mint authority, which paid for the launch. It no longer
+    /// holds any role on the mint.
+    pub payer_keypair: bitcoin::key::Keypair,
+}
+
+/// Launches a fixed-supply token: creates a mint with `decimals` and no
+/// freeze authority, then in one transaction creates `recipient_owner`'s
+/// token account, mints `total_supply` into it and removes the mint
+/// authority so no more can ever be minted. The mint is read back to confirm.
+pub fn launch_fixed_supply(
+    client: &ArchRpcClient,
+    decimals: u8,
+    total_supply: u64,
+    recipient_owner: &arch_program::pubkey::Pubkey,
+) -> Result<LaunchResult, Box<dyn std::error::Error>> {
+    let (authority_keypair, mint_pubkey) = create_token_mint_with_options(client, decimals, None)?;
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    let (token_account_keypair, token_account, _) = generate_new_keypair(default_network());
+    let account_size = get_token_account_size(client, mint_pubkey)?;
+
+    let instructions = [
+        create_token_program_account(
+            client,
+            &authority_pubkey,
+            &token_account,
+            rent_exempt_minimum(client, account_size as usize)?,
+            account_size,
+        )?,
+        apl_token::instruction::initialize_account(&apl_token::id(), &token_account, &mint_pubkey, recipient_owner)?,
+        apl_token::instruction::mint_to(&apl_token::id(), &mint_pubkey, &token_account, &authority_pubkey, &[], total_supply)?,
+        apl_token::instruction::set_authority(
+            &apl_token::id(),
+            &mint_pubkey,
+            None,                   // Nobody can mint after this
+            AuthorityType::MintTokens,
+            &authority_pubkey,
+            &[],
+        )?,
+    ];
+    send_ixs(
+        client,
+        &instructions,
+        &authority_pubkey,
+        vec![authority_keypair, token_account_keypair],
+        "launch fixed supply",
+    )?;
+
+    let mint = read_mint(client, mint_pubkey)?;
+    let mint_authority: Option<arch_program::pubkey::Pubkey> = mint.mint_authority.into();
+    if mint_authority.is_some() || mint.supply != total_supply {
+        return Err(format!(
+            "Launch of {} did not stick: supply {} (expected {}), mint authority {:?}",
+            mint_pubkey, mint.supply, total_supply, mint_authority
+        )
+        .into());
+    }
+
+    emit!("🚀 Launched {} with a fixed supply of {}", log_pubkey(&mint_pubkey), TokenAmount::new(total_supply, decimals));
+    Ok(LaunchResult {
+        mint_pubkey,
+        token_account,
+        supply: mint.supply,
+        mint_authority,
+        payer_keypair: authority_keypair,
+    })
+}
+
+/// Parameters for one mint created by [`create_token_mints`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MintSpec {
+    pub decimals: u8,
+    pub freeze_authority: Option<arch_program::pubkey::Pubkey>,
+}
+
+/// Creates one mint per entry of `specs` under a single authority, funded
+/// once from the faucet and paying for every mint. The mints are packed as
+/// many to a transaction as fit. Returns
+/// `(authority_keypair, mint)` pairs in `specs` order; the keypair is the
+/// same for all of them.
+pub fn create_token_mints(
+    client: &ArchRpcClient,
+    specs: &[MintSpec],
+) -> Result<Vec<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey)>, Box<dyn std::error::Error>> {
+    let (authority_keypair, authority_pubkey, _) = generate_new_keypair(default_network());
+    fund_account_with_faucet_default(client, &authority_keypair)?;
+    let rent = rent_exempt_minimum(client, Mint::LEN)?;
+
+    let mut mints = Vec::with_capacity(specs.len());
+    let mut groups = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let (token_mint_keypair, token_mint_pubkey, _) = generate_new_keypair(default_network());
+
+        groups.push(vec![
+            create_token_program_account(
+                client,
+                &authority_pubkey,
+                &token_mint_pubkey,
+                rent,
+                Mint::LEN as u64,
+            )?,
+            apl_token::instruction::initialize_mint(
+                &apl_token::id(),
+                &token_mint_pubkey,
+                &authority_pubkey,
+                spec.freeze_authority.as_ref(),
+                spec.decimals,
+            )?,
+        ]);
+        mints.push((token_mint_keypair, token_mint_pubkey));
+    }
+
+    // Only sizes the chunks; each send fetches its own hash
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+    let mut created = Vec::with_capacity(specs.len());
+    for (_, range) in batch::chunk_instruction_groups(groups.clone(), Some(authority_pubkey), &recent_blockhash)? {
+        let signers = std::iter::once(authority_keypair)
+            .chain(mints[range.clone()].iter().map(|(token_mint_keypair, _)| *token_mint_keypair))
+            .collect();
+        send_ixs(client, &groups[range.clone()].concat(), &authority_pubkey, signers, "create token mints")?;
+        created.extend(mints[range].iter().map(|(_, token_mint_pubkey)| (authority_keypair, *token_mint_pubkey)));
+    }
+
+    emit!("🎉 Created {} token mints", created.len());
+    Ok(created)
+}
+
+/// Same as [`create_token_mint`], but initializes with `InitializeMint2`. On
+/// APL token 0.5 both instructions take only the mint account (there is no
+/// rent sysvar to drop), so this is kept for parity with SPL tooling.
+pub fn create_token_mint2(client: &ArchRpcClient) -> Result<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey), Box<dyn std::error::Error>> {
+    let (authority_keypair, _, _) = generate_new_keypair(default_network());
+    fund_account_with_faucet_default(client, &authority_keypair)?;
+
+    let (token_mint_keypair, token_mint_pubkey, _) = generate_new_keypair(default_network());
+
+    initialize_new_mint(client, &authority_keypair, token_mint_keypair, network::NetworkKind::default().defaults().decimals, None, true)?;
+
+    Ok((authority_keypair, token_mint_pubkey))
+}
+
+/// Creates a mint at `token_mint_keypair`'s address (a vanity or pre-agreed
+/// one) with `authority_keypair` as mint authority and payer, instead of
+/// generating both.
+pub fn create_token_mint_with_keypair(
+    client: &ArchRpcClient,
+    token_mint_keypair: bitcoin::key::Keypair,
+    authority_keypair: &bitcoin::key::Keypair,
+    decimals: u8,
+    freeze_authority: Option<&arch_program::pubkey::Pubkey>,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let token_mint_pubkey = arch_pubkey(&token_mint_keypair);
+
+    fund_account_with_faucet_default(client, authority_keypair)?;
+    initialize_new_mint(client, authority_keypair, token_mint_keypair, decimals, freeze_authority, false)?;
+
+    Ok(token_mint_pubkey)
+}
+
+/// Creates the mint at `token_mint_keypair`'s address unless it already holds
+/// an initialized mint, in which case that mint is returned untouched. Safe to
+/// call on every run of a setup script. The flag is `true` when a mint was
+/// created by this call.
+pub fn create_token_mint_if_absent(
+    client: &ArchRpcClient,
+    token_mint_keypair: bitcoin::key::Keypair,
+    authority_keypair: &bitcoin::key::Keypair,
+    decimals: u8,
+    freeze_authority: Option<&arch_program::pubkey::Pubkey>,
+) -> Result<(Mint, bool), Box<dyn std::error::Error>> {
+    let token_mint_pubkey = arch_pubkey(&token_mint_keypair);
+
+    match client.read_account_info(token_mint_pubkey) {
+        Ok(account_info) => {
+            if account_info.owner != apl_token::id() {
+                return Err(format!("Account {} exists but is not owned by the token program", token_mint_pubkey).into());
+            }
+            let mint = unpack_mint(token_mint_pubkey, &account_info.data)?;
+            emit!("♻️ Token mint already exists: {}", log_pubkey(&token_mint_pubkey));
+            return Ok((mint, false));
+        }
+        Err(arch_sdk::ArchError::NotFound(_)) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    fund_account_with_faucet_default(client, authority_keypair)?;
+    initialize_new_mint(client, authority_keypair, token_mint_keypair, decimals, freeze_authority, false)?;
+
+    let mint = unpack_mint(token_mint_pubkey, &client.read_account_info(token_mint_pubkey)?.data)?;
+    Ok((mint, true))
+}
+
+fn initialize_new_mint(
+    client: &ArchRpcClient,
+    authority_keypair: &bitcoin::key::Keypair,
+    token_mint_keypair: bitcoin::key::Keypair,
+    decimals: u8,
+    freeze_authority: Option<&arch_program::pubkey::Pubkey>,
+    use_mint2: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let authority_pubkey = arch_pubkey(authority_keypair);
+    let token_mint_pubkey = arch_pubkey(&token_mint_keypair);
 
     // 3. Create the mint account on-chain
-    let create_account_ix = arch_program::system_instruction::create_account(
+    let create_account_ix = create_token_program_account(
+        client,
         &authority_pubkey,       // Payer
         &token_mint_pubkey,      // New account
-        arch_program::account::MIN_ACCOUNT_LAMPORTS, // Rent
+        rent_exempt_minimum(client, Mint::LEN)?, // Rent
         Mint::LEN as u64,        // Space needed
-        &apl_token::id(),        // Owner program
-    );
+    )?;
 
     // 4. Initialize the mint with your token parameters
-    let initialize_mint_ix = apl_token::instruction::initialize_mint(
+    let initialize_mint = if use_mint2 {
+        apl_token::instruction::initialize_mint2
+    } else {
+        apl_token::instruction::initialize_mint
+    };
+    let initialize_mint_ix = initialize_mint(
         &apl_token::id(),
         &token_mint_pubkey,
         &authority_pubkey,       // Mint authority (can create tokens)
-        None,                   // No freeze authority (optional)
-        9,                      // Decimals (9 = like USDC, 0 = whole numbers only)
+        freeze_authority,       // Freeze authority (optional)
+        decimals,               // Decimals (9 = like USDC, 0 = whole numbers only)
     )?;
 
     // 5. Send transaction
-    let transaction = build_and_sign_transaction(
-        ArchMessage::new(
-            &[create_account_ix, initialize_mint_ix],
-            Some(authority_pubkey),
-            client.get_best_block_hash()?,
-        ),
-        vec![authority_keypair, token_mint_keypair],
-        BITCOIN_NETWORK,
-    );
-
-    let processed_txs = send_transactions_and_wait(vec![transaction]);
-    if processed_txs[0].status != Status::Processed {
-        return Err("Failed to create token mint".into());
-    }
+    send_ixs(
+        client,
+        &[create_account_ix, initialize_mint_ix],
+        &authority_pubkey,
+        vec![*authority_keypair, token_mint_keypair],
+        "create token mint",
+    )?;
 
-    println!("🎉 Token mint created: {}", token_mint_pubkey);
-    
-    Ok((authority_keypair, token_mint_pubkey))
+    emit!("🎉 Token mint created: {}", log_pubkey(&token_mint_pubkey));
+    Ok(())
 }
 
 pub fn create_token_account(
@@ -128,22 +804,149 @@ pub fn create_token_account(
     token_mint_pubkey: arch_program::pubkey::Pubkey,
     owner_keypair: bitcoin::key::Keypair,
 ) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let (token_account_keypair, _, _) = generate_new_keypair(default_network());
+    create_token_account_inner(client, token_mint_pubkey, owner_keypair, token_account_keypair, false)
+}
+
+/// Like [`create_token_account`], but at `token_account_keypair`'s address so
+/// the account is known ahead of time. Fails with
+/// [`TokenError::AlreadyInitialized`] if that address already holds a token
+/// account for the mint, e.g. when a setup script is re-run.
+pub fn create_token_account_with_keypair(
+    client: &ArchRpcClient,
+    token_mint_pubkey: arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    token_account_keypair: bitcoin::key::Keypair,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    create_token_account_inner(client, token_mint_pubkey, owner_keypair, token_account_keypair, false)
+}
+
+/// Domain tag mixed into [`derive_seeded_keypair`]'s hash so its keys can't
+/// collide with keys derived from the owner's secret for anything else.
+const SEEDED_ACCOUNT_TAG: &[u8] = b"apl-token-seeded-account";
+
+/// The keypair of `owner_keypair`'s token account for `mint_pubkey` named
+/// `seed` ("savings", "spending", ...). The 0.5 system program has no
+/// `CreateAccountWithSeed` and a program address can't sign its own
+/// creation, so the account key is instead hashed from the owner's secret,
+/// the mint and the seed: the same inputs always give the same address, and
+/// only the owner can derive it.
+pub fn derive_seeded_keypair(
+    owner_keypair: &bitcoin::key::Keypair,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    seed: &str,
+) -> Result<bitcoin::key::Keypair, Box<dyn std::error::Error>> {
+    use bitcoin::hashes::{sha256, Hash};
+
+    let mut preimage = SEEDED_ACCOUNT_TAG.to_vec();
+    preimage.extend_from_slice(&owner_keypair.secret_bytes());
+    preimage.extend_from_slice(mint_pubkey.as_ref());
+    preimage.extend_from_slice(seed.as_bytes());
+    let secret = sha256::Hash::hash(&preimage);
+    Ok(bitcoin::key::Keypair::from_seckey_slice(&bitcoin::secp256k1::Secp256k1::new(), secret.as_byte_array())?)
+}
+
+/// Address of the account [`create_token_account_with_seed`] creates for
+/// `seed`, whether or not it exists yet.
+pub fn derive_seeded_address(
+    owner_keypair: &bitcoin::key::Keypair,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    seed: &str,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    Ok(arch_pubkey(&derive_seeded_keypair(owner_keypair, mint_pubkey, seed)?))
+}
+
+/// Like [`create_token_account_with_keypair`], at the address
+/// [`derive_seeded_keypair`] gives for `seed`, so an owner can keep several
+/// named accounts for one mint and find them again without storing their
+/// addresses. Fails with [`TokenError::AlreadyInitialized`] if the seed's
+/// account already exists.
+pub fn create_token_account_with_seed(
+    client: &ArchRpcClient,
+    token_mint_pubkey: arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    seed: &str,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let token_account_keypair = derive_seeded_keypair(&owner_keypair, &token_mint_pubkey, seed)?;
+    create_token_account_inner(client, token_mint_pubkey, owner_keypair, token_account_keypair, false)
+}
+
+/// Like [`create_token_account`], but also sends `initialize_immutable_owner`.
+/// This adds no protection on APL token 0.5.2: the program only logs the
+/// instruction, so [`set_account_owner`] still succeeds on these accounts.
+pub fn create_token_account_immutable(
+    client: &ArchRpcClient,
+    token_mint_pubkey: arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let (token_account_keypair, _, _) = generate_new_keypair(default_network());
+    create_token_account_inner(client, token_mint_pubkey, owner_keypair, token_account_keypair, true)
+}
+
+/// Creates a token account for `owner_pubkey` with `initialize_account3`,
+/// which names the owner in the instruction data and needs no rent sysvar.
+/// `payer_keypair` pays for it; the owner doesn't sign, so accounts can be
+/// opened for owners who aren't present.
+pub fn create_token_account3(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    payer_keypair: bitcoin::key::Keypair,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+    let (token_account_keypair, token_account_pubkey, _) = generate_new_keypair(default_network());
+    let account_size = get_token_account_size(client, *mint_pubkey)?;
+
+    let instructions = [
+        create_token_program_account(
+            client,
+            &payer_pubkey,
+            &token_account_pubkey,
+            rent_exempt_minimum(client, account_size as usize)?,
+            account_size,
+        )?,
+        apl_token::instruction::initialize_account3(&apl_token::id(), &token_account_pubkey, mint_pubkey, owner_pubkey)?,
+    ];
+    send_ixs(client, &instructions, &payer_pubkey, vec![payer_keypair, token_account_keypair], "create token account")?;
+
+    emit!("💳 Token account created: {} for {}", log_pubkey(&token_account_pubkey), log_pubkey(owner_pubkey));
+    Ok(token_account_pubkey)
+}
+
+fn create_token_account_inner(
+    client: &ArchRpcClient,
+    token_mint_pubkey: arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    token_account_keypair: bitcoin::key::Keypair,
+    immutable_owner: bool,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
     
-    let owner_pubkey = arch_program::pubkey::Pubkey::from_slice(
-        &owner_keypair.x_only_public_key().0.serialize()
-    );
+    let owner_pubkey = arch_pubkey(&owner_keypair);
+    let token_account_pubkey = arch_pubkey(&token_account_keypair);
 
-    // 1. Create account keypair
-    let (token_account_keypair, token_account_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+    // 1. Refuse to re-initialize an existing token account for this mint
+    if let Ok(account_info) = client.read_account_info(token_account_pubkey) {
+        if account_info.owner == apl_token::id() {
+            if let Ok(existing) = unpack_token_account(token_account_pubkey, &account_info.data) {
+                if existing.mint == token_mint_pubkey {
+                    return Err(TokenError::AlreadyInitialized {
+                        account: token_account_pubkey,
+                        mint: token_mint_pubkey,
+                    }.into());
+                }
+            }
+        }
+    }
 
     // 2. Create account on-chain
-    let create_account_ix = arch_program::system_instruction::create_account(
+    let account_size = get_token_account_size(client, token_mint_pubkey)?;
+    let create_account_ix = create_token_program_account(
+        client,
         &owner_pubkey,
         &token_account_pubkey,
-        arch_program::account::MIN_ACCOUNT_LAMPORTS,
-        apl_token::state::Account::LEN as u64,
-        &apl_token::id(),
-    );
+        rent_exempt_minimum(client, account_size as usize)?,
+        account_size,
+    )?;
 
     // 3. Initialize token account
     let initialize_account_ix = apl_token::instruction::initialize_account(
@@ -153,24 +956,120 @@ pub fn create_token_account(
         &owner_pubkey,           // Who owns this account
     )?;
 
+    // Immutable owner must be set up before the account is initialized
+    let mut instructions = vec![create_account_ix];
+    if immutable_owner {
+        instructions.push(apl_token::instruction::initialize_immutable_owner(
+            &apl_token::id(),
+            &token_account_pubkey,
+        )?);
+    }
+    instructions.push(initialize_account_ix);
+
     // 4. Send transaction
-    let transaction = build_and_sign_transaction(
-        ArchMessage::new(
-            &[create_account_ix, initialize_account_ix],
-            Some(owner_pubkey),
-            client.get_best_block_hash()?,
-        ),
+    send_ixs(
+        client,
+        &instructions,
+        &owner_pubkey,
         vec![owner_keypair, token_account_keypair],
-        BITCOIN_NETWORK,
-    );
+        "create token account",
+    )?;
 
-    let processed_txs = send_transactions_and_wait(vec![transaction]);
-    if processed_txs[0].status != Status::Processed {
-        return Err("Failed to create token account".into());
+    emit!("💳 Token account created: {}", log_pubkey(&token_account_pubkey));
+    Ok(token_account_pubkey)
+}
+
+/// Creates `count` token accounts for fresh owner keypairs, with
+/// `payer_keypair` covering rent for all of them so only the payer needs
+/// faucet funds. As many accounts as fit share a transaction. Returns
+/// `(owner_keypair, token_account)` pairs.
+pub fn create_token_accounts(
+    client: &ArchRpcClient,
+    token_mint_pubkey: arch_program::pubkey::Pubkey,
+    count: usize,
+    payer_keypair: bitcoin::key::Keypair,
+) -> Result<Vec<(bitcoin::key::Keypair, arch_program::pubkey::Pubkey)>, Box<dyn std::error::Error>> {
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+    let account_size = get_token_account_size(client, token_mint_pubkey)?;
+    let rent = rent_exempt_minimum(client, account_size as usize)?;
+
+    let mut accounts = Vec::with_capacity(count);
+    let mut groups = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(default_network());
+        let (token_account_keypair, token_account_pubkey, _) = generate_new_keypair(default_network());
+
+        groups.push(vec![
+            create_token_program_account(
+                client,
+                &payer_pubkey,       // Payer covers rent, not the owner
+                &token_account_pubkey,
+                rent,
+                account_size,
+            )?,
+            apl_token::instruction::initialize_account(
+                &apl_token::id(),
+                &token_account_pubkey,
+                &token_mint_pubkey,
+                &owner_pubkey,
+            )?,
+        ]);
+        accounts.push((owner_keypair, token_account_keypair, token_account_pubkey));
     }
 
-    println!("💳 Token account created: {}", token_account_pubkey);
-    Ok(token_account_pubkey)
+    // Only sizes the chunks; each send fetches its own hash
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+    let mut created = Vec::with_capacity(count);
+    for (_, range) in batch::chunk_instruction_groups(groups.clone(), Some(payer_pubkey), &recent_blockhash)? {
+        let signers = std::iter::once(payer_keypair)
+            .chain(accounts[range.clone()].iter().map(|(_, token_account_keypair, _)| *token_account_keypair))
+            .collect();
+        send_ixs(client, &groups[range.clone()].concat(), &payer_pubkey, signers, "create token accounts")?;
+        created.extend(accounts[range].iter().map(|(owner_keypair, _, token_account)| (*owner_keypair, *token_account)));
+    }
+
+    emit!("💳 Created {} token accounts", created.len());
+    Ok(created)
+}
+
+/// Creates an `m`-of-n multisig over `signers`, paid for by `payer_keypair`.
+/// The returned address can own token accounts or hold mint/freeze authority.
+pub fn create_multisig(
+    client: &ArchRpcClient,
+    payer_keypair: bitcoin::key::Keypair,
+    signers: &[arch_program::pubkey::Pubkey],
+    m: u8,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+
+    let (multisig_keypair, multisig_pubkey, _) = generate_new_keypair(default_network());
+
+    let create_account_ix = create_token_program_account(
+        client,
+        &payer_pubkey,
+        &multisig_pubkey,
+        rent_exempt_minimum(client, Multisig::LEN)?,
+        Multisig::LEN as u64,
+    )?;
+
+    let signer_refs: Vec<&arch_program::pubkey::Pubkey> = signers.iter().collect();
+    let initialize_multisig_ix = apl_token::instruction::initialize_multisig(
+        &apl_token::id(),
+        &multisig_pubkey,
+        &signer_refs,
+        m,                      // Signatures required
+    )?;
+
+    send_ixs(
+        client,
+        &[create_account_ix, initialize_multisig_ix],
+        &payer_pubkey,
+        vec![payer_keypair, multisig_keypair],
+        "create multisig",
+    )?;
+
+    emit!("👥 {}-of-{} multisig created: {}", m, signers.len(), log_pubkey(&multisig_pubkey));
+    Ok(multisig_pubkey)
 }
 
 pub fn mint_tokens(
@@ -181,6 +1080,78 @@ pub fn mint_tokens(
     authority_keypair: bitcoin::key::Keypair,
     amount: u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    mint_tokens_detailed(client, mint_pubkey, account_pubkey, authority_pubkey, authority_keypair, amount)?;
+    Ok(())
+}
+
+/// Same as [`mint_tokens`], but returns the processed transaction so callers can
+/// inspect its logs and status.
+pub fn mint_tokens_detailed(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    account_pubkey: &arch_program::pubkey::Pubkey,
+    authority_pubkey: &arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    mint_tokens_with_options(
+        client,
+        mint_pubkey,
+        account_pubkey,
+        authority_pubkey,
+        authority_keypair,
+        amount,
+        &MintOptions::default(),
+    )
+}
+
+/// Knobs for [`mint_tokens_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct MintOptions {
+    /// Reject mints that would overflow the supply with
+    /// [`TokenError::SupplyOverflow`], then re-read the mint afterwards and
+    /// fail unless its supply grew by exactly `amount`. Costs two extra
+    /// account reads, so it's off by default.
+    pub verify: bool,
+    /// Check up front that `authority_pubkey` is the mint authority, failing
+    /// with [`TokenError::WrongAuthority`] instead of a program rejection.
+    pub check_authority: bool,
+    /// Re-fetch the processed transaction and compare it to what was signed;
+    /// see [`verify_processed_transaction`].
+    pub verify_inclusion: bool,
+    /// Check up front that `account_pubkey` is a token account of
+    /// `mint_pubkey`, failing with [`TokenError::WrongMint`] instead of a
+    /// program rejection.
+    pub check_mint: bool,
+    /// Network to sign for.
+    pub network: network::NetworkKind,
+}
+
+pub fn mint_tokens_with_options(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    account_pubkey: &arch_program::pubkey::Pubkey,
+    authority_pubkey: &arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+    amount: u64,
+    options: &MintOptions,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    if options.check_authority {
+        verify_mint_authority(client, *mint_pubkey, authority_pubkey)?;
+    }
+    if options.check_mint {
+        check_account_mint(client, account_pubkey, mint_pubkey)?;
+    }
+
+    let supply_before = if options.verify {
+        let supply = read_mint(client, *mint_pubkey)?.supply;
+        if math::checked_add(supply, amount).is_err() {
+            return Err(TokenError::SupplyOverflow { mint: *mint_pubkey, supply, amount }.into());
+        }
+        Some(supply)
+    } else {
+        None
+    };
 
     // Create mint instruction
     let mint_ix = apl_token::instruction::mint_to(
@@ -193,23 +1164,56 @@ pub fn mint_tokens(
     )?;
 
     // Send transaction
-    let transaction = build_and_sign_transaction(
-        ArchMessage::new(
-            &[mint_ix],
-            Some(*authority_pubkey),
-            client.get_best_block_hash()?,
-        ),
+    let processed_tx = send_ixs_with(
+        client,
+        &[mint_ix],
+        authority_pubkey,
         vec![authority_keypair],
-        BITCOIN_NETWORK,
-    );
+        "mint tokens",
+        options.network,
+        options.verify_inclusion,
+    )?;
 
-    let processed_txs = send_transactions_and_wait(vec![transaction]);
-    if processed_txs[0].status != Status::Processed {
-        return Err("Failed to mint tokens".into());
+    // A processed status alone doesn't prove the supply moved
+    if let Some(supply_before) = supply_before {
+        let supply_after = read_mint(client, *mint_pubkey)?.supply;
+        if math::checked_add(supply_before, amount)? != supply_after {
+            return Err(format!(
+                "Mint supply mismatch: expected {} + {} after minting, found {}",
+                supply_before, amount, supply_after
+            )
+            .into());
+        }
     }
 
-    println!("🪙 Minted {} tokens", amount);
-    Ok(())
+    emit!("🪙 Minted {} tokens", amount);
+    Ok(processed_tx)
+}
+
+/// Mints into `account_pubkey` whatever brings `mint_pubkey`'s supply up to
+/// exactly `target_supply`, and returns the amount minted (0 if it's already
+/// there). Fails with [`TokenError::SupplyAboveTarget`] if the supply already
+/// exceeds the target, since minting can't lower it.
+pub fn mint_to_supply(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    account_pubkey: &arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+    target_supply: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+
+    let supply = read_mint(client, *mint_pubkey)?.supply;
+    let amount = math::checked_sub(target_supply, supply).map_err(|_| TokenError::SupplyAboveTarget {
+        mint: *mint_pubkey,
+        supply,
+        target: target_supply,
+    })?;
+    if amount > 0 {
+        mint_tokens(client, mint_pubkey, account_pubkey, &authority_pubkey, authority_keypair, amount)?;
+    }
+
+    Ok(amount)
 }
 
 pub fn transfer_tokens(
@@ -220,6 +1224,100 @@ pub fn transfer_tokens(
     owner_keypair: bitcoin::key::Keypair,
     amount: u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    transfer_tokens_detailed(client, from_account, to_account, owner_pubkey, owner_keypair, amount)?;
+    Ok(())
+}
+
+/// Same as [`transfer_tokens`], but returns the processed transaction so callers can
+/// inspect its logs and status.
+pub fn transfer_tokens_detailed(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    transfer_tokens_with_options(
+        client,
+        from_account,
+        to_account,
+        owner_pubkey,
+        owner_keypair,
+        amount,
+        &TransferOptions::default(),
+    )
+}
+
+/// Same as [`transfer_tokens`], but returns the transfer as an
+/// [`events::TransferEvent`] for indexers. Reads `from_account` once for the
+/// mint, which a plain transfer doesn't carry.
+pub fn transfer_tokens_with_event(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<events::TransferEvent, Box<dyn std::error::Error>> {
+    let mint_pubkey = unpack_token_account(*from_account, &client.read_account_info(*from_account)?.data)?.mint;
+    let processed_tx = transfer_tokens_detailed(client, from_account, to_account, owner_pubkey, owner_keypair, amount)?;
+    Ok(events::TransferEvent::from_processed(mint_pubkey, &processed_tx).ok_or("Processed transaction carries no transfer")?)
+}
+
+/// Knobs for [`transfer_tokens_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct TransferOptions {
+    /// Let `from_account == to_account` through. Such a transfer is almost
+    /// always a bug, so it's rejected with [`TokenError::SelfTransfer`] unless
+    /// this is set.
+    pub allow_self_transfer: bool,
+    /// Re-fetch the processed transaction and compare it to what was signed;
+    /// see [`verify_processed_transaction`].
+    pub verify_inclusion: bool,
+    /// Read the mint's supply before and after, failing with
+    /// [`TokenError::SupplyChanged`] if the transfer altered it.
+    pub verify_supply: bool,
+    /// Check both accounts hold the same mint with [`assert_same_mint`]
+    /// before sending. The program rejects cross-mint transfers anyway; this
+    /// fails earlier with a clearer error.
+    pub check_mint: bool,
+    /// Check neither account is frozen with [`is_account_frozen`] before
+    /// sending, failing with [`TokenError::AccountFrozen`] naming the frozen one.
+    pub check_frozen: bool,
+    /// Network to sign for.
+    pub network: network::NetworkKind,
+}
+
+pub fn transfer_tokens_with_options(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+    options: &TransferOptions,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    if from_account == to_account && !options.allow_self_transfer {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
+    }
+    if options.check_mint {
+        assert_same_mint(*from_account, *to_account)?;
+    }
+    if options.check_frozen {
+        for account in [from_account, to_account] {
+            if is_account_frozen(*account)? {
+                return Err(TokenError::AccountFrozen { account: *account }.into());
+            }
+        }
+    }
+
+    let supply_before = if options.verify_supply {
+        let mint_pubkey = unpack_token_account(*from_account, &client.read_account_info(*from_account)?.data)?.mint;
+        Some((mint_pubkey, read_mint(client, mint_pubkey)?.supply))
+    } else {
+        None
+    };
 
     // Create transfer instruction
     let transfer_ix = apl_token::instruction::transfer(
@@ -232,25 +1330,355 @@ pub fn transfer_tokens(
     )?;
 
     // Send transaction
-    let transaction = build_and_sign_transaction(
-        ArchMessage::new(
-            &[transfer_ix],
-            Some(*owner_pubkey),
-            client.get_best_block_hash()?,
-        ),
+    let processed_tx = send_ixs_with(
+        client,
+        &[transfer_ix],
+        owner_pubkey,
         vec![owner_keypair],
-        BITCOIN_NETWORK,
-    );
+        "transfer tokens",
+        options.network,
+        options.verify_inclusion,
+    )?;
+
+    if let Some((mint, before)) = supply_before {
+        let after = read_mint(client, mint)?.supply;
+        if after != before {
+            return Err(TokenError::SupplyChanged { mint, before, after }.into());
+        }
+    }
+
+    emit!("📤 Transferred {} tokens", amount);
+    Ok(processed_tx)
+}
+
+/// Transfers like [`transfer_tokens`], but with `fee_payer_keypair` paying
+/// for the transaction while the owner only signs to authorize the transfer.
+/// Lets a relayer move tokens for an owner with no lamports of its own.
+pub fn transfer_tokens_with_payer(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    fee_payer_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    if from_account == to_account {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
+    }
+    let fee_payer_pubkey = arch_pubkey(&fee_payer_keypair);
+
+    let transfer_ix = apl_token::instruction::transfer(
+        &apl_token::id(),
+        from_account,
+        to_account,
+        owner_pubkey,
+        &[],
+        amount,
+    )?;
+
+    let processed_tx = send_ixs(
+        client,
+        &[transfer_ix],
+        &fee_payer_pubkey,
+        vec![fee_payer_keypair, owner_keypair],
+        "transfer tokens",
+    )?;
+
+    emit!("📤 Transferred {} tokens (fees paid by {})", amount, log_pubkey(&fee_payer_pubkey));
+    Ok(processed_tx)
+}
+
+/// Transfers like [`transfer_tokens`], then re-reads both accounts and checks
+/// each moved by exactly `amount`. Returns the post-transfer balances as
+/// `(from_after, to_after)`, or [`TokenError::UnexpectedBalance`] naming the
+/// first account that doesn't add up.
+pub fn transfer_verified(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let from_before = get_token_balance(*from_account)?;
+    let to_before = get_token_balance(*to_account)?;
+
+    transfer_tokens(client, from_account, to_account, owner_pubkey, owner_keypair, amount)?;
+
+    let from_after = get_token_balance(*from_account)?;
+    let to_after = get_token_balance(*to_account)?;
+
+    let expected = [
+        (from_account, math::checked_sub(from_before, amount).ok(), from_after),
+        (to_account, math::checked_add(to_before, amount).ok(), to_after),
+    ];
+    for (account, expected, actual) in expected {
+        if expected != Some(actual) {
+            return Err(TokenError::UnexpectedBalance { account: *account, expected, actual }.into());
+        }
+    }
+
+    Ok((from_after, to_after))
+}
+
+/// [`transfer_verified`], returning just the source account's new balance
+/// for a wallet to display.
+pub fn transfer_and_report(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let (from_after, _) = transfer_verified(client, from_account, to_account, owner_pubkey, owner_keypair, amount)?;
+    Ok(from_after)
+}
 
-    let processed_txs = send_transactions_and_wait(vec![transaction]);
-    if processed_txs[0].status != Status::Processed {
-        return Err("Failed to transfer tokens".into());
+/// Transfers a human-readable amount such as `"12.5"`, parsed exactly against
+/// the mint's decimals (see [`TokenAmount::parse`]). Uses `transfer_checked`
+/// so the program also verifies the decimals.
+pub fn transfer_ui(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    ui_amount: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if from_account == to_account {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
     }
 
-    println!("📤 Transferred {} tokens", amount);
+    let decimals = amount::decimals_cache().decimals(client, *mint_pubkey)?;
+    let amount = TokenAmount::parse(ui_amount, decimals)?;
+
+    let transfer_ix = apl_token::instruction::transfer_checked(
+        &apl_token::id(),
+        from_account,
+        mint_pubkey,
+        to_account,
+        owner_pubkey,
+        &[],
+        amount.raw,
+        decimals,
+    )?;
+
+    send_ixs(client, &[transfer_ix], owner_pubkey, vec![owner_keypair], "transfer tokens")?;
+
+    emit!("📤 Transferred {} tokens", amount);
+    Ok(())
+}
+
+/// Mints a human-readable amount such as `"1000"`, parsed exactly against the
+/// mint's decimals like [`transfer_ui`]. Uses `mint_to_checked` so the
+/// program also verifies the decimals.
+pub fn mint_ui(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    account_pubkey: &arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+    ui_amount: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+    let decimals = amount::decimals_cache().decimals(client, *mint_pubkey)?;
+    let amount = TokenAmount::parse(ui_amount, decimals)?;
+
+    let mint_ix = apl_token::instruction::mint_to_checked(
+        &apl_token::id(),
+        mint_pubkey,
+        account_pubkey,
+        &authority_pubkey,
+        &[],
+        amount.raw,
+        decimals,
+    )?;
+
+    send_ixs(client, &[mint_ix], &authority_pubkey, vec![authority_keypair], "mint tokens")?;
+
+    emit!("🪙 Minted {} tokens", amount);
     Ok(())
 }
 
+/// [`transfer_ui`] to `recipient_owner` rather than to a token account: the
+/// amount is parsed against the mint's decimals first, then the recipient's
+/// account is found or created with [`ensure_token_account`] (paid by
+/// `payer_keypair`), then the tokens move with `transfer_checked`. A new
+/// account costs a transaction of its own. Returns the recipient's account.
+pub fn transfer_checked_to_owner(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    recipient_owner: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    payer_keypair: bitcoin::key::Keypair,
+    ui_amount: &str,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let owner_pubkey = arch_pubkey(&owner_keypair);
+    let decimals = amount::decimals_cache().decimals(client, *mint_pubkey)?;
+    let amount = TokenAmount::parse(ui_amount, decimals)?;
+
+    let destination = ensure_token_account(client, mint_pubkey, recipient_owner, payer_keypair)?;
+    if destination == *from_account {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
+    }
+
+    let transfer_ix = apl_token::instruction::transfer_checked(
+        &apl_token::id(),
+        from_account,
+        mint_pubkey,
+        &destination,
+        &owner_pubkey,
+        &[],
+        amount.raw,
+        decimals,
+    )?;
+    send_ixs(client, &[transfer_ix], &owner_pubkey, vec![owner_keypair], "transfer tokens")?;
+
+    emit!("📤 Transferred {} tokens to {}", amount, log_pubkey(&destination));
+    Ok(destination)
+}
+
+/// Transfers `amount` to `recipient_owner`'s token account for `mint_pubkey`,
+/// creating that account first if the recipient has none. APL 0.5 has no
+/// associated token account program, so the destination is found by scanning
+/// the token program's accounts. A new account gets a fresh address, with
+/// `payer_keypair` paying its rent; it is created and funded in one
+/// transaction. Returns the destination token account.
+pub fn transfer_or_create(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    recipient_owner: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    payer_keypair: bitcoin::key::Keypair,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let owner_pubkey = arch_pubkey(&owner_keypair);
+
+    if let Some(destination) = find_token_account(client, recipient_owner, mint_pubkey)? {
+        transfer_tokens(client, from_account, &destination, &owner_pubkey, owner_keypair, amount)?;
+        return Ok(destination);
+    }
+
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+    let (token_account_keypair, token_account_pubkey, _) = generate_new_keypair(default_network());
+    let account_size = get_token_account_size(client, *mint_pubkey)?;
+
+    let instructions = [
+        create_token_program_account(
+            client,
+            &payer_pubkey,
+            &token_account_pubkey,
+            rent_exempt_minimum(client, account_size as usize)?,
+            account_size,
+        )?,
+        apl_token::instruction::initialize_account(
+            &apl_token::id(),
+            &token_account_pubkey,
+            mint_pubkey,
+            recipient_owner,
+        )?,
+        apl_token::instruction::transfer(
+            &apl_token::id(),
+            from_account,
+            &token_account_pubkey,
+            &owner_pubkey,
+            &[],
+            amount,
+        )?,
+    ];
+
+    let mut signers = vec![payer_keypair, token_account_keypair];
+    if owner_pubkey != payer_pubkey {
+        signers.push(owner_keypair);
+    }
+    send_ixs(client, &instructions, &payer_pubkey, signers, "create account and transfer tokens")?;
+
+    emit!("📤 Transferred {} tokens to new account {}", amount, log_pubkey(&token_account_pubkey));
+    Ok(token_account_pubkey)
+}
+
+/// `owner_pubkey`'s token account for `mint_pubkey`, created with
+/// [`create_token_account3`] and paid by `payer_keypair` if the owner has
+/// none yet, so
+/// onboarding scripts can be re-run safely. As in [`mint_to_owner`], an
+/// existing account is found by scan rather than at [`derive_ata`]'s address,
+/// which nothing on 0.5 can create.
+pub fn ensure_token_account(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    payer_keypair: bitcoin::key::Keypair,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    if let Some(token_account) = find_token_account(client, owner_pubkey, mint_pubkey)? {
+        return Ok(token_account);
+    }
+    create_token_account3(client, mint_pubkey, owner_pubkey, payer_keypair)
+}
+
+/// Mints `amount` to `owner_pubkey`'s token account for `mint_pubkey`,
+/// creating the account first if the owner has none. As in
+/// [`transfer_or_create`], there is no associated token account program to
+/// create [`derive_ata`]'s address, so an existing account is found by scan
+/// and a new one gets a fresh address, with `payer_keypair` paying its rent.
+/// A new account is created and funded in one transaction. Returns the
+/// owner's token account.
+pub fn mint_to_owner(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+    payer_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let authority_pubkey = arch_pubkey(&authority_keypair);
+
+    if let Some(token_account) = find_token_account(client, owner_pubkey, mint_pubkey)? {
+        mint_tokens(client, mint_pubkey, &token_account, &authority_pubkey, authority_keypair, amount)?;
+        return Ok(token_account);
+    }
+
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+    let (token_account_keypair, token_account_pubkey, _) = generate_new_keypair(default_network());
+    let account_size = get_token_account_size(client, *mint_pubkey)?;
+
+    let instructions = [
+        create_token_program_account(
+            client,
+            &payer_pubkey,
+            &token_account_pubkey,
+            rent_exempt_minimum(client, account_size as usize)?,
+            account_size,
+        )?,
+        apl_token::instruction::initialize_account(
+            &apl_token::id(),
+            &token_account_pubkey,
+            mint_pubkey,
+            owner_pubkey,
+        )?,
+        apl_token::instruction::mint_to(
+            &apl_token::id(),
+            mint_pubkey,
+            &token_account_pubkey,
+            &authority_pubkey,
+            &[],
+            amount,
+        )?,
+    ];
+
+    let mut signers = vec![payer_keypair, token_account_keypair];
+    if authority_pubkey != payer_pubkey {
+        signers.push(authority_keypair);
+    }
+    send_ixs(client, &instructions, &payer_pubkey, signers, "create account and mint tokens")?;
+
+    emit!("🪙 Minted {} tokens to new account {}", amount, log_pubkey(&token_account_pubkey));
+    Ok(token_account_pubkey)
+}
+
 pub fn burn_tokens(
     client: &ArchRpcClient,
     token_account: &arch_program::pubkey::Pubkey,
@@ -259,6 +1687,20 @@ pub fn burn_tokens(
     owner_keypair: bitcoin::key::Keypair,
     amount: u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    burn_tokens_detailed(client, token_account, mint_pubkey, owner_pubkey, owner_keypair, amount)?;
+    Ok(())
+}
+
+/// Same as [`burn_tokens`], but returns the processed transaction so callers can
+/// inspect its logs and status.
+pub fn burn_tokens_detailed(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
 
     let burn_ix = apl_token::instruction::burn(
         &apl_token::id(),
@@ -269,31 +1711,1497 @@ pub fn burn_tokens(
         amount,
     )?;
 
-    let transaction = build_and_sign_transaction(
-        ArchMessage::new(
-            &[burn_ix],
-            Some(*owner_pubkey),
-            client.get_best_block_hash()?,
-        ),
-        vec![owner_keypair],
-        BITCOIN_NETWORK,
-    );
+    let processed_tx = send_ixs(client, &[burn_ix], owner_pubkey, vec![owner_keypair], "burn tokens")?;
+    burns::session().record(*mint_pubkey, amount);
+
+    emit!("🔥 Burned {} tokens", amount);
+    Ok(processed_tx)
+}
+
+/// [`transfer_tokens`] for a source account owned by a multisig. Enough of
+/// the multisig's signers to meet its threshold must be passed, which
+/// [`check_multisig_signers`] verifies before sending; the first one pays the
+/// fee. Transfers *to* a multisig-owned account need nothing special.
+pub fn transfer_tokens_multisig(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    multisig_pubkey: &arch_program::pubkey::Pubkey,
+    signer_keypairs: &[bitcoin::key::Keypair],
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if from_account == to_account {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
+    }
+
+    let signer_pubkeys = keypair_pubkeys(signer_keypairs);
+    check_multisig_signers(multisig_pubkey, &read_multisig(client, *multisig_pubkey)?, &signer_pubkeys)?;
+    let signer_refs: Vec<&arch_program::pubkey::Pubkey> = signer_pubkeys.iter().collect();
+    let payer = signer_pubkeys.first().ok_or("Multisig transfer needs at least one signer")?;
 
-    let processed_txs = send_transactions_and_wait(vec![transaction]);
-    if processed_txs[0].status != Status::Processed {
-        return Err("Failed to burn tokens".into());
+    let transfer_ix = apl_token::instruction::transfer(
+        &apl_token::id(),
+        from_account,
+        to_account,
+        multisig_pubkey,        // Owner is the multisig itself
+        &signer_refs,
+        amount,
+    )?;
+
+    send_ixs(client, &[transfer_ix], payer, signer_keypairs.to_vec(), "transfer tokens")?;
+
+    emit!("📤 Transferred {} tokens ({} multisig signers)", amount, signer_keypairs.len());
+    Ok(())
+}
+
+/// [`burn_tokens`] for an account owned by a multisig; see
+/// [`transfer_tokens_multisig`] for how signers are used.
+pub fn burn_tokens_multisig(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    multisig_pubkey: &arch_program::pubkey::Pubkey,
+    signer_keypairs: &[bitcoin::key::Keypair],
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let signer_pubkeys = keypair_pubkeys(signer_keypairs);
+    check_multisig_signers(multisig_pubkey, &read_multisig(client, *multisig_pubkey)?, &signer_pubkeys)?;
+    let signer_refs: Vec<&arch_program::pubkey::Pubkey> = signer_pubkeys.iter().collect();
+    let payer = signer_pubkeys.first().ok_or("Multisig burn needs at least one signer")?;
+
+    let burn_ix = apl_token::instruction::burn(
+        &apl_token::id(),
+        token_account,
+        mint_pubkey,
+        multisig_pubkey,
+        &signer_refs,
+        amount,
+    )?;
+
+    send_ixs(client, &[burn_ix], payer, signer_keypairs.to_vec(), "burn tokens")?;
+    burns::session().record(*mint_pubkey, amount);
+
+    emit!("🔥 Burned {} tokens ({} multisig signers)", amount, signer_keypairs.len());
+    Ok(())
+}
+
+/// Reads the multisig at `multisig_pubkey`.
+pub fn read_multisig(
+    client: &ArchRpcClient,
+    multisig_pubkey: arch_program::pubkey::Pubkey,
+) -> Result<Multisig, Box<dyn std::error::Error>> {
+    let account_info = client.read_account_info(multisig_pubkey)?;
+    assert_token_program_owned(multisig_pubkey, &account_info)?;
+    Ok(Multisig::unpack(&account_info.data)
+        .map_err(|_| TokenError::Decode { account: multisig_pubkey, expected: "multisig" })?)
+}
+
+/// Fails with [`TokenError::NotMultisigSigner`] if any of `signers` isn't one
+/// of `multisig`'s, or [`TokenError::NotEnoughSigners`] if fewer distinct
+/// signers than its threshold are given, so a multisig operation fails before
+/// it's sent rather than on-chain.
+pub fn check_multisig_signers(
+    multisig_pubkey: &arch_program::pubkey::Pubkey,
+    multisig: &Multisig,
+    signers: &[arch_program::pubkey::Pubkey],
+) -> Result<(), TokenError> {
+    let members = &multisig.signers[..multisig.n as usize];
+    if let Some(signer) = signers.iter().find(|signer| !members.contains(signer)) {
+        return Err(TokenError::NotMultisigSigner { multisig: *multisig_pubkey, signer: *signer });
+    }
+
+    let mut distinct = signers.to_vec();
+    distinct.sort();
+    distinct.dedup();
+    if distinct.len() < multisig.m as usize {
+        return Err(TokenError::NotEnoughSigners {
+            multisig: *multisig_pubkey,
+            required: multisig.m,
+            provided: distinct.len(),
+        });
     }
+    Ok(())
+}
+
+fn keypair_pubkeys(keypairs: &[bitcoin::key::Keypair]) -> Vec<arch_program::pubkey::Pubkey> {
+    keypairs
+        .iter()
+        .map(arch_pubkey)
+        .collect()
+}
+
+pub fn set_account_owner(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    new_owner: &arch_program::pubkey::Pubkey,
+    current_owner_pubkey: &arch_program::pubkey::Pubkey,
+    current_owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    // Hand the account to a new owner; balance stays where it is
+    let set_authority_ix = apl_token::instruction::set_authority(
+        &apl_token::id(),
+        token_account,
+        Some(new_owner),
+        AuthorityType::AccountOwner,
+        current_owner_pubkey,
+        &[],
+    )?;
 
-    println!("🔥 Burned {} tokens", amount);
+    send_ixs(client, &[set_authority_ix], current_owner_pubkey, vec![current_owner_keypair], "set account owner")?;
+
+    emit!("🔑 Account owner changed to {}", log_pubkey(new_owner));
     Ok(())
 }
 
-pub fn get_token_balance(token_account: arch_program::pubkey::Pubkey) -> Result<u64, Box<dyn std::error::Error>> {
-    let account_info = read_account_info(token_account);
-    let account_data = Account::unpack(&account_info.data)?;
-    Ok(account_data.amount)
+/// Hands `token_account` to `new_owner` via [`set_account_owner`] and reports
+/// whether the account is the canonical associated token account of the new
+/// owner and `mint_pubkey` under `ata_program_id`. ATAs are address-derived,
+/// so a migrated account almost never is; a warning is printed when it isn't.
+pub fn rotate_account_owner(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    new_owner: &arch_program::pubkey::Pubkey,
+    current_owner_pubkey: &arch_program::pubkey::Pubkey,
+    current_owner_keypair: bitcoin::key::Keypair,
+    ata_program_id: &arch_program::pubkey::Pubkey,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    set_account_owner(client, token_account, new_owner, current_owner_pubkey, current_owner_keypair)?;
+
+    let is_canonical_ata = get_associated_token_address(new_owner, mint_pubkey, ata_program_id) == *token_account;
+    if !is_canonical_ata {
+        emit!("⚠️ {} is not the associated token account of {} for this mint", log_pubkey(token_account), log_pubkey(new_owner));
+    }
+    Ok(is_canonical_ata)
+}
+
+/// Id of the associated token account program, as declared by the published
+/// `apl-associated-token-account` crate (`ATok9pxLsNzM5zJJ3UQpXBrMriHpZiY5Yio3GKYU4we3`).
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: arch_program::pubkey::Pubkey = arch_program::pubkey::Pubkey([
+    140, 151, 35, 17, 132, 146, 123, 119, 181, 241, 128, 17, 143, 204, 104, 52,
+    20, 183, 124, 82, 30, 90, 119, 8, 28, 247, 29, 95, 96, 106, 83, 132,
+]);
+
+/// `owner`'s associated token address for `mint_pubkey` under
+/// [`ASSOCIATED_TOKEN_PROGRAM_ID`]. Pure derivation: the account may or may
+/// not exist.
+pub fn derive_ata(
+    owner: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+) -> arch_program::pubkey::Pubkey {
+    get_associated_token_address(owner, mint_pubkey, &ASSOCIATED_TOKEN_PROGRAM_ID)
+}
+
+/// Address of `owner`'s associated token account for `mint_pubkey`, derived
+/// with the ATA program's seeds (owner, token program, mint). The 0.5 SDK
+/// doesn't ship an ATA program, so its id must be supplied.
+pub fn get_associated_token_address(
+    owner: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    ata_program_id: &arch_program::pubkey::Pubkey,
+) -> arch_program::pubkey::Pubkey {
+    arch_program::pubkey::Pubkey::find_program_address(
+        &[&owner.serialize(), &apl_token::id().serialize(), &mint_pubkey.serialize()],
+        ata_program_id,
+    )
+    .0
 }
 
+pub fn set_close_authority(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    new_close_authority: Option<&arch_program::pubkey::Pubkey>,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    // None clears the close authority, leaving only the owner able to close
+    let set_authority_ix = apl_token::instruction::set_authority(
+        &apl_token::id(),
+        token_account,
+        new_close_authority,
+        AuthorityType::CloseAccount,
+        owner_pubkey,
+        &[],
+    )?;
+
+    send_ixs(client, &[set_authority_ix], owner_pubkey, vec![owner_keypair], "set close authority")?;
+
+    match new_close_authority {
+        Some(authority) => emit!("🔑 Close authority set to {}", log_pubkey(authority)),
+        None => emit!("🔑 Close authority cleared"),
+    }
+    Ok(())
+}
+
+pub fn close_token_account(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    destination: &arch_program::pubkey::Pubkey,
+    authority_pubkey: &arch_program::pubkey::Pubkey,
+    authority_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    // Authority is the close authority if one is set, otherwise the owner
+    let close_ix = apl_token::instruction::close_account(
+        &apl_token::id(),
+        token_account,
+        destination,            // Receives the reclaimed lamports
+        authority_pubkey,
+        &[],
+    )?;
+
+    send_ixs(client, &[close_ix], authority_pubkey, vec![authority_keypair], "close token account")?;
+
+    emit!("🗑️ Token account closed: {}", log_pubkey(token_account));
+    Ok(())
+}
+
+/// Closes `token_account` like [`close_token_account`], but first checks it
+/// holds `expected_mint`, failing with [`TokenError::WrongMint`] otherwise.
+/// Guards scripts that close accounts in bulk against closing the wrong one.
+pub fn close_token_account_for_mint(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    expected_mint: &arch_program::pubkey::Pubkey,
+    destination: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    check_account_mint(client, token_account, expected_mint)?;
+    close_token_account(client, token_account, destination, owner_pubkey, owner_keypair)
+}
+
+/// Burns the whole balance of `token_account` and closes it in a single
+/// transaction, sending its lamports to `rent_destination`. Either both
+/// happen or neither does.
+pub fn burn_and_close(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    rent_destination: &arch_program::pubkey::Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let balance = get_token_balance(*token_account)?;
+
+    let mut instructions = Vec::with_capacity(2);
+    if balance > 0 {
+        instructions.push(apl_token::instruction::burn(
+            &apl_token::id(),
+            token_account,
+            mint_pubkey,
+            owner_pubkey,
+            &[],
+            balance,
+        )?);
+    }
+    instructions.push(apl_token::instruction::close_account(
+        &apl_token::id(),
+        token_account,
+        rent_destination,
+        owner_pubkey,
+        &[],
+    )?);
+
+    send_ixs(client, &instructions, owner_pubkey, vec![owner_keypair], "burn and close token account")?;
+    burns::session().record(*mint_pubkey, balance);
+
+    emit!("🔥 Burned {} tokens and closed {}", balance, log_pubkey(token_account));
+    Ok(())
+}
+
+pub fn set_freeze_authority(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    new_freeze_authority: Option<&arch_program::pubkey::Pubkey>,
+    current_freeze_authority_pubkey: &arch_program::pubkey::Pubkey,
+    current_freeze_authority_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    // None removes the freeze authority for good; it can't be re-enabled
+    let set_authority_ix = apl_token::instruction::set_authority(
+        &apl_token::id(),
+        mint_pubkey,
+        new_freeze_authority,
+        AuthorityType::FreezeAccount,
+        current_freeze_authority_pubkey,
+        &[],
+    )?;
+
+    send_ixs(
+        client,
+        &[set_authority_ix],
+        current_freeze_authority_pubkey,
+        vec![current_freeze_authority_keypair],
+        "set freeze authority",
+    )?;
+
+    match new_freeze_authority {
+        Some(authority) => emit!("🔑 Freeze authority set to {}", log_pubkey(authority)),
+        None => emit!("🔑 Freeze authority removed"),
+    }
+    Ok(())
+}
+
+/// Hands off (or removes, with `None`) the mint and freeze authorities in a
+/// single transaction, typically to lock a mint after launch. The current
+/// authority must hold both roles. The mint is read back afterwards to confirm.
+pub fn finalize_mint(
+    client: &ArchRpcClient,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    new_mint_authority: Option<&arch_program::pubkey::Pubkey>,
+    new_freeze_authority: Option<&arch_program::pubkey::Pubkey>,
+    current_authority_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let current_authority_pubkey = arch_pubkey(&current_authority_keypair);
+
+    let set_mint_authority_ix = apl_token::instruction::set_authority(
+        &apl_token::id(),
+        mint_pubkey,
+        new_mint_authority,
+        AuthorityType::MintTokens,
+        &current_authority_pubkey,
+        &[],
+    )?;
+    let set_freeze_authority_ix = apl_token::instruction::set_authority(
+        &apl_token::id(),
+        mint_pubkey,
+        new_freeze_authority,
+        AuthorityType::FreezeAccount,
+        &current_authority_pubkey,
+        &[],
+    )?;
+
+    send_ixs(
+        client,
+        &[set_mint_authority_ix, set_freeze_authority_ix],
+        &current_authority_pubkey,
+        vec![current_authority_keypair],
+        "finalize mint",
+    )?;
+
+    let mint = read_mint(client, *mint_pubkey)?;
+    if Option::from(mint.mint_authority) != new_mint_authority.copied()
+        || Option::from(mint.freeze_authority) != new_freeze_authority.copied()
+    {
+        return Err(format!("Mint {} authorities did not update as requested", mint_pubkey).into());
+    }
+
+    emit!("🏁 Mint finalized: {}", log_pubkey(mint_pubkey));
+    Ok(())
+}
+
+/// Freezes `token_account` so it can't move tokens until thawed. Fails with
+/// [`TokenError::WrongAuthority`] before sending if `freeze_authority_pubkey`
+/// isn't the mint's freeze authority.
+pub fn freeze_token_account(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    freeze_authority_pubkey: &arch_program::pubkey::Pubkey,
+    freeze_authority_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    verify_freeze_authority(client, *mint_pubkey, freeze_authority_pubkey)?;
+
+    let freeze_ix = apl_token::instruction::freeze_account(
+        &apl_token::id(),
+        token_account,
+        mint_pubkey,
+        freeze_authority_pubkey,
+        &[],
+    )?;
+
+    send_ixs(client, &[freeze_ix], freeze_authority_pubkey, vec![freeze_authority_keypair], "freeze token account")?;
+
+    emit!("🧊 Token account frozen: {}", log_pubkey(token_account));
+    Ok(())
+}
+
+/// Reverses [`freeze_token_account`], letting the account move tokens again.
+/// The freeze authority is checked up front the same way.
+pub fn thaw_token_account(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    freeze_authority_pubkey: &arch_program::pubkey::Pubkey,
+    freeze_authority_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    verify_freeze_authority(client, *mint_pubkey, freeze_authority_pubkey)?;
+
+    let thaw_ix = apl_token::instruction::thaw_account(
+        &apl_token::id(),
+        token_account,
+        mint_pubkey,
+        freeze_authority_pubkey,
+        &[],
+    )?;
+
+    send_ixs(client, &[thaw_ix], freeze_authority_pubkey, vec![freeze_authority_keypair], "thaw token account")?;
+
+    emit!("☀️ Token account thawed: {}", log_pubkey(token_account));
+    Ok(())
+}
+
+pub fn approve_delegate(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    delegate: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    // Replaces any existing delegation on the account
+    let approve_ix = apl_token::instruction::approve(
+        &apl_token::id(),
+        token_account,
+        delegate,
+        owner_pubkey,
+        &[],
+        amount,                 // Most the delegate may move
+    )?;
+
+    send_ixs(client, &[approve_ix], owner_pubkey, vec![owner_keypair], "approve delegate")?;
+
+    emit!("🤝 Delegated {} tokens to {}", amount, log_pubkey(delegate));
+    Ok(())
+}
+
+pub fn revoke_delegate(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+
+    let revoke_ix = apl_token::instruction::revoke(
+        &apl_token::id(),
+        token_account,
+        owner_pubkey,
+        &[],
+    )?;
+
+    send_ixs(client, &[revoke_ix], owner_pubkey, vec![owner_keypair], "revoke delegate")?;
+
+    emit!("🚫 Delegation revoked on {}", log_pubkey(token_account));
+    Ok(())
+}
+
+/// Transfers `amount` out of `from_account` and revokes its delegation in one
+/// transaction, so there's no window in which the delegate can still spend
+/// what's left after the transfer.
+pub fn transfer_and_revoke(
+    client: &ArchRpcClient,
+    from_account: &arch_program::pubkey::Pubkey,
+    to_account: &arch_program::pubkey::Pubkey,
+    owner_pubkey: &arch_program::pubkey::Pubkey,
+    owner_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if from_account == to_account {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
+    }
+
+    let transfer_ix = apl_token::instruction::transfer(
+        &apl_token::id(),
+        from_account,
+        to_account,
+        owner_pubkey,
+        &[],
+        amount,
+    )?;
+    let revoke_ix = apl_token::instruction::revoke(
+        &apl_token::id(),
+        from_account,
+        owner_pubkey,
+        &[],
+    )?;
+
+    send_ixs(client, &[transfer_ix, revoke_ix], owner_pubkey, vec![owner_keypair], "transfer and revoke")?;
+
+    emit!("💸 Transferred {} tokens and revoked delegation on {}", amount, log_pubkey(from_account));
+    Ok(())
+}
+
+/// A token account and whether [`approve_many`] set its delegation.
+pub type ApprovalResult = (arch_program::pubkey::Pubkey, Result<(), TokenError>);
+
+/// Approves `delegate_pubkey` on each `(token_account, owner_keypair, amount)`
+/// like [`approve_delegate`], as many accounts to a transaction as fit with
+/// the first owner in each paying. A failed transaction fails every account in
+/// it; the rest still go through. Signs for `network`. Returns each account,
+/// in order, with its outcome.
+pub fn approve_many(
+    client: &ArchRpcClient,
+    delegate_pubkey: &arch_program::pubkey::Pubkey,
+    approvals: &[(arch_program::pubkey::Pubkey, bitcoin::key::Keypair, u64)],
+    network: network::NetworkKind,
+) -> Result<Vec<ApprovalResult>, Box<dyn std::error::Error>> {
+    let groups = approvals
+        .iter()
+        .map(|(token_account, owner_keypair, amount)| {
+            let owner_pubkey = arch_pubkey(owner_keypair);
+            apl_token::instruction::approve(&apl_token::id(), token_account, delegate_pubkey, &owner_pubkey, &[], *amount)
+                .map(|instruction| vec![instruction])
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+    let mut transactions = Vec::new();
+    let mut ranges = Vec::new();
+    for (message, range) in batch::chunk_instruction_groups(groups, None, &recent_blockhash)? {
+        let mut signers: Vec<bitcoin::key::Keypair> = Vec::with_capacity(range.len());
+        for (_, owner_keypair, _) in &approvals[range.clone()] {
+            if !signers.iter().any(|signer| arch_pubkey(signer) == arch_pubkey(owner_keypair)) {
+                signers.push(*owner_keypair);
+            }
+        }
+        transactions.push(build_and_sign_transaction(message, signers, network.to_bitcoin_network()));
+        ranges.push(range);
+    }
+
+    let result = batch::send_batch(client, transactions);
+    let results: Vec<ApprovalResult> = approvals
+        .iter()
+        .enumerate()
+        .map(|(i, (token_account, _, _))| match result.group_failure(&ranges, i) {
+            Some(reason) => (*token_account, Err(reason.clone())),
+            None => (*token_account, Ok(())),
+        })
+        .collect();
+
+    emit!(
+        "🤝 Delegated to {} on {} of {} accounts",
+        log_pubkey(delegate_pubkey),
+        results.iter().filter(|(_, result)| result.is_ok()).count(),
+        approvals.len()
+    );
+    Ok(results)
+}
+
+/// Burns `amount` from `token_account` on its owner's behalf, signed by the
+/// delegate set with [`approve_delegate`]. The program caps this at the
+/// approved allowance and reduces it by `amount`; the delegate pays the fee.
+pub fn burn_from_delegated(
+    client: &ArchRpcClient,
+    token_account: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+    delegate_pubkey: &arch_program::pubkey::Pubkey,
+    delegate_keypair: bitcoin::key::Keypair,
+    amount: u64,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    let burn_ix = apl_token::instruction::burn(
+        &apl_token::id(),
+        token_account,
+        mint_pubkey,
+        delegate_pubkey,        // Delegate signs in place of the owner
+        &[],
+        amount,
+    )?;
+
+    let processed_tx = send_ixs(client, &[burn_ix], delegate_pubkey, vec![delegate_keypair], "burn delegated tokens")?;
+    burns::session().record(*mint_pubkey, amount);
+
+    emit!("🔥 Delegate {} burned {} tokens", log_pubkey(delegate_pubkey), amount);
+    Ok(processed_tx)
+}
+
+/// Wrapped-native tokens are not available on Arch: APL token 0.5 has no native
+/// mint, no `SyncNative` instruction, and its `Account` carries no `is_native`
+/// reserve. The native helpers below only exist to fail with that explanation.
+const NATIVE_UNSUPPORTED: &str =
+    "Wrapped-native accounts are not supported by the APL token program (no SyncNative instruction)";
+
+pub fn sync_native_account(
+    _client: &ArchRpcClient,
+    _token_account: &arch_program::pubkey::Pubkey,
+    _owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(NATIVE_UNSUPPORTED.into())
+}
+
+pub fn create_native_token_account(
+    _client: &ArchRpcClient,
+    _owner_keypair: bitcoin::key::Keypair,
+    _lamports: u64,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    Err(NATIVE_UNSUPPORTED.into())
+}
+
+/// On a program with wrapped-native support this would be `close_token_account`
+/// on a native account: closing releases the wrapped lamports to `destination`.
+/// For ordinary token accounts [`close_token_account`] already reclaims the
+/// account's rent lamports, so there is nothing extra to unwrap on Arch.
+pub fn unwrap_native(
+    _client: &ArchRpcClient,
+    _native_account: &arch_program::pubkey::Pubkey,
+    _destination: &arch_program::pubkey::Pubkey,
+    _owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(NATIVE_UNSUPPORTED.into())
+}
+
+/// Account extensions an existing token account could be grown to hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountExtension {
+    ImmutableOwner,
+}
+
+/// APL token 0.5 has no `Reallocate` instruction and fixed-size accounts:
+/// `InitializeImmutableOwner` is accepted but stores nothing, and only before
+/// `InitializeAccount`. Existing accounts can't adopt extensions, so
+/// [`reallocate_account`] only exists to fail with that explanation.
+const REALLOCATE_UNSUPPORTED: &str =
+    "Account reallocation is not supported by the APL token program (no Reallocate instruction or extension space)";
+
+pub fn reallocate_account(
+    _client: &ArchRpcClient,
+    _token_account: &arch_program::pubkey::Pubkey,
+    _new_extensions: &[AccountExtension],
+    _payer_keypair: bitcoin::key::Keypair,
+    _owner_keypair: bitcoin::key::Keypair,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(REALLOCATE_UNSUPPORTED.into())
+}
+
+/// Length of `token_account`'s data, which is `Account::LEN` unless the
+/// account carries extensions.
+pub fn account_data_len(token_account: arch_program::pubkey::Pubkey) -> Result<usize, Box<dyn std::error::Error>> {
+    let account_info = read_account_info(token_account);
+    assert_token_program_owned(token_account, &account_info)?;
+    Ok(account_info.data.len())
+}
+
+/// Whether `token_account` holds data past the base `Account` layout. Always
+/// `false` under APL token 0.5, which has no extension space (see
+/// [`reallocate_account`]); such accounts don't unpack as a plain `Account`.
+pub fn has_extensions(token_account: arch_program::pubkey::Pubkey) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(account_data_len(token_account)? > Account::LEN)
+}
+
+/// Lamports a single faucet request is guaranteed to provide.
+pub const DEFAULT_FUNDING_LAMPORTS: u64 = 1_000_000_000;
+
+/// Failed faucet requests tolerated before giving up on funding.
+const FAUCET_MAX_FAILURES: u32 = 5;
+
+/// First retry delay after a failed faucet request; doubles on each failure.
+const FAUCET_BACKOFF_MS: u64 = 500;
+
+/// Requests faucet airdrops until `keypair`'s account holds at least `lamports`,
+/// backing off exponentially when the faucet errors or is rate-limited. Gives
+/// up with [`TokenError::FaucetFailed`] after `FAUCET_MAX_FAILURES` failed
+/// requests. Returns the final balance.
+pub fn fund_account_with_faucet(
+    client: &ArchRpcClient,
+    keypair: &bitcoin::key::Keypair,
+    lamports: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    fund_account_with_faucet_on(client, keypair, lamports, network::NetworkKind::default())
+}
+
+/// [`fund_account_with_faucet`] for the default network's faucet amount; see
+/// [`NetworkKind::defaults`](network::NetworkKind::defaults).
+pub fn fund_account_with_faucet_default(
+    client: &ArchRpcClient,
+    keypair: &bitcoin::key::Keypair,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    fund_account_with_faucet_default_on(client, keypair, network::NetworkKind::default())
+}
+
+/// [`fund_account_with_faucet_default`] against `network`, with its faucet
+/// amount.
+pub(crate) fn fund_account_with_faucet_default_on(
+    client: &ArchRpcClient,
+    keypair: &bitcoin::key::Keypair,
+    network: network::NetworkKind,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    fund_account_with_faucet_on(client, keypair, network.defaults().faucet_lamports.unwrap_or_default(), network)
+}
+
+/// [`fund_account_with_faucet`] against `network`. Networks without a faucet
+/// fail up front with [`TokenError::NoFaucet`] instead of attempting a request.
+pub(crate) fn fund_account_with_faucet_on(
+    client: &ArchRpcClient,
+    keypair: &bitcoin::key::Keypair,
+    lamports: u64,
+    network: network::NetworkKind,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let pubkey = arch_pubkey(keypair);
+    if !network.has_faucet() {
+        return Err(TokenError::NoFaucet { network, account: pubkey }.into());
+    }
+
+    let balance_of = |pubkey| client.read_account_info(pubkey).map(|info| info.lamports).unwrap_or(0);
+
+    let mut balance = balance_of(pubkey);
+    let mut failures = 0;
+    while balance < lamports {
+        // The SDK call asserts on the funded balance and unwraps airdrop results
+        let result = std::panic::catch_unwind(|| client.create_and_fund_account_with_faucet(keypair, network.to_bitcoin_network()))
+            .unwrap_or_else(|_| Err(arch_sdk::ArchError::UnknownError("faucet request panicked".to_string())));
+        let new_balance = balance_of(pubkey);
+
+        if result.is_err() || new_balance <= balance {
+            failures += 1;
+            if failures >= FAUCET_MAX_FAILURES {
+                let reason = match result {
+                    Err(e) => e.to_string(),
+                    Ok(()) => "faucet did not increase the balance".to_string(),
+                };
+                return Err(TokenError::FaucetFailed {
+                    account: pubkey,
+                    requested: lamports,
+                    balance: new_balance,
+                    attempts: failures,
+                    reason,
+                }
+                .into());
+            }
+            std::thread::sleep(std::time::Duration::from_millis(FAUCET_BACKOFF_MS << (failures - 1)));
+        }
+        balance = new_balance;
+    }
+
+    emit!("💧 Funded {} with {} lamports", log_pubkey(&pubkey), balance);
+    Ok(balance)
+}
+
+/// Lamports charged per transaction. The 0.5 SDK and RPC expose no fee
+/// schedule or estimate endpoint, and the transaction builders don't budget a
+/// fee, so there is nothing to charge for beyond the rent accounts carry.
+pub const TRANSACTION_FEE_LAMPORTS: u64 = 0;
+
+/// Expected fee for `transaction`. Always [`TRANSACTION_FEE_LAMPORTS`] on this
+/// SDK version; transactions over the runtime size limit are rejected here
+/// since the node would refuse them anyway.
+pub fn estimate_fee(transaction: &arch_sdk::RuntimeTransaction) -> Result<u64, Box<dyn std::error::Error>> {
+    transaction.check_tx_size_limit()?;
+    Ok(TRANSACTION_FEE_LAMPORTS)
+}
+
+/// Lamports an account holding `data_len` bytes must carry. The 0.5 node
+/// exposes no rent query, and the runtime only enforces the flat
+/// `MIN_ACCOUNT_LAMPORTS` floor whatever the size, so that is what this
+/// returns. Sizes over the runtime's data limit are rejected up front.
+pub fn rent_exempt_minimum(
+    _client: &ArchRpcClient,
+    data_len: usize,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    if data_len > arch_program::entrypoint::MAX_PERMITTED_DATA_LENGTH {
+        return Err(format!(
+            "Account data length {} exceeds the maximum of {} bytes",
+            data_len,
+            arch_program::entrypoint::MAX_PERMITTED_DATA_LENGTH
+        ).into());
+    }
+    Ok(arch_program::account::MIN_ACCOUNT_LAMPORTS)
+}
+
+/// Lamports creating `account_count` accounts of `data_len` bytes each will
+/// lock up in rent, for funding a payer before a batch.
+pub fn estimate_batch_rent(
+    client: &ArchRpcClient,
+    account_count: usize,
+    data_len: usize,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    Ok(math::checked_mul(rent_exempt_minimum(client, data_len)?, account_count as u64)?)
+}
+
+/// Fails with [`TokenError::InsufficientLamports`] unless `payer` holds at
+/// least `required` lamports. A payer with no account yet holds none. Batch
+/// helpers call it up front so they don't run out of funds halfway through.
+pub fn ensure_payer_balance(payer: arch_program::pubkey::Pubkey, required: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let balance = try_read_account_info(payer).map_or(0, |account_info| account_info.lamports);
+    if balance < required {
+        return Err(TokenError::InsufficientLamports { payer, balance, required }.into());
+    }
+    Ok(())
+}
+
+/// Tops `account_pubkey` up to `target_lamports` (e.g. its
+/// [`rent_exempt_minimum`]) with a system transfer from `payer_keypair`.
+/// Nothing is sent if it already holds enough. Returns the lamports added.
+pub fn top_up_lamports(
+    client: &ArchRpcClient,
+    account_pubkey: &arch_program::pubkey::Pubkey,
+    payer_keypair: bitcoin::key::Keypair,
+    target_lamports: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let current = try_read_account_info(*account_pubkey).map_or(0, |account_info| account_info.lamports);
+    let Some(shortfall) = target_lamports.checked_sub(current).filter(|shortfall| *shortfall > 0) else {
+        return Ok(0);
+    };
+
+    let payer_pubkey = arch_pubkey(&payer_keypair);
+    let transfer_ix = arch_program::system_instruction::transfer(&payer_pubkey, account_pubkey, shortfall);
+    send_ixs(client, &[transfer_ix], &payer_pubkey, vec![payer_keypair], "top up lamports")?;
+
+    emit!("💸 Topped up {} with {} lamports", log_pubkey(account_pubkey), shortfall);
+    Ok(shortfall)
+}
+
+/// Fails with [`TokenError::InsufficientRent`] if `lamports` is below
+/// [`rent_exempt_minimum`] for `data_len` bytes, since such an account could
+/// be garbage-collected.
+pub fn ensure_rent_exempt(
+    client: &ArchRpcClient,
+    lamports: u64,
+    data_len: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let required = rent_exempt_minimum(client, data_len)?;
+    if lamports < required {
+        return Err(TokenError::InsufficientRent { provided: lamports, required }.into());
+    }
+    Ok(())
+}
+
+/// Lamports `account` keeps after `withdraw` leaves it. Fails with
+/// [`TokenError::WithdrawExceedsBalance`] if it doesn't hold that much, and with
+/// [`TokenError::InsufficientRent`] if what's left is above zero but below
+/// [`rent_exempt_minimum`] for `data_len` bytes. Emptying it entirely is
+/// fine, as a close does.
+pub fn check_remaining_rent(
+    client: &ArchRpcClient,
+    account: arch_program::pubkey::Pubkey,
+    balance: u64,
+    withdraw: u64,
+    data_len: usize,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let remaining = balance
+        .checked_sub(withdraw)
+        .ok_or(TokenError::WithdrawExceedsBalance { account, balance, withdraw })?;
+    if remaining > 0 {
+        ensure_rent_exempt(client, remaining, data_len)?;
+    }
+    Ok(remaining)
+}
+
+/// Moves `lamports` out of `from_keypair`'s own account to `destination` with
+/// a system transfer, refusing (via [`check_remaining_rent`]) to strand it
+/// below rent exemption. Token accounts can't be partially drained; close
+/// them with [`close_token_account`] instead. Returns the lamports left.
+pub fn reclaim_lamports(
+    client: &ArchRpcClient,
+    from_keypair: bitcoin::key::Keypair,
+    destination: &arch_program::pubkey::Pubkey,
+    lamports: u64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let from_pubkey = arch_pubkey(&from_keypair);
+    let account_info = client.read_account_info(from_pubkey)?;
+    let remaining = check_remaining_rent(client, from_pubkey, account_info.lamports, lamports, account_info.data.len())?;
+
+    let transfer_ix = arch_program::system_instruction::transfer(&from_pubkey, destination, lamports);
+    send_ixs(client, &[transfer_ix], &from_pubkey, vec![from_keypair], "reclaim lamports")?;
+
+    emit!("💸 Reclaimed {} lamports from {} to {}", lamports, log_pubkey(&from_pubkey), log_pubkey(destination));
+    Ok(remaining)
+}
+
+/// `create_account` for an account owned by the token program, checked with
+/// [`ensure_rent_exempt`] first. Every account-creation helper goes through this.
+fn create_token_program_account(
+    client: &ArchRpcClient,
+    payer: &arch_program::pubkey::Pubkey,
+    account: &arch_program::pubkey::Pubkey,
+    lamports: u64,
+    space: u64,
+) -> Result<Instruction, Box<dyn std::error::Error>> {
+    ensure_rent_exempt(client, lamports, space as usize)?;
+    Ok(arch_program::system_instruction::create_account(payer, account, lamports, space, &apl_token::id()))
+}
+
+/// Network the free helpers generate keys on. Only the keys' Arch pubkeys
+/// are used, so this shapes nothing but the unused Bitcoin address.
+fn default_network() -> bitcoin::Network {
+    network::NetworkKind::default().to_bitcoin_network()
+}
+
+/// Signs `instructions` with `signers`, sends them as one transaction paid by
+/// `payer` and waits for it to be processed via [`poll::send_once`]. The
+/// outcome is printed with [`display::summarize_processed`]; any status other
+/// than `Processed` becomes a [`TokenError::ProgramFailed`] for `action`.
+/// Signs for the default network; see [`send_ixs_on`].
+fn send_ixs(
+    client: &ArchRpcClient,
+    instructions: &[Instruction],
+    payer: &arch_program::pubkey::Pubkey,
+    signers: Vec<bitcoin::key::Keypair>,
+    action: &str,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    send_ixs_on(client, instructions, payer, signers, action, network::NetworkKind::default())
+}
+
+/// [`send_ixs`], signing for `network`.
+fn send_ixs_on(
+    client: &ArchRpcClient,
+    instructions: &[Instruction],
+    payer: &arch_program::pubkey::Pubkey,
+    signers: Vec<bitcoin::key::Keypair>,
+    action: &str,
+    network: network::NetworkKind,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    send_ixs_with(client, instructions, payer, signers, action, network, false)
+}
+
+/// [`send_ixs_on`], optionally followed by [`fetch_and_verify_transaction`].
+fn send_ixs_with(
+    client: &ArchRpcClient,
+    instructions: &[Instruction],
+    payer: &arch_program::pubkey::Pubkey,
+    signers: Vec<bitcoin::key::Keypair>,
+    action: &str,
+    network: network::NetworkKind,
+    verify_inclusion: bool,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    let (transaction, processed_tx) = poll::send_with_blockhash_retry(
+        || poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE),
+        |recent_blockhash| {
+            let transaction = build_and_sign_transaction(
+                ArchMessage::new(instructions, Some(*payer), recent_blockhash),
+                signers.clone(),
+                network.to_bitcoin_network(),
+            );
+            let processed_tx = poll::send_once(client, transaction.clone())?;
+            // An expired hash can also surface as a failed status; hand it back to be retried
+            if error::classify_failure(&processed_tx) == Some(error::FailureKind::ExpiredBlockhash) {
+                return Err(format!("Transaction {} failed on an expired blockhash", processed_tx.txid()).into());
+            }
+            Ok((transaction, processed_tx))
+        },
+    )?;
+    receipts::record(receipts::TransactionReceipt::from_processed(action, &processed_tx));
+    display::print_summary(std::slice::from_ref(&processed_tx));
+    if let Some(failure) = error::program_failure(action, &processed_tx) {
+        return Err(failure.into());
+    }
+
+    if verify_inclusion {
+        return fetch_and_verify_transaction(client, &transaction);
+    }
+
+    Ok(processed_tx)
+}
+
+/// Checks that `processed` carries the same message as the locally signed
+/// `signed`, by comparing message hashes.
+pub fn verify_processed_transaction(
+    signed: &arch_sdk::RuntimeTransaction,
+    processed: &ProcessedTransaction,
+) -> Result<(), TokenError> {
+    let expected = hex::encode(signed.message.hash());
+    let actual = hex::encode(processed.runtime_transaction.message.hash());
+    if expected != actual {
+        return Err(TokenError::Mismatch { txid: signed.txid(), expected, actual });
+    }
+    Ok(())
+}
+
+/// Fetches `signed` back from the node by its txid and runs
+/// [`verify_processed_transaction`] on it. Guards against a node that
+/// substitutes or reorders what was submitted, at the cost of a round trip.
+pub fn fetch_and_verify_transaction(
+    client: &ArchRpcClient,
+    signed: &arch_sdk::RuntimeTransaction,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    let txid = signed.txid();
+    let processed_tx = client
+        .get_processed_transaction(&txid)?
+        .ok_or_else(|| TokenError::TransactionFailed { reason: format!("{} not found on the node", txid) })?;
+
+    verify_processed_transaction(signed, &processed_tx)?;
+    Ok(processed_tx)
+}
+
+/// How many times [`get_token_balance`] reads an account before giving up.
+const BALANCE_READ_ATTEMPTS: u32 = 3;
+
+/// Pause between [`get_token_balance`]'s reads.
+const BALANCE_READ_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Balance of `token_account`. Reads a few times if needed, since an account
+/// created moments ago may not be visible yet; see [`get_token_balance_retry`].
+pub fn get_token_balance(token_account: arch_program::pubkey::Pubkey) -> Result<u64, Box<dyn std::error::Error>> {
+    get_token_balance_retry(token_account, BALANCE_READ_ATTEMPTS, BALANCE_READ_DELAY)
+}
+
+/// Reads `token_account` up to `attempts` times, `delay` apart, until it is
+/// a token account that unpacks, and returns its balance. Right after
+/// creation the node can still report the account as missing or empty. Once
+/// attempts run out, the last read's error is returned.
+pub fn get_token_balance_retry(
+    token_account: arch_program::pubkey::Pubkey,
+    attempts: u32,
+    delay: std::time::Duration,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let mut last_error: Box<dyn std::error::Error> = format!("Account {} not found", token_account).into();
+    for attempt in 0..attempts.max(1) {
+        if attempt > 0 {
+            std::thread::sleep(delay);
+        }
+        let Some(account_info) = try_read_account_info(token_account) else {
+            continue;
+        };
+        match assert_token_program_owned(token_account, &account_info)
+            .and_then(|()| unpack_token_account(token_account, &account_info.data))
+        {
+            Ok(account_data) => return Ok(account_data.amount),
+            Err(e) => last_error = e.into(),
+        }
+    }
+    Err(last_error)
+}
+
+/// Balance of `token_account` as of block `block_hash`. The 0.5 node has no
+/// historical account reads and only serves the latest state, so this can
+/// only answer for the current best block: any other block fails with
+/// [`TokenError::HistoricalStateUnavailable`], as does a read during which a
+/// new block arrived, since it can't tell which block's state it saw. For
+/// past balances, record [`get_token_balance`] at the time instead, e.g.
+/// with [`snapshot::export_balances`].
+pub fn get_token_balance_at(
+    client: &ArchRpcClient,
+    token_account: arch_program::pubkey::Pubkey,
+    block_hash: &str,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let unavailable = |best_block_hash: String| TokenError::HistoricalStateUnavailable {
+        block_hash: block_hash.to_string(),
+        best_block_hash,
+    };
+
+    let best_block_hash = client.get_best_block_hash()?;
+    if best_block_hash != block_hash {
+        return Err(unavailable(best_block_hash).into());
+    }
+    let account_info = client.read_account_info(token_account)?;
+    let best_block_hash = client.get_best_block_hash()?;
+    if best_block_hash != block_hash {
+        return Err(unavailable(best_block_hash).into());
+    }
+
+    assert_token_program_owned(token_account, &account_info)?;
+    Ok(unpack_token_account(token_account, &account_info.data)?.amount)
+}
+
+/// Combined balance of `accounts`, e.g. one owner's holdings of a mint spread
+/// over several token accounts. Fails with [`TokenError::WrongMint`] on the
+/// first account that doesn't hold `expected_mint`.
+pub fn total_holdings(
+    accounts: &[arch_program::pubkey::Pubkey],
+    expected_mint: arch_program::pubkey::Pubkey,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let mut total = 0;
+    for account in accounts {
+        let account_info = read_account_info(*account);
+        assert_token_program_owned(*account, &account_info)?;
+        let account_data = unpack_token_account(*account, &account_info.data)?;
+        if account_data.mint != expected_mint {
+            return Err(TokenError::WrongMint { account: *account, expected: expected_mint, actual: account_data.mint }.into());
+        }
+        total = math::checked_add(total, account_data.amount)?;
+    }
+    Ok(total)
+}
+
+/// Each of `candidates`, in order, paired with whether it's an initialized
+/// token account of `mint_pubkey`. Missing accounts, accounts of other
+/// programs, mints and other mints' accounts are all just invalid, so
+/// user-supplied address lists can be screened before a batch.
+pub fn filter_valid_token_accounts(
+    candidates: &[arch_program::pubkey::Pubkey],
+    mint_pubkey: arch_program::pubkey::Pubkey,
+) -> Vec<(arch_program::pubkey::Pubkey, bool)> {
+    candidates
+        .iter()
+        .map(|candidate| {
+            let valid = try_read_account_info(*candidate).is_some_and(|account_info| {
+                assert_token_program_owned(*candidate, &account_info).is_ok()
+                    && unpack_token_account(*candidate, &account_info.data).is_ok_and(|account| account.mint == mint_pubkey)
+            });
+            (*candidate, valid)
+        })
+        .collect()
+}
+
+/// The mint shared by token accounts `account_a` and `account_b`, or
+/// [`TokenError::MintMismatch`] if they hold different mints.
+pub fn assert_same_mint(
+    account_a: arch_program::pubkey::Pubkey,
+    account_b: arch_program::pubkey::Pubkey,
+) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let (info_a, info_b) = (read_account_info(account_a), read_account_info(account_b));
+    assert_token_program_owned(account_a, &info_a)?;
+    assert_token_program_owned(account_b, &info_b)?;
+    let mint_a = unpack_token_account(account_a, &info_a.data)?.mint;
+    let mint_b = unpack_token_account(account_b, &info_b.data)?.mint;
+    if mint_a != mint_b {
+        return Err(TokenError::MintMismatch { account: account_b, expected: mint_a, actual: mint_b }.into());
+    }
+    Ok(mint_a)
+}
+
+/// Lamports held by `pubkey`, whatever kind of account it is.
+pub fn get_account_lamports(pubkey: arch_program::pubkey::Pubkey) -> Result<u64, Box<dyn std::error::Error>> {
+    let account_info = try_read_account_info(pubkey).ok_or_else(|| format!("Account {} not found", pubkey))?;
+    Ok(account_info.lamports)
+}
+
+/// Polls `token_account` every `interval` and prints its balance whenever it
+/// changes, starting with the current one. Runs until interrupted or a read
+/// fails.
+pub fn watch_balance(
+    token_account: arch_program::pubkey::Pubkey,
+    interval: std::time::Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mint_pubkey = get_account_summary(token_account)?.mint;
+    emit!("👀 Watching {} every {:?}", log_pubkey(&token_account), interval);
+
+    let mut last = None;
+    loop {
+        let balance = amount::get_token_amount(token_account, mint_pubkey)?;
+        if last != Some(balance) {
+            match last {
+                Some(previous) => emit!("💰 Balance changed: {} -> {}", previous, balance),
+                None => emit!("💰 Balance: {}", balance),
+            }
+            last = Some(balance);
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Lamport and token state of a token account, read together for health
+/// checks. An account with `lamports` below [`rent_exempt_minimum`] is at
+/// risk of not being rent-exempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountSummary {
+    pub lamports: u64,
+    pub amount: u64,
+    pub owner: arch_program::pubkey::Pubkey,
+    pub mint: arch_program::pubkey::Pubkey,
+}
+
+pub fn get_account_summary(token_account: arch_program::pubkey::Pubkey) -> Result<AccountSummary, Box<dyn std::error::Error>> {
+    let account_info = read_account_info(token_account);
+    assert_token_program_owned(token_account, &account_info)?;
+    let account_data = unpack_token_account(token_account, &account_info.data)?;
+    Ok(AccountSummary {
+        lamports: account_info.lamports,
+        amount: account_data.amount,
+        owner: account_data.owner,
+        mint: account_data.mint,
+    })
+}
+
+/// The active delegate and its remaining allowance, if any.
+pub fn get_delegation(
+    token_account: arch_program::pubkey::Pubkey,
+) -> Result<Option<(arch_program::pubkey::Pubkey, u64)>, Box<dyn std::error::Error>> {
+    let account_info = read_account_info(token_account);
+    assert_token_program_owned(token_account, &account_info)?;
+    let account_data = unpack_token_account(token_account, &account_info.data)?;
+    Ok(Option::<arch_program::pubkey::Pubkey>::from(account_data.delegate)
+        .map(|delegate| (delegate, account_data.delegated_amount)))
+}
+
+/// How much `token_account`'s delegate can still spend; 0 with no delegate.
+/// Each delegated transfer or burn draws this down.
+pub fn remaining_allowance(token_account: arch_program::pubkey::Pubkey) -> Result<u64, Box<dyn std::error::Error>> {
+    Ok(get_delegation(token_account)?.map_or(0, |(_, delegated_amount)| delegated_amount))
+}
+
+/// Whether `token_account` has been frozen by its mint's freeze authority.
+pub fn is_account_frozen(token_account: arch_program::pubkey::Pubkey) -> Result<bool, Box<dyn std::error::Error>> {
+    let account_info = read_account_info(token_account);
+    assert_token_program_owned(token_account, &account_info)?;
+    Ok(unpack_token_account(token_account, &account_info.data)?.is_frozen())
+}
+
+/// The wrapped-native rent reserve of `token_account`, if it is a native
+/// account. APL 0.5 accounts have no `is_native` field (see
+/// [`sync_native_account`]), so any valid token account reads as `None`; the
+/// account is still decoded so non-token accounts are rejected.
+pub fn get_is_native(
+    token_account: arch_program::pubkey::Pubkey,
+) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    let account_info = read_account_info(token_account);
+    assert_token_program_owned(token_account, &account_info)?;
+    unpack_token_account(token_account, &account_info.data)?;
+    Ok(None)
+}
+
+pub fn get_mint_info(mint_pubkey: arch_program::pubkey::Pubkey) -> Result<Mint, Box<dyn std::error::Error>> {
+    let account_info = read_account_info(mint_pubkey);
+    assert_token_program_owned(mint_pubkey, &account_info)?;
+    let mint_data = unpack_mint(mint_pubkey, &account_info.data)?;
+    Ok(mint_data)
+}
+
+/// First token account (by address) held by `owner` for `mint_pubkey`, if
+/// any. Looked up with a `get_program_accounts` scan over the token program.
+pub fn find_token_account(
+    client: &ArchRpcClient,
+    owner: &arch_program::pubkey::Pubkey,
+    mint_pubkey: &arch_program::pubkey::Pubkey,
+) -> Result<Option<arch_program::pubkey::Pubkey>, Box<dyn std::error::Error>> {
+    // Account layout: mint at offset 0, owner at offset 32
+    let filters = vec![
+        arch_sdk::AccountFilter::DataContent { offset: 0, bytes: mint_pubkey.serialize().to_vec() },
+        arch_sdk::AccountFilter::DataContent { offset: 32, bytes: owner.serialize().to_vec() },
+    ];
+
+    let accounts = match client.get_program_accounts(&apl_token::id(), Some(filters)) {
+        Ok(accounts) => accounts,
+        Err(arch_sdk::ArchError::NotFound(_)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(accounts
+        .into_iter()
+        .filter(|program_account| unpack_token_account(program_account.pubkey, &program_account.account.data).is_ok())
+        .map(|program_account| program_account.pubkey)
+        .min())
+}
+
+/// Whether `pubkey` holds program-owned data, i.e. something was created and
+/// assigned there. Plain wallets and unused addresses report `false`.
+pub fn account_exists(pubkey: arch_program::pubkey::Pubkey) -> bool {
+    try_read_account_info(pubkey).is_some_and(|account_info| {
+        !account_info.data.is_empty() && account_info.owner != arch_program::pubkey::Pubkey::system_program()
+    })
+}
+
+/// Fails with [`TokenError::UnexpectedDecimals`] unless `mint_pubkey` has
+/// `expected` decimals. Call it before amount math that assumes a precision.
+pub fn require_decimals(mint_pubkey: arch_program::pubkey::Pubkey, expected: u8) -> Result<(), Box<dyn std::error::Error>> {
+    let have = get_mint_info(mint_pubkey)?.decimals;
+    if have != expected {
+        return Err(TokenError::UnexpectedDecimals { mint: mint_pubkey, have, expected }.into());
+    }
+    Ok(())
+}
+
+pub fn has_freeze_authority(mint_pubkey: arch_program::pubkey::Pubkey) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(get_freeze_authority(mint_pubkey)?.is_some())
+}
+
+/// Who can freeze and thaw `mint_pubkey`'s accounts; `None` if nobody can,
+/// as for the lifecycle's mint.
+pub fn get_freeze_authority(
+    mint_pubkey: arch_program::pubkey::Pubkey,
+) -> Result<Option<arch_program::pubkey::Pubkey>, Box<dyn std::error::Error>> {
+    Ok(get_mint_info(mint_pubkey)?.freeze_authority.into())
+}
+
+/// Whether `keypair` is the current mint authority of `mint_pubkey`. Lets a
+/// script warn before attempting a mint it can't sign for.
+pub fn is_mint_authority(
+    mint_pubkey: arch_program::pubkey::Pubkey,
+    keypair: &bitcoin::key::Keypair,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mint_authority: Option<arch_program::pubkey::Pubkey> = get_mint_info(mint_pubkey)?.mint_authority.into();
+    Ok(mint_authority == Some(arch_pubkey(keypair)))
+}
+
+/// Fails with [`TokenError::WrongProgram`] unless `account` is owned by the
+/// token program, so a wallet or another program's account passed to a read
+/// helper is named as such rather than reported as undecodable.
+pub fn assert_token_program_owned(
+    account: arch_program::pubkey::Pubkey,
+    account_info: &arch_sdk::AccountInfo,
+) -> Result<(), TokenError> {
+    if account_info.owner != apl_token::id() {
+        return Err(TokenError::WrongProgram { account, owner: account_info.owner });
+    }
+    Ok(())
+}
+
+/// Decodes a token account, naming the account on failure.
+fn unpack_token_account(account: arch_program::pubkey::Pubkey, data: &[u8]) -> Result<Account, TokenError> {
+    Account::unpack(data).map_err(|_| TokenError::Decode { account, expected: "token account" })
+}
+
+/// Decodes a mint, naming the account on failure. Data of the wrong length is
+/// [`TokenError::WrongAccountSize`], e.g. a token account passed as a mint;
+/// [`TokenError::Decode`] means the right size but invalid contents.
+fn unpack_mint(account: arch_program::pubkey::Pubkey, data: &[u8]) -> Result<Mint, TokenError> {
+    if data.len() != Mint::LEN {
+        return Err(TokenError::WrongAccountSize { account, got: data.len(), expected: Mint::LEN });
+    }
+    Mint::unpack(data).map_err(|_| TokenError::Decode { account, expected: "mint" })
+}
+
+/// Fails with [`TokenError::WrongMint`] unless token account `account` holds
+/// `expected_mint`.
+fn check_account_mint(
+    client: &ArchRpcClient,
+    account: &arch_program::pubkey::Pubkey,
+    expected_mint: &arch_program::pubkey::Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let account_info = client.read_account_info(*account)?;
+    assert_token_program_owned(*account, &account_info)?;
+    let actual = unpack_token_account(*account, &account_info.data)?.mint;
+    if actual != *expected_mint {
+        return Err(TokenError::WrongMint { account: *account, expected: *expected_mint, actual }.into());
+    }
+    Ok(())
+}
+
+fn read_mint(
+    client: &ArchRpcClient,
+    mint_pubkey: arch_program::pubkey::Pubkey,
+) -> Result<Mint, Box<dyn std::error::Error>> {
+    let account_info = client.read_account_info(mint_pubkey)?;
+    assert_token_program_owned(mint_pubkey, &account_info)?;
+    Ok(unpack_mint(mint_pubkey, &account_info.data)?)
+}
+
+/// Fails with [`TokenError::WrongAuthority`] unless `authority` is the mint
+/// authority of `mint_pubkey`.
+pub fn verify_mint_authority(
+    client: &ArchRpcClient,
+    mint_pubkey: arch_program::pubkey::Pubkey,
+    authority: &arch_program::pubkey::Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mint = read_mint(client, mint_pubkey)?;
+    check_authority(mint_pubkey, "mint authority", mint.mint_authority.into(), authority)
+}
+
+/// Fails with [`TokenError::WrongAuthority`] unless `authority` is the freeze
+/// authority of `mint_pubkey`.
+pub fn verify_freeze_authority(
+    client: &ArchRpcClient,
+    mint_pubkey: arch_program::pubkey::Pubkey,
+    authority: &arch_program::pubkey::Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mint = read_mint(client, mint_pubkey)?;
+    check_authority(mint_pubkey, "freeze authority", mint.freeze_authority.into(), authority)
+}
+
+/// Fails with [`TokenError::WrongAuthority`] unless `authority` may close
+/// `token_account`: its close authority if set, otherwise its owner.
+pub fn verify_close_authority(
+    client: &ArchRpcClient,
+    token_account: arch_program::pubkey::Pubkey,
+    authority: &arch_program::pubkey::Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let account_info = client.read_account_info(token_account)?;
+    assert_token_program_owned(token_account, &account_info)?;
+    let account = unpack_token_account(token_account, &account_info.data)?;
+    let close_authority = Option::from(account.close_authority).unwrap_or(account.owner);
+    check_authority(token_account, "close authority", Some(close_authority), authority)
+}
+
+fn check_authority(
+    account: arch_program::pubkey::Pubkey,
+    role: &'static str,
+    expected: Option<arch_program::pubkey::Pubkey>,
+    provided: &arch_program::pubkey::Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if expected.as_ref() != Some(provided) {
+        return Err(TokenError::WrongAuthority { account, role, expected, provided: *provided }.into());
+    }
+    Ok(())
+}
+
+/// Space a token account for `mint_pubkey` needs. Mirrors the program's
+/// `GetAccountDataSize`: the mint is validated, and since APL token 0.5 has no
+/// account extensions the answer is always `Account::LEN`.
+pub fn get_token_account_size(
+    client: &ArchRpcClient,
+    mint_pubkey: arch_program::pubkey::Pubkey,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    read_mint(client, mint_pubkey)?;
+    Ok(Account::LEN as u64)
+}
+
+/// The Arch pubkey for `keypair`: the 32-byte x-only (BIP340) public key,
+/// with the parity bit dropped. This is the same derivation
+/// `generate_new_keypair` and the faucet use, so it's the address an account
+/// signed for by `keypair` lives at. The mapping is one-way: a pubkey can't be
+/// turned back into a keypair, and the full 33-byte compressed key is never
+/// what Arch expects.
+pub fn arch_pubkey(keypair: &bitcoin::key::Keypair) -> arch_program::pubkey::Pubkey {
+    arch_program::pubkey::Pubkey::from_slice(&keypair.x_only_public_key().0.serialize())
+}
+
+/// Parses a pubkey in the hex form Arch prints them in (64 hex characters).
+pub fn parse_pubkey(s: &str) -> Result<arch_program::pubkey::Pubkey, Box<dyn std::error::Error>> {
+    let bytes = hex::decode(s.trim()).map_err(|e| format!("Invalid pubkey '{}': {}", s, e))?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("Invalid pubkey '{}': expected 32 bytes, got {}", s, bytes.len()))?;
+    Ok(arch_program::pubkey::Pubkey::from(bytes))
+}
+
+/// Imports a private key in wallet import format, e.g. one exported from
+/// Bitcoin Core. The key's network and compression flag don't matter to
+/// Arch and are ignored. Errors never echo the input.
+pub fn keypair_from_wif(s: &str) -> Result<bitcoin::key::Keypair, Box<dyn std::error::Error>> {
+    let private_key = bitcoin::PrivateKey::from_wif(s.trim()).map_err(|e| format!("Invalid WIF key: {}", e))?;
+    Ok(bitcoin::key::Keypair::from_secret_key(&bitcoin::secp256k1::Secp256k1::new(), &private_key.inner))
+}
+
+/// Imports a raw 32-byte private key written as 64 hex characters. Errors
+/// never echo the input.
+pub fn keypair_from_hex(s: &str) -> Result<bitcoin::key::Keypair, Box<dyn std::error::Error>> {
+    let bytes = hex::decode(s.trim()).map_err(|e| format!("Invalid hex key: {}", e))?;
+    if bytes.len() != 32 {
+        return Err(format!("Invalid hex key: expected 32 bytes, got {}", bytes.len()).into());
+    }
+    bitcoin::key::Keypair::from_seckey_slice(&bitcoin::secp256k1::Secp256k1::new(), &bytes)
+        .map_err(|e| format!("Invalid hex key: {}", e).into())
+}
+
+/// What [`check_connection`] learned about the node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeHealth {
+    pub best_block_hash: String,
+    pub block_height: u64,
+}
+
+/// Reads the node's tip. An unreachable node is reported as an error.
+pub fn check_connection(client: &ArchRpcClient) -> Result<NodeHealth, Box<dyn std::error::Error>> {
+    let best_block_hash = client.get_best_block_hash()?;
+    let block_height = client.get_block_count()?;
+
+    Ok(NodeHealth {
+        best_block_hash,
+        block_height,
+    })
+}
+
+pub mod accounting;
+pub mod amount;
+pub mod batch;
+pub mod bench;
+pub mod burns;
+pub mod cli;
+pub mod client;
+pub mod describe;
+pub mod display;
+pub mod distribute;
+pub mod error;
+pub mod events;
+pub mod explorer;
+pub mod inspect;
+pub mod launch;
+pub mod logger;
+pub mod math;
+pub mod mint_config;
+pub mod network;
+pub mod offline;
+pub mod ops;
+pub mod overview;
+pub mod poll;
+pub mod pubkey_serde;
+pub mod receipts;
+pub mod scope;
+pub mod snapshot;
+pub mod stress;
+
 // Include the test module
 #[cfg(test)]
 mod test;
\ No newline at end of file
@@ -0,0 +1,25 @@
+use crate::error::TokenError;
+
+/// `a + b`, or [`TokenError::AmountOverflow`] past `u64::MAX`.
+pub fn checked_add(a: u64, b: u64) -> Result<u64, TokenError> {
+    a.checked_add(b).ok_or_else(|| TokenError::AmountOverflow { expression: format!("{} + {}", a, b) })
+}
+
+/// `a - b`, or [`TokenError::AmountOverflow`] if `b` is larger.
+pub fn checked_sub(a: u64, b: u64) -> Result<u64, TokenError> {
+    a.checked_sub(b).ok_or_else(|| TokenError::AmountOverflow { expression: format!("{} - {}", a, b) })
+}
+
+/// `a * b`, or [`TokenError::AmountOverflow`] past `u64::MAX`.
+pub fn checked_mul(a: u64, b: u64) -> Result<u64, TokenError> {
+    a.checked_mul(b).ok_or_else(|| TokenError::AmountOverflow { expression: format!("{} * {}", a, b) })
+}
+
+/// Whole tokens to base units: `whole * 10^decimals`, e.g. 1,000 tokens at 9
+/// decimals is `1_000_000_000_000`.
+pub fn scale_by_decimals(whole: u64, decimals: u8) -> Result<u64, TokenError> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|factor| whole.checked_mul(factor))
+        .ok_or_else(|| TokenError::AmountOverflow { expression: format!("{} * 10^{}", whole, decimals) })
+}
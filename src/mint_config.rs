@@ -0,0 +1,91 @@
+use std::{fmt, fs, path::Path};
+
+use apl_token::state::Mint;
+use arch_program::pubkey::Pubkey;
+use serde::Deserialize;
+
+use crate::{get_mint_info, logger::emit};
+
+/// The intended state of a deployed mint, as kept in a TOML file:
+///
+/// ```toml
+/// decimals = 9
+/// mint_authority = "<hex pubkey>"
+/// freeze_authority = "<hex pubkey>"
+/// ```
+///
+/// An authority left out means the mint should have none.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MintConfig {
+    pub decimals: u8,
+    #[serde(default, with = "crate::pubkey_serde::option")]
+    pub mint_authority: Option<Pubkey>,
+    #[serde(default, with = "crate::pubkey_serde::option")]
+    pub freeze_authority: Option<Pubkey>,
+}
+
+impl MintConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// One field where a mint differs from its [`MintConfig`], with both values
+/// rendered for display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, found {}", self.field, self.expected, self.actual)
+    }
+}
+
+/// Every field of `mint` that doesn't match `config`, in config order.
+pub fn diff_mint_config(config: &MintConfig, mint: &Mint) -> Vec<ConfigDiff> {
+    let authority = |authority: Option<Pubkey>| authority.map_or_else(|| "none".to_string(), |pubkey| pubkey.to_string());
+
+    let mut diffs = Vec::new();
+    if config.decimals != mint.decimals {
+        diffs.push(ConfigDiff {
+            field: "decimals",
+            expected: config.decimals.to_string(),
+            actual: mint.decimals.to_string(),
+        });
+    }
+    for (field, expected, actual) in [
+        ("mint_authority", config.mint_authority, Option::<Pubkey>::from(mint.mint_authority)),
+        ("freeze_authority", config.freeze_authority, Option::<Pubkey>::from(mint.freeze_authority)),
+    ] {
+        if expected != actual {
+            diffs.push(ConfigDiff { field, expected: authority(expected), actual: authority(actual) });
+        }
+    }
+    diffs
+}
+
+/// Reads `mint_pubkey` and compares it with the [`MintConfig`] at
+/// `config_path`, printing and returning each difference. Empty means the
+/// deployed mint matches.
+pub fn verify_mint_config(
+    mint_pubkey: Pubkey,
+    config_path: impl AsRef<Path>,
+) -> Result<Vec<ConfigDiff>, Box<dyn std::error::Error>> {
+    let config = MintConfig::load(config_path)?;
+    let diffs = diff_mint_config(&config, &get_mint_info(mint_pubkey)?);
+
+    if diffs.is_empty() {
+        emit!("✅ Mint {} matches its config", mint_pubkey);
+    } else {
+        emit!("⚠️ Mint {} differs from its config in {} fields:", mint_pubkey, diffs.len());
+        for diff in &diffs {
+            emit!("  {}", diff);
+        }
+    }
+    Ok(diffs)
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+/// Which Bitcoin network the Arch node settles to. Lets code target a real
+/// network without going through the test SDK's fixed `BITCOIN_NETWORK`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NetworkKind {
+    Mainnet,
+    Testnet,
+    /// Matches the test SDK's `BITCOIN_NETWORK`.
+    #[default]
+    Regtest,
+    Signet,
+}
+
+impl NetworkKind {
+    pub fn to_bitcoin_network(self) -> bitcoin::Network {
+        match self {
+            NetworkKind::Mainnet => bitcoin::Network::Bitcoin,
+            NetworkKind::Testnet => bitcoin::Network::Testnet,
+            NetworkKind::Regtest => bitcoin::Network::Regtest,
+            NetworkKind::Signet => bitcoin::Network::Signet,
+        }
+    }
+
+    /// Whether the network's nodes run a faucet. Mainnet accounts have to be
+    /// funded with real BTC.
+    pub fn has_faucet(self) -> bool {
+        self.defaults().faucet_lamports.is_some()
+    }
+
+    /// What helpers use on this network when the caller doesn't say.
+    pub fn defaults(self) -> NetworkDefaults {
+        match self {
+            NetworkKind::Regtest => NetworkDefaults { decimals: 9, faucet_lamports: Some(crate::DEFAULT_FUNDING_LAMPORTS) },
+            // Public faucets are shared and rate-limited, so ask for less
+            NetworkKind::Testnet | NetworkKind::Signet => NetworkDefaults { decimals: 9, faucet_lamports: Some(100_000_000) },
+            NetworkKind::Mainnet => NetworkDefaults { decimals: 9, faucet_lamports: None },
+        }
+    }
+}
+
+/// Per-network defaults; see [`NetworkKind::defaults`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkDefaults {
+    /// Decimals for new mints.
+    pub decimals: u8,
+    /// Lamports to request when funding from the faucet; `None` where there
+    /// is no faucet.
+    pub faucet_lamports: Option<u64>,
+}
+
+impl fmt::Display for NetworkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NetworkKind::Mainnet => "mainnet",
+            NetworkKind::Testnet => "testnet",
+            NetworkKind::Regtest => "regtest",
+            NetworkKind::Signet => "signet",
+        };
+        f.write_str(name)
+    }
+}
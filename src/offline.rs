@@ -0,0 +1,124 @@
+use arch_program::{pubkey::Pubkey, sanitized::ArchMessage};
+use arch_sdk::{
+    build_and_sign_transaction, sign_message_bip322, ArchRpcClient, ProcessedTransaction, RuntimeTransaction, Signature,
+    Status,
+};
+use bitcoin::key::Keypair;
+
+use crate::{
+    arch_pubkey,
+    error::TokenError,
+    network::NetworkKind,
+    poll,
+    receipts::{self, TransactionReceipt},
+};
+
+/// Unsigned message minting `amount` into `account_pubkey`, paid for by the
+/// mint authority. Sign it wherever the authority key lives, or hand it to
+/// [`sign_and_send`].
+pub fn build_mint_message(
+    client: &ArchRpcClient,
+    mint_pubkey: &Pubkey,
+    account_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    amount: u64,
+) -> Result<ArchMessage, Box<dyn std::error::Error>> {
+    let mint_ix = apl_token::instruction::mint_to(
+        &apl_token::id(),
+        mint_pubkey,
+        account_pubkey,
+        authority_pubkey,
+        &[],
+        amount,
+    )?;
+    Ok(ArchMessage::new(&[mint_ix], Some(*authority_pubkey), poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?))
+}
+
+/// Unsigned message transferring `amount` from `from_account` to
+/// `to_account`, paid for by the owner.
+pub fn build_transfer_message(
+    client: &ArchRpcClient,
+    from_account: &Pubkey,
+    to_account: &Pubkey,
+    owner_pubkey: &Pubkey,
+    amount: u64,
+) -> Result<ArchMessage, Box<dyn std::error::Error>> {
+    if from_account == to_account {
+        return Err(TokenError::SelfTransfer { account: *from_account }.into());
+    }
+
+    let transfer_ix = apl_token::instruction::transfer(
+        &apl_token::id(),
+        from_account,
+        to_account,
+        owner_pubkey,
+        &[],
+        amount,
+    )?;
+    Ok(ArchMessage::new(&[transfer_ix], Some(*owner_pubkey), poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?))
+}
+
+/// Signs a message from one of the builders above with `signers` for
+/// `network` and waits for it to be processed. The message keeps the block
+/// hash it was built with, so a message left unsigned for too long will be
+/// rejected as stale.
+pub fn sign_and_send(
+    client: &ArchRpcClient,
+    message: ArchMessage,
+    signers: Vec<Keypair>,
+    network: NetworkKind,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    let transaction = build_and_sign_transaction(message, signers, network.to_bitcoin_network());
+    let processed_tx = poll::send_once(client, transaction)?;
+    receipts::record(TransactionReceipt::from_processed("send signed message", &processed_tx));
+    if processed_tx.status != Status::Processed {
+        return Err(TokenError::TransactionFailed {
+            reason: crate::display::summarize_processed(std::slice::from_ref(&processed_tx)),
+        }
+        .into());
+    }
+    Ok(processed_tx)
+}
+
+/// Placeholder for a signature not collected yet. A real BIP-322 signature is
+/// never all zeros.
+const MISSING_SIGNATURE: [u8; 64] = [0; 64];
+
+/// Signs `message` for `network` with whichever of its required signers are
+/// in `available_signers`, leaving placeholders for the rest, and returns the
+/// transaction with the signers still missing, in message order. Pass it on
+/// to [`add_signatures`] until nothing is missing, then send it.
+pub fn partial_sign(
+    message: ArchMessage,
+    available_signers: &[Keypair],
+    network: NetworkKind,
+) -> (RuntimeTransaction, Vec<Pubkey>) {
+    let required = message.header.num_required_signatures as usize;
+    let mut transaction = RuntimeTransaction {
+        version: 0,
+        signatures: vec![Signature(MISSING_SIGNATURE.to_vec()); required],
+        message,
+    };
+    let missing = add_signatures(&mut transaction, available_signers, network);
+    (transaction, missing)
+}
+
+/// Fills in the signatures of any of `transaction`'s missing signers found in
+/// `signers`, signing for `network`; signatures already present are kept.
+/// Returns the signers still missing.
+pub fn add_signatures(transaction: &mut RuntimeTransaction, signers: &[Keypair], network: NetworkKind) -> Vec<Pubkey> {
+    let digest = transaction.message.hash();
+    let required = transaction.message.header.num_required_signatures as usize;
+
+    let mut missing = Vec::new();
+    for (key, signature) in transaction.message.account_keys.iter().take(required).zip(&mut transaction.signatures) {
+        if signature.0 != MISSING_SIGNATURE {
+            continue;
+        }
+        match signers.iter().find(|signer| arch_pubkey(signer) == *key) {
+            Some(signer) => *signature = Signature(sign_message_bip322(signer, &digest, network.to_bitcoin_network()).to_vec()),
+            None => missing.push(*key),
+        }
+    }
+    missing
+}
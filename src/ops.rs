@@ -0,0 +1,133 @@
+use std::{collections::HashMap, fmt};
+
+use arch_program::pubkey::Pubkey;
+
+use crate::{error::TokenError, math};
+
+/// Every token operation this crate can build a transaction for. Used for the
+/// CLI's `--list-ops` and as the one place operation help text lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenOp {
+    CreateMint,
+    CreateAccount,
+    Mint,
+    Transfer,
+    Burn,
+    Approve,
+    Revoke,
+    Freeze,
+    Thaw,
+    Close,
+    SetAuthority,
+}
+
+impl TokenOp {
+    pub const ALL: [TokenOp; 11] = [
+        TokenOp::CreateMint,
+        TokenOp::CreateAccount,
+        TokenOp::Mint,
+        TokenOp::Transfer,
+        TokenOp::Burn,
+        TokenOp::Approve,
+        TokenOp::Revoke,
+        TokenOp::Freeze,
+        TokenOp::Thaw,
+        TokenOp::Close,
+        TokenOp::SetAuthority,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            TokenOp::CreateMint => "Create and initialize a new token mint",
+            TokenOp::CreateAccount => "Create a token account holding a mint's tokens",
+            TokenOp::Mint => "Mint new tokens into a token account",
+            TokenOp::Transfer => "Move tokens between two accounts of the same mint",
+            TokenOp::Burn => "Destroy tokens from an account, reducing supply",
+            TokenOp::Approve => "Let a delegate spend up to an amount from an account",
+            TokenOp::Revoke => "Remove an account's delegate",
+            TokenOp::Freeze => "Block all movement in and out of an account",
+            TokenOp::Thaw => "Lift a freeze on an account",
+            TokenOp::Close => "Close an empty account and reclaim its lamports",
+            TokenOp::SetAuthority => "Change or remove a mint or account authority",
+        }
+    }
+
+    /// Who must sign the transaction, besides any newly created account.
+    pub fn required_signers(self) -> &'static [&'static str] {
+        match self {
+            TokenOp::CreateMint => &["payer", "mint"],
+            TokenOp::CreateAccount => &["payer", "token account"],
+            TokenOp::Mint => &["mint authority"],
+            TokenOp::Transfer | TokenOp::Burn => &["owner or delegate"],
+            TokenOp::Approve | TokenOp::Revoke => &["owner"],
+            TokenOp::Freeze | TokenOp::Thaw => &["freeze authority"],
+            TokenOp::Close => &["owner or close authority"],
+            TokenOp::SetAuthority => &["current authority"],
+        }
+    }
+}
+
+impl fmt::Display for TokenOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TokenOp::CreateMint => "create-mint",
+            TokenOp::CreateAccount => "create-account",
+            TokenOp::Mint => "mint",
+            TokenOp::Transfer => "transfer",
+            TokenOp::Burn => "burn",
+            TokenOp::Approve => "approve",
+            TokenOp::Revoke => "revoke",
+            TokenOp::Freeze => "freeze",
+            TokenOp::Thaw => "thaw",
+            TokenOp::Close => "close",
+            TokenOp::SetAuthority => "set-authority",
+        };
+        f.pad(name)
+    }
+}
+
+/// One balance-changing step of a planned sequence, for
+/// [`simulate_operations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenOpSpec {
+    Mint { account: Pubkey, amount: u64 },
+    Transfer { from: Pubkey, to: Pubkey, amount: u64 },
+    Burn { account: Pubkey, amount: u64 },
+}
+
+/// Applies `ops` in order to the token balances in `initial`, entirely in
+/// memory, and returns the balances that would result. Accounts missing from
+/// `initial` start at zero. Fails with [`TokenError::InsufficientBalance`] on
+/// the first transfer or burn that takes more than an account holds.
+pub fn simulate_operations(
+    initial: HashMap<Pubkey, u64>,
+    ops: &[TokenOpSpec],
+) -> Result<HashMap<Pubkey, u64>, TokenError> {
+    let mut balances = initial;
+    for op in ops {
+        match *op {
+            TokenOpSpec::Mint { account, amount } => credit(&mut balances, account, amount)?,
+            TokenOpSpec::Transfer { from, to, amount } => {
+                debit(&mut balances, from, amount)?;
+                credit(&mut balances, to, amount)?;
+            }
+            TokenOpSpec::Burn { account, amount } => debit(&mut balances, account, amount)?,
+        }
+    }
+
+    Ok(balances)
+}
+
+fn credit(balances: &mut HashMap<Pubkey, u64>, account: Pubkey, amount: u64) -> Result<(), TokenError> {
+    let balance = balances.entry(account).or_default();
+    *balance = math::checked_add(*balance, amount)?;
+    Ok(())
+}
+
+fn debit(balances: &mut HashMap<Pubkey, u64>, account: Pubkey, amount: u64) -> Result<(), TokenError> {
+    let balance = balances.entry(account).or_default();
+    *balance = balance
+        .checked_sub(amount)
+        .ok_or(TokenError::InsufficientBalance { account, balance: *balance, amount })?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use arch_program::pubkey::Pubkey;
+use arch_sdk::ArchRpcClient;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::TokenError, unpack_mint, unpack_token_account};
+
+/// Supply, decimals, authorities and balances of a mint and a set of its
+/// token accounts, as read in a single RPC round trip.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenOverview {
+    #[serde(with = "crate::pubkey_serde")]
+    pub mint_pubkey: Pubkey,
+    pub supply: u64,
+    pub decimals: u8,
+    #[serde(with = "crate::pubkey_serde::option")]
+    pub mint_authority: Option<Pubkey>,
+    #[serde(with = "crate::pubkey_serde::option")]
+    pub freeze_authority: Option<Pubkey>,
+    /// Balance of each requested account, in the order given.
+    #[serde(with = "crate::pubkey_serde::pairs")]
+    pub balances: Vec<(Pubkey, u64)>,
+}
+
+impl TokenOverview {
+    /// Sum of the listed accounts' balances.
+    pub fn total_balance(&self) -> u64 {
+        self.balances.iter().map(|(_, balance)| balance).sum()
+    }
+}
+
+/// Fetches the mint and every account in `accounts` with one
+/// `get_multiple_accounts` call. Fails with [`TokenError::Decode`] if any of
+/// them is missing or not the expected kind.
+pub fn load_token_overview(
+    client: &ArchRpcClient,
+    mint_pubkey: Pubkey,
+    accounts: &[Pubkey],
+) -> Result<TokenOverview, Box<dyn std::error::Error>> {
+    let mut pubkeys = Vec::with_capacity(accounts.len() + 1);
+    pubkeys.push(mint_pubkey);
+    pubkeys.extend_from_slice(accounts);
+
+    let infos = client.get_multiple_accounts(pubkeys.clone())?;
+    let data_of = |i: usize, expected: &'static str| {
+        infos
+            .get(i)
+            .and_then(Option::as_ref)
+            .map(|info| info.data.as_slice())
+            .ok_or(TokenError::Decode { account: pubkeys[i], expected })
+    };
+
+    let mint = unpack_mint(mint_pubkey, data_of(0, "mint")?)?;
+    let balances = accounts
+        .iter()
+        .enumerate()
+        .map(|(i, pubkey)| Ok((*pubkey, unpack_token_account(*pubkey, data_of(i + 1, "token account")?)?.amount)))
+        .collect::<Result<Vec<_>, TokenError>>()?;
+
+    Ok(TokenOverview {
+        mint_pubkey,
+        supply: mint.supply,
+        decimals: mint.decimals,
+        mint_authority: mint.mint_authority.into(),
+        freeze_authority: mint.freeze_authority.into(),
+        balances,
+    })
+}
@@ -0,0 +1,234 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime},
+};
+
+use arch_sdk::{ArchRpcClient, ProcessedTransaction, RuntimeTransaction, Status};
+use log::info;
+
+/// How long [`send_and_poll`] waits for every transaction to settle.
+const POLL_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Times [`send_with_blockhash_retry`] rebuilds a transaction whose block
+/// hash expired before giving up.
+pub const MAX_BLOCKHASH_RETRIES: u32 = 3;
+
+/// Oldest best block [`fresh_blockhash`] accepts by default. Well inside the
+/// window a node still accepts a hash in, so a transaction built with it has
+/// time to be signed and sent.
+pub const MAX_BLOCKHASH_AGE: Duration = Duration::from_secs(30);
+
+/// Times [`fresh_blockhash`] fetches the best block hash looking for a recent
+/// enough one.
+const FRESH_BLOCKHASH_ATTEMPTS: u32 = 3;
+
+/// Progress of a submitted transaction, as reported by [`send_and_poll`] and
+/// [`poll_statuses`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TxState {
+    /// Submitted, but the node doesn't know about it yet.
+    Pending,
+    /// Queued by the node, not yet executed.
+    Processing,
+    Processed,
+    Failed(String),
+}
+
+impl TxState {
+    fn from_status(status: &Status) -> Self {
+        match status {
+            Status::Queued => TxState::Processing,
+            Status::Processed => TxState::Processed,
+            Status::Failed(reason) => TxState::Failed(reason.clone()),
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        matches!(self, TxState::Processed | TxState::Failed(_))
+    }
+}
+
+/// Like `send_transactions_and_wait`, but calls `on_update(txid, state)` each
+/// time a transaction changes state, so callers can show live progress. Each
+/// settled transaction is recorded as a receipt for `operation`. Returns the
+/// processed transactions in submission order.
+pub fn send_and_poll(
+    client: &ArchRpcClient,
+    operation: &str,
+    transactions: Vec<RuntimeTransaction>,
+    mut on_update: impl FnMut(&str, &TxState),
+) -> Result<Vec<ProcessedTransaction>, Box<dyn std::error::Error>> {
+    let txids = client.send_transactions(transactions)?;
+    txids.iter().for_each(|txid| crate::receipts::record_submitted(txid));
+
+    let mut states = vec![TxState::Pending; txids.len()];
+    let mut processed: Vec<Option<ProcessedTransaction>> = vec![None; txids.len()];
+    for txid in &txids {
+        on_update(txid, &TxState::Pending);
+    }
+
+    let started = Instant::now();
+    while !states.iter().all(TxState::is_final) {
+        if started.elapsed() > POLL_TIMEOUT {
+            return Err(format!("Timed out after {:?} waiting for transactions to be processed", POLL_TIMEOUT).into());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+
+        for (i, txid) in txids.iter().enumerate() {
+            if states[i].is_final() {
+                continue;
+            }
+            let Some(processed_tx) = client.get_processed_transaction(txid)? else {
+                continue;
+            };
+
+            let state = TxState::from_status(&processed_tx.status);
+            if state != states[i] {
+                on_update(txid, &state);
+                states[i] = state;
+            }
+            if states[i].is_final() {
+                crate::receipts::record(crate::receipts::TransactionReceipt::from_processed(operation, &processed_tx));
+            }
+            processed[i] = Some(processed_tx);
+        }
+    }
+
+    Ok(processed.into_iter().flatten().collect())
+}
+
+/// Submits `transactions` and returns their txids straight away, without
+/// waiting for any of them. Submission is all that's known to have happened:
+/// callers must check the returned ids with [`poll_statuses`] to learn
+/// whether each one was processed or failed.
+pub fn send_no_wait(
+    client: &ArchRpcClient,
+    transactions: Vec<RuntimeTransaction>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let txids = client.send_transactions(transactions)?;
+    txids.iter().for_each(|txid| crate::receipts::record_submitted(txid));
+    Ok(txids)
+}
+
+/// Current state of each transaction in `txids`, in the same order, from one
+/// query per id. Ids the node hasn't seen yet come back as
+/// [`TxState::Pending`]; call again later to see them settle.
+pub fn poll_statuses(
+    client: &ArchRpcClient,
+    txids: &[String],
+) -> Result<Vec<(String, TxState)>, Box<dyn std::error::Error>> {
+    txids
+        .iter()
+        .map(|txid| {
+            let state = match client.get_processed_transaction(txid)? {
+                Some(processed_tx) => TxState::from_status(&processed_tx.status),
+                None => TxState::Pending,
+            };
+            Ok((txid.clone(), state))
+        })
+        .collect()
+}
+
+/// [`poll_statuses`] grouped by state, e.g. to count how much of a
+/// fire-and-forget batch has settled. Failures are grouped by reason. Each
+/// group keeps the order of `txids`.
+pub fn group_statuses(
+    client: &ArchRpcClient,
+    txids: &[String],
+) -> Result<HashMap<TxState, Vec<String>>, Box<dyn std::error::Error>> {
+    Ok(group_by_state(poll_statuses(client, txids)?))
+}
+
+pub(crate) fn group_by_state(statuses: Vec<(String, TxState)>) -> HashMap<TxState, Vec<String>> {
+    let mut groups: HashMap<TxState, Vec<String>> = HashMap::new();
+    for (txid, state) in statuses {
+        groups.entry(state).or_default().push(txid);
+    }
+    groups
+}
+
+/// Sends `transaction` and waits for it, unless the node already knows its
+/// txid, in which case the earlier submission's result is returned instead.
+/// Re-running a script that crashed after submitting therefore can't apply
+/// the same signed transaction twice.
+pub fn send_once(
+    client: &ArchRpcClient,
+    transaction: RuntimeTransaction,
+) -> Result<ProcessedTransaction, Box<dyn std::error::Error>> {
+    let mut txid = transaction.txid();
+    if client.get_processed_transaction(&txid)?.is_some() {
+        info!("Transaction {} was already submitted; reusing its result", txid);
+    } else {
+        txid = client.send_transaction(transaction)?;
+        crate::receipts::record_submitted(&txid);
+    }
+    Ok(client.wait_for_processed_transaction(&txid)?)
+}
+
+/// Runs `build_and_send` with a block hash from `fresh_blockhash`, and if it
+/// fails because that hash expired, fetches a new one and runs it again, up
+/// to [`MAX_BLOCKHASH_RETRIES`] times. Resending the old transaction would
+/// fail the same way: the message has to be rebuilt and re-signed, which
+/// gives it a new signature and txid. Other errors are returned as-is.
+pub fn send_with_blockhash_retry<T>(
+    mut fresh_blockhash: impl FnMut() -> Result<String, Box<dyn std::error::Error>>,
+    mut build_and_send: impl FnMut(String) -> Result<T, Box<dyn std::error::Error>>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let mut retries = 0;
+    loop {
+        match build_and_send(fresh_blockhash()?) {
+            Err(e) if is_blockhash_error(&e.to_string()) && retries < MAX_BLOCKHASH_RETRIES => {
+                retries += 1;
+                info!("Block hash expired ({}); rebuilding, retry {}/{}", e, retries, MAX_BLOCKHASH_RETRIES);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The best block hash, provided its block is at most `max_age` old;
+/// otherwise it waits briefly and fetches again, a few times. If no recent
+/// enough block shows up, the newest hash is returned anyway and the node has
+/// the final say. The age check needs the block's timestamp: if the node
+/// can't return the block, the hash is returned unchecked.
+pub fn fresh_blockhash(client: &ArchRpcClient, max_age: Duration) -> Result<String, Box<dyn std::error::Error>> {
+    let mut attempt = 1;
+    loop {
+        let hash = client.get_best_block_hash()?;
+        let Ok(Some(block)) = client.get_block_by_hash(&hash) else {
+            return Ok(hash);
+        };
+
+        let age = block_age(block.timestamp, SystemTime::now());
+        if age <= max_age {
+            return Ok(hash);
+        }
+        if attempt == FRESH_BLOCKHASH_ATTEMPTS {
+            info!("Best block {} is {:?} old; using it anyway", hash, age);
+            return Ok(hash);
+        }
+        info!("Best block {} is {:?} old; fetching again, attempt {}/{}", hash, age, attempt, FRESH_BLOCKHASH_ATTEMPTS);
+        attempt += 1;
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// How long before `now` a block with `timestamp` was produced. Timestamps
+/// large enough to be milliseconds since the epoch are read as such, smaller
+/// ones as seconds. A timestamp ahead of `now` is zero old.
+pub(crate) fn block_age(timestamp: u128, now: SystemTime) -> Duration {
+    // 10^12 ms is 2001; 10^12 s is tens of thousands of years away
+    let produced = if timestamp >= 1_000_000_000_000 {
+        Duration::from_millis(timestamp.min(u64::MAX as u128) as u64)
+    } else {
+        Duration::from_secs(timestamp as u64)
+    };
+    let now = now.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+    now.saturating_sub(produced)
+}
+
+/// Whether a send or processing error is the node rejecting a stale block hash.
+pub(crate) fn is_blockhash_error(message: &str) -> bool {
+    message.to_lowercase().contains("blockhash")
+}
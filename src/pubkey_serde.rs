@@ -0,0 +1,90 @@
+//! Serde helpers writing pubkeys as their canonical hex string, the same form
+//! `Display` prints and [`parse_pubkey`](crate::parse_pubkey) reads, instead
+//! of the derived byte array. Use with `#[serde(with = "...")]`: this module
+//! for a single `Pubkey`, or the submodule matching the field's shape.
+
+use arch_program::pubkey::Pubkey;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    parse(&String::deserialize(deserializer)?)
+}
+
+fn parse<E: serde::de::Error>(s: &str) -> Result<Pubkey, E> {
+    crate::parse_pubkey(s).map_err(|e| E::custom(e.to_string()))
+}
+
+/// `Option<Pubkey>`, with `None` as `null`.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(pubkey: &Option<Pubkey>, serializer: S) -> Result<S::Ok, S::Error> {
+        match pubkey {
+            Some(pubkey) => serializer.collect_str(pubkey),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+        Option::<String>::deserialize(deserializer)?.map(|s| parse(&s)).transpose()
+    }
+}
+
+/// `Vec<Pubkey>` as an array of strings.
+pub mod vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(pubkeys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pubkeys.iter().map(Pubkey::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Pubkey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?.iter().map(|s| parse(s)).collect()
+    }
+}
+
+/// `Vec<(Pubkey, T)>` as an array of `[pubkey, value]` pairs, keeping order.
+pub mod pairs {
+    use serde::Serialize;
+
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize>(pairs: &[(Pubkey, T)], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pairs.iter().map(|(pubkey, value)| (pubkey.to_string(), value)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(Pubkey, T)>, D::Error> {
+        Vec::<(String, T)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(s, value)| Ok((parse(&s)?, value)))
+            .collect()
+    }
+}
+
+/// `BTreeMap<Pubkey, T>` as an object keyed by pubkey string.
+pub mod map {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize>(map: &BTreeMap<Pubkey, T>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(map.iter().map(|(pubkey, value)| (pubkey.to_string(), value)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<Pubkey, T>, D::Error> {
+        BTreeMap::<String, T>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(s, value)| Ok((parse(&s)?, value)))
+            .collect()
+    }
+}
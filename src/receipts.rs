@@ -0,0 +1,157 @@
+use std::{fmt, fs::OpenOptions, io::Write, path::Path, sync::Mutex};
+
+use arch_program::pubkey::Pubkey;
+use arch_sdk::{ProcessedTransaction, Status};
+use serde::{Deserialize, Serialize};
+
+/// Token instruction tags whose data carries a `u64` amount right after the
+/// tag: Transfer, Approve, MintTo, Burn and their checked variants.
+const AMOUNT_INSTRUCTION_TAGS: [u8; 8] = [3, 4, 7, 8, 12, 13, 14, 15];
+
+/// Audit record of one transaction sent by this crate's helpers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    /// The helper's action, e.g. "mint tokens".
+    pub operation: String,
+    pub signature: String,
+    /// Every account the instructions read or wrote, in first-use order.
+    #[serde(with = "crate::pubkey_serde::vec")]
+    pub accounts_touched: Vec<Pubkey>,
+    /// Sum of the token amounts moved, for instructions that carry one.
+    pub amount: Option<u64>,
+    pub status: Status,
+    /// Free-form tag for filtering the log later. Never sent on-chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl TransactionReceipt {
+    pub fn from_processed(operation: &str, processed_tx: &ProcessedTransaction) -> Self {
+        let message = &processed_tx.runtime_transaction.message;
+
+        let mut accounts_touched: Vec<Pubkey> = Vec::new();
+        let mut amount: Option<u64> = None;
+        for instruction in &message.instructions {
+            for index in &instruction.accounts {
+                if let Some(key) = message.get_account_key(*index as usize) {
+                    if !accounts_touched.contains(key) {
+                        accounts_touched.push(*key);
+                    }
+                }
+            }
+
+            let is_token = message.get_account_key(instruction.program_id_index as usize) == Some(&apl_token::id());
+            if let (true, Some(tag), Some(bytes)) = (is_token, instruction.data.first(), instruction.data.get(1..9)) {
+                if AMOUNT_INSTRUCTION_TAGS.contains(tag) {
+                    let value = u64::from_le_bytes(bytes.try_into().expect("slice of 8"));
+                    amount = Some(amount.unwrap_or(0).saturating_add(value));
+                }
+            }
+        }
+
+        Self {
+            operation: operation.to_string(),
+            signature: processed_tx.runtime_transaction.txid(),
+            accounts_touched,
+            amount,
+            status: processed_tx.status.clone(),
+            label: None,
+        }
+    }
+
+    pub fn with_label(self, label: &str) -> Self {
+        Self { label: Some(label.to_string()), ..self }
+    }
+
+    /// One log line: status, operation, amount, accounts and signature, e.g.
+    /// `✅ transfer tokens: 1.5 tokens [ab12…yz89, cd34…wx67] (txid)`. The
+    /// amount is a UI amount when the mint's `decimals` are given, base
+    /// units otherwise. Pubkeys follow [`log_pubkey`](crate::display::log_pubkey).
+    pub fn to_human(&self, decimals: Option<u8>) -> String {
+        let icon = match &self.status {
+            Status::Processed => "✅",
+            Status::Failed(_) => "❌",
+            _ => "⏳",
+        };
+
+        let mut line = format!("{} {}", icon, self.operation);
+        match (self.amount, decimals) {
+            (Some(amount), Some(decimals)) => line += &format!(": {} tokens", crate::amount::TokenAmount::new(amount, decimals)),
+            (Some(amount), None) => line += &format!(": {} base units", amount),
+            (None, _) => {}
+        }
+        let accounts: Vec<String> = self.accounts_touched.iter().map(crate::display::log_pubkey).collect();
+        line += &format!(" [{}] ({})", accounts.join(", "), self.signature);
+        if let Status::Failed(reason) = &self.status {
+            line += &format!(" failed: {}", reason);
+        }
+        if let Some(label) = &self.label {
+            line += &format!(" #{}", label);
+        }
+        line
+    }
+}
+
+/// [`TransactionReceipt::to_human`] without the mint's decimals.
+impl fmt::Display for TransactionReceipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_human(None))
+    }
+}
+
+static SESSION: Mutex<Vec<TransactionReceipt>> = Mutex::new(Vec::new());
+static SUBMITTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LABEL: Mutex<Option<String>> = Mutex::new(None);
+
+/// Tags every receipt recorded from now on with `label`, or stops tagging
+/// with `None`. Receipts that already carry a label keep it.
+pub fn set_label(label: Option<&str>) {
+    *LABEL.lock().unwrap() = label.map(str::to_string);
+}
+
+/// Adds `receipt` to the process-wide log every helper records into.
+pub fn record(mut receipt: TransactionReceipt) {
+    if receipt.label.is_none() {
+        receipt.label = LABEL.lock().unwrap().clone();
+    }
+    SESSION.lock().unwrap().push(receipt);
+}
+
+/// Notes that `txid` was handed to the node, before its outcome is known.
+/// Every send path records here so an interrupted run can report what it
+/// already submitted.
+pub fn record_submitted(txid: &str) {
+    SUBMITTED.lock().unwrap().push(txid.to_string());
+}
+
+/// Every txid submitted so far, paired with whether it has a receipt yet,
+/// i.e. whether its outcome was seen. Those without one may still be in
+/// flight on the node.
+pub fn submitted() -> Vec<(String, bool)> {
+    let session = SESSION.lock().unwrap();
+    SUBMITTED
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|txid| (txid.clone(), session.iter().any(|receipt| receipt.signature == *txid)))
+        .collect()
+}
+
+/// Removes and returns everything recorded so far.
+pub fn take() -> Vec<TransactionReceipt> {
+    std::mem::take(&mut *SESSION.lock().unwrap())
+}
+
+/// The receipts tagged with `label`.
+pub fn filter_by_label(receipts: &[TransactionReceipt], label: &str) -> Vec<TransactionReceipt> {
+    receipts.iter().filter(|receipt| receipt.label.as_deref() == Some(label)).cloned().collect()
+}
+
+/// Appends `receipts` to `path` as JSON lines, creating the file if needed.
+pub fn append_jsonl(path: impl AsRef<Path>, receipts: &[TransactionReceipt]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for receipt in receipts {
+        writeln!(file, "{}", serde_json::to_string(receipt)?)?;
+    }
+    Ok(())
+}
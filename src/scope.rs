@@ -0,0 +1,181 @@
+use arch_program::pubkey::Pubkey;
+use arch_sdk::{build_and_sign_transaction, ArchRpcClient};
+use bitcoin::key::Keypair;
+use log::warn;
+
+use crate::{
+    arch_pubkey,
+    batch::{chunk_instruction_groups, send_batch},
+    burn_tokens, burns, close_token_account, create_token_account,
+    error::TokenError,
+    logger::emit,
+    network::NetworkKind,
+    poll, unpack_token_account,
+};
+
+struct TrackedAccount {
+    token_account: Pubkey,
+    mint: Pubkey,
+    owner_keypair: Keypair,
+}
+
+/// Keeps track of token accounts created during a test and tears them down
+/// afterwards: remaining balances are burned and the accounts closed, with the
+/// lamports going to `payer`. Runs on [`TestScope::cleanup`] or on drop.
+/// Failures are logged per account and never panic.
+pub struct TestScope<'a> {
+    client: &'a ArchRpcClient,
+    payer: Pubkey,
+    accounts: Vec<TrackedAccount>,
+}
+
+impl<'a> TestScope<'a> {
+    pub fn new(client: &'a ArchRpcClient, payer: Pubkey) -> Self {
+        Self {
+            client,
+            payer,
+            accounts: Vec::new(),
+        }
+    }
+
+    /// Creates a token account via [`create_token_account`] and tracks it.
+    pub fn create_token_account(
+        &mut self,
+        mint: Pubkey,
+        owner_keypair: Keypair,
+    ) -> Result<Pubkey, Box<dyn std::error::Error>> {
+        let token_account = create_token_account(self.client, mint, owner_keypair)?;
+        self.track(token_account, mint, owner_keypair);
+        Ok(token_account)
+    }
+
+    /// Tracks an account created elsewhere. `owner_keypair` must be able to
+    /// burn from and close it.
+    pub fn track(&mut self, token_account: Pubkey, mint: Pubkey, owner_keypair: Keypair) {
+        self.accounts.push(TrackedAccount {
+            token_account,
+            mint,
+            owner_keypair,
+        });
+    }
+
+    /// Burns and closes every tracked account. Returns how many were closed.
+    pub fn cleanup(&mut self) -> usize {
+        let mut closed = 0;
+        for tracked in std::mem::take(&mut self.accounts) {
+            match self.close(&tracked) {
+                Ok(()) => closed += 1,
+                Err(e) => warn!("Failed to clean up token account {}: {}", tracked.token_account, e),
+            }
+        }
+        closed
+    }
+
+    fn close(&self, tracked: &TrackedAccount) -> Result<(), Box<dyn std::error::Error>> {
+        let owner_pubkey = arch_pubkey(&tracked.owner_keypair);
+
+        let balance = unpack_token_account(tracked.token_account, &self.client.read_account_info(tracked.token_account)?.data)?.amount;
+        if balance > 0 {
+            burn_tokens(self.client, &tracked.token_account, &tracked.mint, &owner_pubkey, tracked.owner_keypair, balance)?;
+        }
+
+        close_token_account(self.client, &tracked.token_account, &self.payer, &owner_pubkey, tracked.owner_keypair)
+    }
+}
+
+impl Drop for TestScope<'_> {
+    fn drop(&mut self) {
+        if !self.accounts.is_empty() {
+            self.cleanup();
+        }
+    }
+}
+
+/// A token account and whether [`close_all`] closed it.
+pub type CloseResult = (Pubkey, Result<(), TokenError>);
+
+/// Burns whatever is left in each `(token_account, owner_keypair)` and closes
+/// it, sending the lamports to `rent_destination`. Accounts are packed as many
+/// to a transaction as fit, the first owner in each paying. Keeps going past
+/// failures: an account that can't be read fails alone, a failed transaction
+/// fails every account in it. Signs for `network`. Returns each account, in
+/// order, with its outcome.
+pub fn close_all(
+    client: &ArchRpcClient,
+    accounts: &[(Pubkey, Keypair)],
+    rent_destination: &Pubkey,
+    mint_pubkey: &Pubkey,
+    network: NetworkKind,
+) -> Result<Vec<CloseResult>, Box<dyn std::error::Error>> {
+    let mut results: Vec<CloseResult> = Vec::with_capacity(accounts.len());
+    let mut batched = Vec::new();
+    for (token_account, owner_keypair) in accounts {
+        let owner_pubkey = arch_pubkey(owner_keypair);
+        let balance = client
+            .read_account_info(*token_account)
+            .map_err(|e| TokenError::TransactionFailed { reason: e.to_string() })
+            .and_then(|info| unpack_token_account(*token_account, &info.data))
+            .map(|account| account.amount);
+        let prepared = balance.and_then(|balance| {
+            close_instructions(token_account, mint_pubkey, rent_destination, &owner_pubkey, balance)
+                .map(|instructions| (balance, instructions))
+        });
+        match prepared {
+            Ok((balance, instructions)) => {
+                batched.push((results.len(), *owner_keypair, balance, instructions));
+                results.push((*token_account, Ok(())));
+            }
+            Err(e) => results.push((*token_account, Err(e))),
+        }
+    }
+
+    let recent_blockhash = poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE)?;
+    let groups = batched.iter().map(|(_, _, _, instructions)| instructions.clone()).collect();
+    let mut transactions = Vec::new();
+    let mut ranges = Vec::new();
+    for (message, range) in chunk_instruction_groups(groups, None, &recent_blockhash)? {
+        let mut signers: Vec<Keypair> = Vec::with_capacity(range.len());
+        for (_, owner_keypair, _, _) in &batched[range.clone()] {
+            if !signers.iter().any(|signer| arch_pubkey(signer) == arch_pubkey(owner_keypair)) {
+                signers.push(*owner_keypair);
+            }
+        }
+        transactions.push(build_and_sign_transaction(message, signers, network.to_bitcoin_network()));
+        ranges.push(range);
+    }
+
+    let batch = send_batch(client, transactions);
+    for (group, (i, _, balance, _)) in batched.iter().enumerate() {
+        match batch.group_failure(&ranges, group) {
+            Some(reason) => results[*i].1 = Err(reason.clone()),
+            None => burns::session().record(*mint_pubkey, *balance),
+        }
+    }
+
+    let closed = results.iter().filter(|(_, result)| result.is_ok()).count();
+    emit!("🧹 Closed {} of {} token accounts", closed, accounts.len());
+    Ok(results)
+}
+
+/// Burn of the remaining `balance`, if any, then the close.
+fn close_instructions(
+    token_account: &Pubkey,
+    mint_pubkey: &Pubkey,
+    rent_destination: &Pubkey,
+    owner_pubkey: &Pubkey,
+    balance: u64,
+) -> Result<Vec<arch_program::instruction::Instruction>, TokenError> {
+    let build_error = |e: arch_program::program_error::ProgramError| TokenError::TransactionFailed { reason: e.to_string() };
+    let mut instructions = Vec::with_capacity(2);
+    if balance > 0 {
+        instructions.push(
+            apl_token::instruction::burn(&apl_token::id(), token_account, mint_pubkey, owner_pubkey, &[], balance)
+                .map_err(build_error)?,
+        );
+    }
+    instructions.push(
+        apl_token::instruction::close_account(&apl_token::id(), token_account, rent_destination, owner_pubkey, &[])
+            .map_err(build_error)?,
+    );
+    Ok(instructions)
+}
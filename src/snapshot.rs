@@ -0,0 +1,69 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use arch_program::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+
+use crate::{amount::TokenAmount, error::TokenError, get_account_summary, get_mint_info, logger::emit};
+
+/// One row of a balances snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceEntry {
+    pub label: String,
+    #[serde(with = "crate::pubkey_serde")]
+    pub account: Pubkey,
+    pub amount: u64,
+    pub ui_amount: String,
+}
+
+/// Reads every labelled token account and writes its balance to `out_path`
+/// as a JSON array of `{label, account, amount, ui_amount}`. Fails with
+/// [`TokenError::WrongMint`] on the first account that doesn't hold
+/// `mint_pubkey`, before anything is written.
+pub fn export_balances(
+    accounts: &[(String, Pubkey)],
+    mint_pubkey: Pubkey,
+    out_path: impl AsRef<Path>,
+) -> Result<Vec<BalanceEntry>, Box<dyn std::error::Error>> {
+    let decimals = get_mint_info(mint_pubkey)?.decimals;
+
+    let mut entries = Vec::with_capacity(accounts.len());
+    for (label, account) in accounts {
+        let summary = get_account_summary(*account)?;
+        if summary.mint != mint_pubkey {
+            return Err(TokenError::WrongMint { account: *account, expected: mint_pubkey, actual: summary.mint }.into());
+        }
+        let amount = summary.amount;
+        entries.push(BalanceEntry {
+            label: label.clone(),
+            account: *account,
+            amount,
+            ui_amount: TokenAmount::new(amount, decimals).to_string(),
+        });
+    }
+
+    fs::write(out_path.as_ref(), serde_json::to_string_pretty(&entries)?)?;
+    emit!("📝 Exported {} balances to {}", entries.len(), out_path.as_ref().display());
+
+    Ok(entries)
+}
+
+/// Loads a snapshot previously written by [`export_balances`].
+pub fn read_balance_snapshot(path: impl AsRef<Path>) -> Result<Vec<BalanceEntry>, Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Signed balance change per account (`after - before`), ordered by pubkey.
+/// Accounts missing from one side count as zero there, so new accounts show
+/// their full balance and vanished ones a full decrease. Unchanged accounts
+/// are included with a change of 0.
+pub fn diff_balances(before: &[(Pubkey, u64)], after: &[(Pubkey, u64)]) -> Vec<(Pubkey, i128)> {
+    let mut changes: BTreeMap<Pubkey, i128> = BTreeMap::new();
+    for (account, amount) in before {
+        *changes.entry(*account).or_default() -= *amount as i128;
+    }
+    for (account, amount) in after {
+        *changes.entry(*account).or_default() += *amount as i128;
+    }
+    changes.into_iter().collect()
+}
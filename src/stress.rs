@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+
+use arch_program::{pubkey::Pubkey, sanitized::ArchMessage};
+use arch_sdk::{build_and_sign_transaction, ArchRpcClient, Status};
+use bitcoin::key::Keypair;
+
+use crate::{
+    arch_pubkey, display,
+    logger::emit,
+    network::NetworkKind,
+    poll,
+    receipts::{self, TransactionReceipt},
+};
+
+/// Throughput and latency figures collected by [`stress_transfers`].
+#[derive(Debug, Clone, Default)]
+pub struct StressStats {
+    pub attempted: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// One message per failed transfer, in no particular order.
+    pub errors: Vec<String>,
+    pub elapsed: Duration,
+    pub min_latency: Duration,
+    pub max_latency: Duration,
+    pub avg_latency: Duration,
+}
+
+impl StressStats {
+    /// Successful transfers per second over the whole run.
+    pub fn throughput(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.succeeded as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Load-testing tool, not part of the normal lifecycle. Spawns one thread per
+/// `(token_account, owner_keypair)` pair; each thread sends `rounds` transfers
+/// of a single base unit to the next account in the list, signed for
+/// `network`. Individual failures are recorded in the returned stats instead
+/// of aborting the run.
+pub fn stress_transfers(
+    client: &ArchRpcClient,
+    accounts: Vec<(Pubkey, Keypair)>,
+    rounds: usize,
+    network: NetworkKind,
+) -> Result<StressStats, Box<dyn std::error::Error>> {
+    if accounts.len() < 2 {
+        return Err("Stress transfers need at least two accounts".into());
+    }
+
+    let started = Instant::now();
+    let results: Vec<Result<Duration, String>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, (from_account, owner_keypair))| {
+                let to_account = accounts[(i + 1) % accounts.len()].0;
+                scope.spawn(move || {
+                    (0..rounds)
+                        .map(|_| timed_transfer(client, from_account, &to_account, *owner_keypair, network))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|_| vec![Err("Transfer thread panicked".to_string())]))
+            .collect()
+    });
+
+    let mut stats = StressStats { elapsed: started.elapsed(), ..Default::default() };
+    let mut total_latency = Duration::ZERO;
+    for result in results {
+        stats.attempted += 1;
+        match result {
+            Ok(latency) => {
+                if stats.succeeded == 0 || latency < stats.min_latency {
+                    stats.min_latency = latency;
+                }
+                stats.max_latency = stats.max_latency.max(latency);
+                total_latency += latency;
+                stats.succeeded += 1;
+            }
+            Err(e) => {
+                stats.failed += 1;
+                stats.errors.push(e);
+            }
+        }
+    }
+    if stats.succeeded > 0 {
+        stats.avg_latency = total_latency / stats.succeeded as u32;
+    }
+
+    emit!(
+        "⏱️ Stress run: {}/{} transfers in {:?} ({:.2} tx/s)",
+        stats.succeeded,
+        stats.attempted,
+        stats.elapsed,
+        stats.throughput()
+    );
+    Ok(stats)
+}
+
+fn timed_transfer(
+    client: &ArchRpcClient,
+    from_account: &Pubkey,
+    to_account: &Pubkey,
+    owner_keypair: Keypair,
+    network: NetworkKind,
+) -> Result<Duration, String> {
+    let started = Instant::now();
+    let owner_pubkey = arch_pubkey(&owner_keypair);
+
+    let transfer_ix = apl_token::instruction::transfer(
+        &apl_token::id(),
+        from_account,
+        to_account,
+        &owner_pubkey,
+        &[],
+        1,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let transaction = build_and_sign_transaction(
+        ArchMessage::new(
+            &[transfer_ix],
+            Some(owner_pubkey),
+            poll::fresh_blockhash(client, poll::MAX_BLOCKHASH_AGE).map_err(|e| e.to_string())?,
+        ),
+        vec![owner_keypair],
+        network.to_bitcoin_network(),
+    );
+
+    // Go through the client directly: the test-sdk helper panics on RPC errors
+    let txid = client.send_transaction(transaction).map_err(|e| e.to_string())?;
+    receipts::record_submitted(&txid);
+    let processed_tx = client.wait_for_processed_transaction(&txid).map_err(|e| e.to_string())?;
+    receipts::record(TransactionReceipt::from_processed("stress transfer", &processed_tx));
+    if processed_tx.status != Status::Processed {
+        return Err(display::summarize_processed(std::slice::from_ref(&processed_tx)));
+    }
+
+    Ok(started.elapsed())
+}
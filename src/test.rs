@@ -3,16 +3,150 @@ mod tests {
     use crate::*;
     use apl_token::state::{Mint, Account};
     use arch_program::program_pack::Pack;
-    use arch_sdk::{generate_new_keypair, ArchRpcClient};
+    use arch_sdk::{generate_new_keypair, ArchRpcClient, Status};
     use arch_test_sdk::{
         constants::{BITCOIN_NETWORK, NODE1_ADDRESS},
-        helper::{create_and_fund_account_with_faucet, read_account_info},
+        helper::{read_account_info, try_read_account_info},
     };
 
     fn setup_test_client() -> ArchRpcClient {
         ArchRpcClient::new(NODE1_ADDRESS)
     }
 
+    #[track_caller]
+    fn assert_balance(token_account: arch_program::pubkey::Pubkey, expected: u64) {
+        let balance = get_token_balance(token_account)
+            .unwrap_or_else(|e| panic!("Failed to read balance of {}: {}", token_account, e));
+        assert_eq!(balance, expected, "Balance of {} should be {}", token_account, expected);
+    }
+
+    #[track_caller]
+    fn assert_supply(mint_pubkey: arch_program::pubkey::Pubkey, expected: u64) {
+        let mint = get_mint_info(mint_pubkey)
+            .unwrap_or_else(|e| panic!("Failed to read mint {}: {}", mint_pubkey, e));
+        assert_eq!(mint.supply, expected, "Supply of {} should be {}", mint_pubkey, expected);
+    }
+
+    #[track_caller]
+    fn assert_owner(token_account: arch_program::pubkey::Pubkey, expected: arch_program::pubkey::Pubkey) {
+        let account = Account::unpack(&read_account_info(token_account).data)
+            .unwrap_or_else(|e| panic!("Failed to unpack token account {}: {}", token_account, e));
+        assert_eq!(account.owner, expected, "Owner of {} should be {}", token_account, expected);
+    }
+
+    /// Fails unless `result` is a [`error::TokenError::ProgramFailed`] carrying
+    /// `expected`'s code and description.
+    #[track_caller]
+    fn assert_program_error<T>(result: Result<T, Box<dyn std::error::Error>>, expected: apl_token::error::TokenError) {
+        let Err(err) = result else {
+            panic!("Expected {:?}, but the transaction went through", expected);
+        };
+        match err.downcast_ref::<error::TokenError>() {
+            Some(error::TokenError::ProgramFailed { code, reason, .. }) => {
+                assert_eq!(*code, Some(expected.clone() as u32), "Unexpected error: {}", err);
+                assert_eq!(reason, &expected.to_string());
+            }
+            _ => panic!("Expected {:?}, got: {}", expected, err),
+        }
+    }
+
+    /// Fails unless every transaction in `processed_txs` reached
+    /// `Status::Processed`, naming the first one that didn't and why.
+    #[track_caller]
+    fn assert_processed(processed_txs: &[ProcessedTransaction]) {
+        for processed_tx in processed_txs {
+            if processed_tx.status != Status::Processed {
+                panic!(
+                    "Transaction {} was not processed: {}",
+                    processed_tx.runtime_transaction.txid(),
+                    display::summarize_processed(std::slice::from_ref(processed_tx))
+                );
+            }
+        }
+    }
+
+    /// Sends `transactions`, waits for all of them, and fails unless every
+    /// one was processed. Returns them in submission order.
+    #[track_caller]
+    fn send_expect_processed(client: &ArchRpcClient, transactions: Vec<arch_sdk::RuntimeTransaction>) -> Vec<ProcessedTransaction> {
+        let processed_txs = poll::send_and_poll(client, "test send", transactions, |_, _| {})
+            .unwrap_or_else(|e| panic!("Failed to send transactions: {}", e));
+        assert_processed(&processed_txs);
+        processed_txs
+    }
+
+    /// Sends `transactions`, waits for all of them, and fails unless every
+    /// one failed as `expected` according to [`error::classify_failure`].
+    #[track_caller]
+    fn send_expect_failure(client: &ArchRpcClient, transactions: Vec<arch_sdk::RuntimeTransaction>, expected: error::FailureKind) {
+        let processed_txs = poll::send_and_poll(client, "test send", transactions, |_, _| {})
+            .unwrap_or_else(|e| panic!("Failed to send transactions: {}", e));
+        for processed_tx in &processed_txs {
+            let txid = processed_tx.runtime_transaction.txid();
+            match error::classify_failure(processed_tx) {
+                Some(kind) => assert_eq!(kind, expected, "Transaction {} failed for the wrong reason: {:?}", txid, processed_tx.status),
+                None => panic!("Transaction {} was processed, expected it to fail with {:?}", txid, expected),
+            }
+        }
+    }
+
+    /// A mint and funded holders, set up in one call with
+    /// [`TestFixture::builder`].
+    struct TestFixture {
+        authority_keypair: bitcoin::key::Keypair,
+        mint: arch_program::pubkey::Pubkey,
+        /// In the order they were added to the builder.
+        holders: Vec<Holder>,
+    }
+
+    /// A faucet-funded owner and their token account.
+    struct Holder {
+        keypair: bitcoin::key::Keypair,
+        pubkey: arch_program::pubkey::Pubkey,
+        token_account: arch_program::pubkey::Pubkey,
+    }
+
+    struct TestFixtureBuilder {
+        decimals: u8,
+        balances: Vec<u64>,
+    }
+
+    impl TestFixture {
+        fn builder(decimals: u8) -> TestFixtureBuilder {
+            TestFixtureBuilder { decimals, balances: Vec::new() }
+        }
+    }
+
+    impl TestFixtureBuilder {
+        /// Adds a new owner whose token account starts with `balance`.
+        fn holder(mut self, balance: u64) -> Self {
+            self.balances.push(balance);
+            self
+        }
+
+        #[track_caller]
+        fn build(self, client: &ArchRpcClient) -> TestFixture {
+            let (authority_keypair, mint) = create_token_mint_with_options(client, self.decimals, None).unwrap();
+            let authority_pubkey = arch_pubkey(&authority_keypair);
+
+            let holders = self
+                .balances
+                .into_iter()
+                .map(|balance| {
+                    let (keypair, pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+                    fund_account_with_faucet(client, &keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+                    let token_account = create_token_account(client, mint, keypair).unwrap();
+                    if balance > 0 {
+                        mint_tokens(client, &mint, &token_account, &authority_pubkey, authority_keypair, balance).unwrap();
+                    }
+                    Holder { keypair, pubkey, token_account }
+                })
+                .collect();
+
+            TestFixture { authority_keypair, mint, holders }
+        }
+    }
+
     #[test]
     fn test_create_token_mint() {
         let client = setup_test_client();
@@ -30,9 +164,7 @@ mod tests {
         assert_eq!(mint_data.decimals, 9, "Mint should have 9 decimals");
         assert_eq!(mint_data.supply, 0, "Initial supply should be 0");
         
-        let expected_authority = arch_program::pubkey::Pubkey::from_slice(
-            &authority_keypair.x_only_public_key().0.serialize()
-        );
+        let expected_authority = arch_pubkey(&authority_keypair);
         assert_eq!(mint_data.mint_authority, Some(expected_authority).into(), "Mint authority should match");
     }
 
@@ -45,7 +177,7 @@ mod tests {
         
         // Create a user keypair
         let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user_keypair, BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
         
         // Create token account
         let result = create_token_account(&client, token_mint_pubkey, user_keypair);
@@ -61,9 +193,7 @@ mod tests {
         assert_eq!(account_data.mint, token_mint_pubkey, "Token account should reference correct mint");
         assert_eq!(account_data.amount, 0, "Initial token balance should be 0");
         
-        let expected_owner = arch_program::pubkey::Pubkey::from_slice(
-            &user_keypair.x_only_public_key().0.serialize()
-        );
+        let expected_owner = arch_pubkey(&user_keypair);
         assert_eq!(account_data.owner, expected_owner, "Token account owner should match");
     }
 
@@ -74,13 +204,11 @@ mod tests {
         // Setup: create mint and token account
         let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
         let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user_keypair, BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
         let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
         
         // Mint tokens
-        let authority_pubkey = arch_program::pubkey::Pubkey::from_slice(
-            &authority_keypair.x_only_public_key().0.serialize()
-        );
+        let authority_pubkey = arch_pubkey(&authority_keypair);
         let mint_amount = 1_000_000_000; // 1000 tokens with 9 decimals
         
         let result = mint_tokens(
@@ -94,112 +222,63 @@ mod tests {
         
         assert!(result.is_ok(), "Failed to mint tokens: {:?}", result.err());
         
-        // Verify the token account balance
-        let balance = get_token_balance(token_account_pubkey).unwrap();
-        assert_eq!(balance, mint_amount, "Token balance should equal minted amount");
-        
-        // Verify mint supply increased
-        let mint_account_info = read_account_info(token_mint_pubkey);
-        let mint_data = Mint::unpack(&mint_account_info.data).unwrap();
-        assert_eq!(mint_data.supply, mint_amount, "Mint supply should equal minted amount");
+        // Verify the token account balance and mint supply
+        assert_balance(token_account_pubkey, mint_amount);
+        assert_supply(token_mint_pubkey, mint_amount);
     }
 
     #[test]
     fn test_transfer_tokens() {
         let client = setup_test_client();
-        
-        // Setup: create mint, two users, and their token accounts
-        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
-        
-        let (user1_keypair, user1_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
-        let (user2_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
-        
-        create_and_fund_account_with_faucet(&user1_keypair, BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user2_keypair, BITCOIN_NETWORK);
-        
-        let user1_token_account = create_token_account(&client, token_mint_pubkey, user1_keypair).unwrap();
-        let user2_token_account = create_token_account(&client, token_mint_pubkey, user2_keypair).unwrap();
-        
-        // Mint tokens to user1
-        let authority_pubkey = arch_program::pubkey::Pubkey::from_slice(
-            &authority_keypair.x_only_public_key().0.serialize()
-        );
+
+        // Setup: user1 holds 1000 tokens, user2 none
         let initial_amount = 1_000_000_000; // 1000 tokens
-        mint_tokens(
-            &client,
-            &token_mint_pubkey,
-            &user1_token_account,
-            &authority_pubkey,
-            authority_keypair,
-            initial_amount,
-        ).unwrap();
-        
+        let fixture = TestFixture::builder(9).holder(initial_amount).holder(0).build(&client);
+        let [user1, user2] = &fixture.holders[..] else { unreachable!() };
+
         // Transfer tokens from user1 to user2
         let transfer_amount = 500_000_000; // 500 tokens
         let result = transfer_tokens(
             &client,
-            &user1_token_account,
-            &user2_token_account,
-            &user1_pubkey,
-            user1_keypair,
+            &user1.token_account,
+            &user2.token_account,
+            &user1.pubkey,
+            user1.keypair,
             transfer_amount,
         );
         
         assert!(result.is_ok(), "Failed to transfer tokens: {:?}", result.err());
         
         // Verify balances
-        let user1_balance = get_token_balance(user1_token_account).unwrap();
-        let user2_balance = get_token_balance(user2_token_account).unwrap();
-        
-        assert_eq!(user1_balance, initial_amount - transfer_amount, "User1 balance should be reduced by transfer amount");
-        assert_eq!(user2_balance, transfer_amount, "User2 balance should equal transfer amount");
+        assert_balance(user1.token_account, initial_amount - transfer_amount);
+        assert_balance(user2.token_account, transfer_amount);
     }
 
     #[test]
     fn test_burn_tokens() {
         let client = setup_test_client();
-        
-        // Setup: create mint, user, and token account with tokens
-        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
-        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user_keypair, BITCOIN_NETWORK);
-        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
-        
-        // Mint tokens
-        let authority_pubkey = arch_program::pubkey::Pubkey::from_slice(
-            &authority_keypair.x_only_public_key().0.serialize()
-        );
+
+        // Setup: one user holding 1000 tokens
         let initial_amount = 1_000_000_000; // 1000 tokens
-        mint_tokens(
-            &client,
-            &token_mint_pubkey,
-            &token_account_pubkey,
-            &authority_pubkey,
-            authority_keypair,
-            initial_amount,
-        ).unwrap();
-        
+        let fixture = TestFixture::builder(9).holder(initial_amount).build(&client);
+        let user = &fixture.holders[0];
+
         // Burn tokens
         let burn_amount = 100_000_000; // 100 tokens
         let result = burn_tokens(
             &client,
-            &token_account_pubkey,
-            &token_mint_pubkey,
-            &user_pubkey,
-            user_keypair,
+            &user.token_account,
+            &fixture.mint,
+            &user.pubkey,
+            user.keypair,
             burn_amount,
         );
         
         assert!(result.is_ok(), "Failed to burn tokens: {:?}", result.err());
         
-        // Verify balance reduced
-        let balance = get_token_balance(token_account_pubkey).unwrap();
-        assert_eq!(balance, initial_amount - burn_amount, "Balance should be reduced by burn amount");
-        
-        // Verify mint supply reduced
-        let mint_account_info = read_account_info(token_mint_pubkey);
-        let mint_data = Mint::unpack(&mint_account_info.data).unwrap();
-        assert_eq!(mint_data.supply, initial_amount - burn_amount, "Mint supply should be reduced by burn amount");
+        // Verify balance and mint supply reduced
+        assert_balance(user.token_account, initial_amount - burn_amount);
+        assert_supply(fixture.mint, initial_amount - burn_amount);
     }
 
     #[test]
@@ -209,7 +288,7 @@ mod tests {
         // Setup: create mint, user, and token account
         let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
         let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user_keypair, BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
         let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
         
         // Initial balance should be 0
@@ -217,9 +296,7 @@ mod tests {
         assert_eq!(initial_balance, 0, "Initial balance should be 0");
         
         // Mint some tokens
-        let authority_pubkey = arch_program::pubkey::Pubkey::from_slice(
-            &authority_keypair.x_only_public_key().0.serialize()
-        );
+        let authority_pubkey = arch_pubkey(&authority_keypair);
         let mint_amount = 500_000_000; // 500 tokens
         mint_tokens(
             &client,
@@ -252,8 +329,8 @@ mod tests {
         let (user1_keypair, user1_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
         let (user2_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
         
-        create_and_fund_account_with_faucet(&user1_keypair, BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user2_keypair, BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user1_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &user2_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
         
         let user1_token_account = create_token_account(&client, token_mint_pubkey, user1_keypair).unwrap();
         let user2_token_account = create_token_account(&client, token_mint_pubkey, user2_keypair).unwrap();
@@ -270,7 +347,7 @@ mod tests {
         );
         
         // This should fail due to insufficient balance
-        assert!(result.is_err(), "Transfer should fail with insufficient balance");
+        assert_program_error(result, apl_token::error::TokenError::InsufficientFunds);
     }
 
     #[test]
@@ -280,7 +357,7 @@ mod tests {
         // Setup: create mint, user, and token account with no tokens
         let (_, token_mint_pubkey) = create_token_mint(&client).unwrap();
         let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
-        create_and_fund_account_with_faucet(&user_keypair, BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
         let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
         
         // Try to burn tokens when account has 0 balance
@@ -295,6 +372,3571 @@ mod tests {
         );
         
         // This should fail due to insufficient balance
-        assert!(result.is_err(), "Burn should fail with insufficient balance");
+        assert_program_error(result, apl_token::error::TokenError::InsufficientFunds);
+    }
+
+    #[test]
+    fn test_export_balances() {
+        let client = setup_test_client();
+
+        // Setup: create mint, user, and token account with tokens
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        mint_tokens(
+            &client,
+            &token_mint_pubkey,
+            &token_account_pubkey,
+            &authority_pubkey,
+            authority_keypair,
+            1_500_000_000, // 1.5 tokens
+        ).unwrap();
+
+        // Export and re-read the snapshot
+        let out_path = std::env::temp_dir().join("test_export_balances.json");
+        let accounts = vec![("user".to_string(), token_account_pubkey)];
+        snapshot::export_balances(&accounts, token_mint_pubkey, &out_path).unwrap();
+
+        let entries = snapshot::read_balance_snapshot(&out_path).unwrap();
+        assert_eq!(entries.len(), 1, "Snapshot should contain one entry");
+        assert_eq!(entries[0].label, "user");
+        assert_eq!(entries[0].account, token_account_pubkey);
+        assert_eq!(entries[0].amount, 1_500_000_000);
+        assert_eq!(entries[0].ui_amount, "1.5", "UI amount should use the mint decimals");
+
+        // Another mint's account would be exported with the wrong decimals
+        let (_, other_mint) = create_token_mint(&client).unwrap();
+        let other_account = create_token_account(&client, other_mint, user_keypair).unwrap();
+        let accounts = vec![("user".to_string(), token_account_pubkey), ("other".to_string(), other_account)];
+        let err = snapshot::export_balances(&accounts, token_mint_pubkey, &out_path).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongMint { account: other_account, expected: token_mint_pubkey, actual: other_mint }),
+        );
+    }
+
+    #[test]
+    fn test_set_account_owner() {
+        let client = setup_test_client();
+
+        // Setup: create mint, two users, and their token accounts
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user1_keypair, user1_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (user2_keypair, user2_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        fund_account_with_faucet(&client, &user1_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &user2_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let user1_token_account = create_token_account(&client, token_mint_pubkey, user1_keypair).unwrap();
+        let user2_token_account = create_token_account(&client, token_mint_pubkey, user2_keypair).unwrap();
+
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let initial_amount = 1_000_000_000; // 1000 tokens
+        mint_tokens(
+            &client,
+            &token_mint_pubkey,
+            &user1_token_account,
+            &authority_pubkey,
+            authority_keypair,
+            initial_amount,
+        ).unwrap();
+
+        // Hand user1's account over to user2
+        let result = set_account_owner(
+            &client,
+            &user1_token_account,
+            &user2_pubkey,
+            &user1_pubkey,
+            user1_keypair,
+        );
+        assert!(result.is_ok(), "Failed to set account owner: {:?}", result.err());
+
+        assert_owner(user1_token_account, user2_pubkey);
+        assert_balance(user1_token_account, initial_amount);
+
+        // The previous owner can no longer move funds
+        let result = transfer_tokens(
+            &client,
+            &user1_token_account,
+            &user2_token_account,
+            &user1_pubkey,
+            user1_keypair,
+            100_000_000,
+        );
+        // Old owner should not be able to transfer
+        assert_program_error(result, apl_token::error::TokenError::OwnerMismatch);
+    }
+
+    #[test]
+    fn test_set_close_authority() {
+        let client = setup_test_client();
+
+        // Setup: create mint, user, and an empty token account
+        let (_, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        // A third party that will be allowed to close the account
+        let (closer_keypair, closer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &closer_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let result = set_close_authority(
+            &client,
+            &token_account_pubkey,
+            Some(&closer_pubkey),
+            &user_pubkey,
+            user_keypair,
+        );
+        assert!(result.is_ok(), "Failed to set close authority: {:?}", result.err());
+
+        let account_info = read_account_info(token_account_pubkey);
+        let account_data = Account::unpack(&account_info.data).unwrap();
+        assert_eq!(account_data.close_authority, Some(closer_pubkey).into(), "Close authority should match");
+
+        // The designated close authority closes the emptied account
+        let result = close_token_account(
+            &client,
+            &token_account_pubkey,
+            &closer_pubkey,
+            &closer_pubkey,
+            closer_keypair,
+        );
+        assert!(result.is_ok(), "Close authority should be able to close: {:?}", result.err());
+
+        let closed = try_read_account_info(token_account_pubkey)
+            .is_none_or(|info| info.data.iter().all(|byte| *byte == 0));
+        assert!(closed, "Token account should be closed");
+    }
+
+    #[test]
+    fn test_set_freeze_authority() {
+        let client = setup_test_client();
+
+        // Setup: a mint whose freeze authority is a separate compliance key
+        let (old_freeze_keypair, old_freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (new_freeze_keypair, new_freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &old_freeze_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &new_freeze_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let (_, token_mint_pubkey) = create_token_mint_with_options(&client, 9, Some(&old_freeze_pubkey)).unwrap();
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        // Rotate the freeze authority
+        let result = set_freeze_authority(
+            &client,
+            &token_mint_pubkey,
+            Some(&new_freeze_pubkey),
+            &old_freeze_pubkey,
+            old_freeze_keypair,
+        );
+        assert!(result.is_ok(), "Failed to rotate freeze authority: {:?}", result.err());
+
+        let mint_data = Mint::unpack(&read_account_info(token_mint_pubkey).data).unwrap();
+        assert_eq!(mint_data.freeze_authority, Some(new_freeze_pubkey).into(), "Freeze authority should be rotated");
+
+        // The old key can no longer freeze
+        let result = freeze_token_account(
+            &client,
+            &token_account_pubkey,
+            &token_mint_pubkey,
+            &old_freeze_pubkey,
+            old_freeze_keypair,
+        );
+        // Old freeze authority is turned away before anything is sent
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongAuthority {
+                account: token_mint_pubkey,
+                role: "freeze authority",
+                expected: Some(new_freeze_pubkey),
+                provided: old_freeze_pubkey,
+            })
+        );
+
+        // Removing the freeze authority disables freezing entirely
+        set_freeze_authority(&client, &token_mint_pubkey, None, &new_freeze_pubkey, new_freeze_keypair).unwrap();
+
+        let mint_data = Mint::unpack(&read_account_info(token_mint_pubkey).data).unwrap();
+        assert_eq!(mint_data.freeze_authority, None.into(), "Freeze authority should be removed");
+    }
+
+    #[test]
+    fn test_native_accounts_unsupported() {
+        let client = setup_test_client();
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        // None of these calls should reach the node; all must explain the limitation
+        let result = create_native_token_account(&client, owner_keypair, 1_000_000);
+        assert!(result.is_err(), "Native account creation should be rejected");
+
+        let result = sync_native_account(&client, &owner_pubkey, owner_keypair);
+        let err = result.expect_err("sync_native should be rejected");
+        assert!(err.to_string().contains("not supported"), "Error should explain the limitation: {}", err);
+
+        let result = unwrap_native(&client, &owner_pubkey, &owner_pubkey, owner_keypair);
+        let err = result.expect_err("unwrap_native should be rejected");
+        assert!(err.to_string().contains("not supported"), "Error should explain the limitation: {}", err);
+    }
+
+    #[test]
+    fn test_create_token_account_immutable() {
+        let client = setup_test_client();
+
+        let (_, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let result = create_token_account_immutable(&client, token_mint_pubkey, user_keypair);
+        assert!(result.is_ok(), "Failed to create immutable-owner account: {:?}", result.err());
+
+        let token_account_pubkey = result.unwrap();
+
+        // The extension must not get in the way of a normal, initialized account
+        let account_data = Account::unpack(&read_account_info(token_account_pubkey).data).unwrap();
+        assert_eq!(account_data.mint, token_mint_pubkey, "Token account should reference correct mint");
+        assert_eq!(account_data.owner, user_pubkey, "Token account owner should match");
+        assert_eq!(account_data.amount, 0, "Initial token balance should be 0");
+
+        // APL 0.5.2 only logs InitializeImmutableOwner, so the owner can still change
+        let (_, new_owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let result = set_account_owner(&client, &token_account_pubkey, &new_owner_pubkey, &user_pubkey, user_keypair);
+        assert!(result.is_ok(), "Owner change should still succeed: {:?}", result.err());
+        assert_owner(token_account_pubkey, new_owner_pubkey);
+    }
+
+    #[test]
+    fn test_get_token_account_size() {
+        let client = setup_test_client();
+
+        let (_, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let size = get_token_account_size(&client, token_mint_pubkey).unwrap();
+        assert_eq!(size, Account::LEN as u64, "Plain token accounts should need Account::LEN bytes");
+
+        // A funded wallet is not a mint
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let err = get_token_account_size(&client, user_pubkey).expect_err("Non-mint accounts should be rejected");
+        assert!(
+            matches!(err.downcast_ref::<error::TokenError>(), Some(error::TokenError::WrongProgram { account, .. }) if *account == user_pubkey),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_mint_tokens_detailed() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let processed_tx = mint_tokens_detailed(
+            &client,
+            &token_mint_pubkey,
+            &token_account_pubkey,
+            &authority_pubkey,
+            authority_keypair,
+            1_000,
+        ).unwrap();
+
+        assert_processed(std::slice::from_ref(&processed_tx));
+        assert!(!processed_tx.logs.is_empty(), "Processed transaction should carry program logs");
+        assert_balance(token_account_pubkey, 1_000);
+    }
+
+    #[test]
+    fn test_check_connection() {
+        let client = setup_test_client();
+        let health = check_connection(&client).unwrap();
+        assert!(!health.best_block_hash.is_empty(), "Best block hash should be set");
+    }
+
+    #[test]
+    fn test_check_connection_unreachable() {
+        // Nothing listens on the discard port
+        let client = ArchRpcClient::new("http://127.0.0.1:9");
+        let result = check_connection(&client);
+        assert!(result.is_err(), "Unreachable node should be reported as an error");
+    }
+
+    #[test]
+    fn test_fund_account_with_faucet() {
+        let client = setup_test_client();
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        // Enough to bankroll several account creations from one payer
+        let target = 3 * DEFAULT_FUNDING_LAMPORTS;
+        let balance = fund_account_with_faucet(&client, &payer_keypair, target).unwrap();
+        assert!(balance >= target, "Payer should hold at least {} lamports, has {}", target, balance);
+        assert_eq!(read_account_info(payer_pubkey).lamports, balance);
+    }
+
+    #[test]
+    fn test_token_amount_display_nine_decimals() {
+        assert_eq!(amount::TokenAmount::new(1_500_000_000, 9).to_string(), "1.5");
+        assert_eq!(amount::TokenAmount::new(1_000_000_000, 9).to_string(), "1");
+        assert_eq!(amount::TokenAmount::new(1, 9).to_string(), "0.000000001");
+        assert_eq!(amount::TokenAmount::new(0, 9).to_string(), "0");
+    }
+
+    #[test]
+    fn test_token_amount_display_six_decimals() {
+        assert_eq!(amount::TokenAmount::new(2_500_000, 6).to_string(), "2.5");
+        assert_eq!(amount::TokenAmount::new(123_456_789, 6).to_string(), "123.456789");
+        assert_eq!(amount::TokenAmount::new(10_000, 6).to_string(), "0.01");
+    }
+
+    #[test]
+    fn test_token_amount_display_zero_decimals() {
+        assert_eq!(amount::TokenAmount::new(42, 0).to_string(), "42");
+        assert_eq!(amount::TokenAmount::new(100, 0).to_string(), "100");
+        assert_eq!(amount::TokenAmount::new(0, 0).to_string(), "0");
+    }
+
+    #[test]
+    fn test_parse_pubkey() {
+        let pubkey = arch_program::pubkey::Pubkey::new_unique();
+        assert_eq!(parse_pubkey(&pubkey.to_string()).unwrap(), pubkey, "Display output should round-trip");
+
+        assert!(parse_pubkey("not-a-pubkey").is_err(), "Non-hex input should be rejected");
+        let err = parse_pubkey("abcd").unwrap_err();
+        assert!(err.to_string().contains("expected 32 bytes"), "Short input should explain the length: {}", err);
+    }
+
+    #[test]
+    fn test_create_token_mint_if_absent() {
+        let client = setup_test_client();
+        let (authority_keypair, authority_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (token_mint_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let (first, created) = create_token_mint_if_absent(&client, token_mint_keypair, &authority_keypair, 6, None).unwrap();
+        assert!(created, "First call should create the mint");
+        assert_eq!(first.decimals, 6);
+        assert_eq!(first.mint_authority, Some(authority_pubkey).into());
+
+        // Second run must find the existing mint instead of sending a transaction
+        let (second, created) = create_token_mint_if_absent(&client, token_mint_keypair, &authority_keypair, 6, None).unwrap();
+        assert!(!created, "Second call should reuse the existing mint");
+        assert_eq!(second, first, "Existing mint state should be returned unchanged");
+    }
+
+    #[test]
+    fn test_mint_tokens_verified() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let options = MintOptions { verify: true, ..Default::default() };
+        // Two rounds so the check has to account for prior supply
+        for _ in 0..2 {
+            let result = mint_tokens_with_options(
+                &client,
+                &token_mint_pubkey,
+                &token_account_pubkey,
+                &authority_pubkey,
+                authority_keypair,
+                250,
+                &options,
+            );
+            assert!(result.is_ok(), "Verified mint should succeed: {:?}", result.err());
+        }
+
+        assert_supply(token_mint_pubkey, 500);
+    }
+
+    #[test]
+    fn test_create_token_mint2() {
+        let client = setup_test_client();
+        let result = create_token_mint2(&client);
+
+        assert!(result.is_ok(), "Failed to create token mint: {:?}", result.err());
+
+        let (authority_keypair, token_mint_pubkey) = result.unwrap();
+
+        let mint_data = Mint::unpack(&read_account_info(token_mint_pubkey).data).unwrap();
+        assert_eq!(mint_data.decimals, 9, "Mint should have 9 decimals");
+        assert_eq!(mint_data.supply, 0, "Initial supply should be 0");
+
+        let expected_authority = arch_pubkey(&authority_keypair);
+        assert_eq!(mint_data.mint_authority, Some(expected_authority).into(), "Mint authority should match");
+    }
+
+    #[test]
+    fn test_stress_transfers() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let mut accounts = Vec::new();
+        for _ in 0..2 {
+            let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+            fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+            let token_account = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+            mint_tokens(&client, &token_mint_pubkey, &token_account, &authority_pubkey, authority_keypair, 100).unwrap();
+            accounts.push((token_account, user_keypair));
+        }
+
+        let stats = stress::stress_transfers(&client, accounts, 3, network::NetworkKind::default()).unwrap();
+        assert_eq!(stats.attempted, 6, "Every round on every thread should be attempted");
+        assert_eq!(stats.succeeded + stats.failed, stats.attempted);
+        assert_eq!(stats.errors.len(), stats.failed);
+    }
+
+    #[test]
+    fn test_token_client_blockhash_cache() {
+        let token_client = client::TokenClient::with_blockhash_cache(setup_test_client(), std::time::Duration::from_secs(30));
+
+        let first = token_client.recent_blockhash().unwrap();
+        assert_eq!(token_client.recent_blockhash().unwrap(), first, "Fresh hash should be served from the cache");
+
+        // Batch of mints reusing the cached hash
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&token_client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&token_client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&token_client, token_mint_pubkey, user_keypair).unwrap();
+
+        // Distinct amounts: identical instructions under one hash would be the same transaction
+        for amount in [10, 20, 30] {
+            token_client
+                .mint_tokens(&token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, amount)
+                .unwrap();
+        }
+        assert_balance(token_account_pubkey, 60);
+
+        token_client.invalidate_blockhash();
+        assert!(token_client.recent_blockhash().is_ok(), "Invalidated cache should refetch");
+    }
+
+    #[test]
+    fn test_scope_cleanup() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let mut scope = scope::TestScope::new(&client, authority_pubkey);
+        let token_account_pubkey = scope.create_token_account(token_mint_pubkey, user_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, 1_000).unwrap();
+
+        assert_eq!(scope.cleanup(), 1, "Funded account should be burned and closed");
+        assert_eq!(get_mint_info(token_mint_pubkey).unwrap().supply, 0, "Remaining balance should be burned");
+
+        let closed = try_read_account_info(token_account_pubkey)
+            .is_none_or(|info| info.data.iter().all(|byte| *byte == 0));
+        assert!(closed, "Token account should be closed");
+    }
+
+    #[test]
+    fn test_transfer_to_self_rejected() {
+        // Rejected before anything is sent, so no node is needed
+        let client = setup_test_client();
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let token_account = arch_program::pubkey::Pubkey::new_unique();
+
+        let result = transfer_tokens(&client, &token_account, &token_account, &owner_pubkey, owner_keypair, 1);
+        let err = result.expect_err("Self-transfer should be rejected by default");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::SelfTransfer { account: token_account }),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_get_delegation() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, 1_000).unwrap();
+
+        assert_eq!(get_delegation(token_account_pubkey).unwrap(), None, "New account should have no delegate");
+
+        let (_, delegate_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        approve_delegate(&client, &token_account_pubkey, &delegate_pubkey, &user_pubkey, user_keypair, 400).unwrap();
+        assert_eq!(get_delegation(token_account_pubkey).unwrap(), Some((delegate_pubkey, 400)));
+
+        revoke_delegate(&client, &token_account_pubkey, &user_pubkey, user_keypair).unwrap();
+        assert_eq!(get_delegation(token_account_pubkey).unwrap(), None, "Revoked delegation should be cleared");
+    }
+
+    #[test]
+    fn test_send_and_poll() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let mint_ix = apl_token::instruction::mint_to(
+            &apl_token::id(),
+            &token_mint_pubkey,
+            &token_account_pubkey,
+            &authority_pubkey,
+            &[],
+            500,
+        ).unwrap();
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[mint_ix], Some(authority_pubkey), client.get_best_block_hash().unwrap()),
+            vec![authority_keypair],
+            BITCOIN_NETWORK,
+        );
+
+        let mut updates = Vec::new();
+        let processed_txs = poll::send_and_poll(&client, "mint tokens", vec![transaction], |_, state| updates.push(state.clone())).unwrap();
+
+        assert_eq!(processed_txs.len(), 1);
+        let txid = processed_txs[0].runtime_transaction.txid();
+        assert!(receipts::submitted().contains(&(txid, true)), "A settled txid should no longer be in flight");
+        assert_eq!(updates.first(), Some(&poll::TxState::Pending), "First update should be the submission");
+        assert_eq!(updates.last(), Some(&poll::TxState::Processed), "Last update should be the final state");
+        assert_balance(token_account_pubkey, 500);
+    }
+
+    #[test]
+    fn test_multisig_transfer_and_burn() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let (signer1_keypair, signer1_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (signer2_keypair, signer2_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &signer1_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &signer2_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let multisig_pubkey = create_multisig(&client, signer1_keypair, &[signer1_pubkey, signer2_pubkey], 2).unwrap();
+
+        // Source account handed over to the 2-of-2 multisig
+        let source_account = create_token_account(&client, token_mint_pubkey, signer1_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &source_account, &authority_pubkey, authority_keypair, 1_000).unwrap();
+        set_account_owner(&client, &source_account, &multisig_pubkey, &signer1_pubkey, signer1_keypair).unwrap();
+
+        let (receiver_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &receiver_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let destination_account = create_token_account(&client, token_mint_pubkey, receiver_keypair).unwrap();
+
+        let result = transfer_tokens_multisig(&client, &source_account, &destination_account, &multisig_pubkey, &[signer1_keypair], 100);
+        assert!(result.is_err(), "One of two signers should not be enough");
+
+        let result = transfer_tokens_multisig(
+            &client,
+            &source_account,
+            &destination_account,
+            &multisig_pubkey,
+            &[signer1_keypair, signer2_keypair],
+            100,
+        );
+        assert!(result.is_ok(), "Both signers should authorize the transfer: {:?}", result.err());
+        assert_balance(destination_account, 100);
+
+        let result = burn_tokens_multisig(
+            &client,
+            &source_account,
+            &token_mint_pubkey,
+            &multisig_pubkey,
+            &[signer1_keypair, signer2_keypair],
+            200,
+        );
+        assert!(result.is_ok(), "Both signers should authorize the burn: {:?}", result.err());
+        assert_balance(source_account, 700);
+    }
+
+    #[test]
+    fn test_explorer_urls() {
+        let txid = "ab".repeat(32);
+        assert_eq!(
+            explorer::explorer_url_for_tx(&txid, bitcoin::Network::Bitcoin),
+            format!("https://mempool.space/tx/{}", txid)
+        );
+        assert_eq!(
+            explorer::explorer_url_for_tx(&txid, bitcoin::Network::Testnet),
+            format!("https://mempool.space/testnet4/tx/{}", txid)
+        );
+
+        let pubkey = arch_program::pubkey::Pubkey::new_unique();
+        assert_eq!(
+            explorer::explorer_url_for_account(&pubkey, bitcoin::Network::Bitcoin),
+            format!("https://mempool.space/address/{}", pubkey)
+        );
+        assert_eq!(
+            explorer::explorer_url_for_account(&pubkey, bitcoin::Network::Testnet),
+            format!("https://mempool.space/testnet4/address/{}", pubkey)
+        );
+    }
+
+    #[test]
+    fn test_create_token_accounts() {
+        let client = setup_test_client();
+
+        let (_, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (payer_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &payer_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        // More than one batch
+        let accounts = create_token_accounts(&client, token_mint_pubkey, 6, payer_keypair).unwrap();
+        assert_eq!(accounts.len(), 6);
+
+        for (owner_keypair, token_account_pubkey) in accounts {
+            let account_data = Account::unpack(&read_account_info(token_account_pubkey).data).unwrap();
+            let expected_owner = arch_pubkey(&owner_keypair);
+            assert_eq!(account_data.mint, token_mint_pubkey, "Token account should reference correct mint");
+            assert_eq!(account_data.owner, expected_owner, "Token account owner should match");
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_step_timings() {
+        let client = setup_test_client();
+
+        let result = run_token_lifecycle_with_options(&client, &LifecycleOptions { record_timings: true, ..Default::default() }).unwrap();
+        assert_eq!(result.step_timings.len(), 8, "Every lifecycle step should be timed");
+        assert_eq!(result.step_timings[0].step, "create token mint");
+        assert_eq!(result.step_timings[7].step, "burn tokens");
+
+        let untimed = run_token_lifecycle(&client).unwrap();
+        assert!(untimed.step_timings.is_empty(), "Timings should only be recorded on request");
+    }
+
+    #[test]
+    fn test_split_proportional_rounding() {
+        // 1000 over weights 1:1:1 leaves a remainder of 1
+        let shares = distribute::split_proportional(1_000, &[1, 1, 1]).unwrap();
+        assert_eq!(shares, vec![334, 333, 333], "Remainder should go to the first recipient");
+
+        let shares = distribute::split_proportional(1_000_000_007, &[7, 3, 13, 1]).unwrap();
+        assert_eq!(shares.iter().sum::<u64>(), 1_000_000_007, "Shares should sum to the total exactly");
+
+        // Large totals must not overflow the intermediate product
+        let shares = distribute::split_proportional(u64::MAX, &[u32::MAX, 1]).unwrap();
+        assert_eq!(shares.iter().sum::<u64>(), u64::MAX);
+
+        assert!(distribute::split_proportional(100, &[0, 0]).is_err(), "Zero total weight should be rejected");
+        assert!(distribute::split_proportional(100, &[]).is_err(), "No recipients should be rejected");
+    }
+
+    #[test]
+    fn test_mint_proportional() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (payer_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &payer_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let accounts = create_token_accounts(&client, token_mint_pubkey, 3, payer_keypair).unwrap();
+
+        let recipients: Vec<_> = accounts.iter().zip([5, 2, 2]).map(|((_, account), weight)| (*account, weight)).collect();
+        let result = distribute::mint_proportional(&client, &token_mint_pubkey, authority_keypair, 1_000, &recipients).unwrap();
+        assert!(result.is_complete(), "All mint batches should succeed: {:?}", result.failures);
+
+        let balances: Vec<u64> = recipients.iter().map(|(account, _)| get_token_balance(*account).unwrap()).collect();
+        assert_eq!(balances, vec![556, 222, 222]);
+        assert_supply(token_mint_pubkey, 1_000);
+    }
+
+    #[test]
+    fn test_read_non_token_account_wrong_program() {
+        let client = setup_test_client();
+
+        // A plain funded wallet exists on-chain but is owned by the system program
+        let (wallet_keypair, wallet_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &wallet_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let expected = error::TokenError::WrongProgram {
+            account: wallet_pubkey,
+            owner: arch_program::pubkey::Pubkey::system_program(),
+        };
+
+        let err = get_token_balance(wallet_pubkey).expect_err("Wallet should not read as a token account");
+        assert_eq!(err.downcast_ref::<error::TokenError>(), Some(&expected), "Unexpected error: {}", err);
+
+        let err = get_mint_info(wallet_pubkey).expect_err("Wallet should not read as a mint");
+        assert_eq!(err.downcast_ref::<error::TokenError>(), Some(&expected), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_network_kind_mapping() {
+        use network::NetworkKind;
+
+        assert_eq!(NetworkKind::Mainnet.to_bitcoin_network(), bitcoin::Network::Bitcoin);
+        assert_eq!(NetworkKind::Testnet.to_bitcoin_network(), bitcoin::Network::Testnet);
+        assert_eq!(NetworkKind::Regtest.to_bitcoin_network(), bitcoin::Network::Regtest);
+        assert_eq!(NetworkKind::Signet.to_bitcoin_network(), bitcoin::Network::Signet);
+
+        // The default keeps matching the test SDK
+        assert_eq!(NetworkKind::default().to_bitcoin_network(), BITCOIN_NETWORK);
+        let token_client = client::TokenClient::new(setup_test_client()).with_network(NetworkKind::Testnet);
+        assert_eq!(token_client.network(), NetworkKind::Testnet);
+    }
+
+    #[test]
+    fn test_mint_authority_precheck() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let options = MintOptions { check_authority: true, ..Default::default() };
+        let err = mint_tokens_with_options(&client, &token_mint_pubkey, &token_account_pubkey, &user_pubkey, user_keypair, 10, &options)
+            .expect_err("Wrong authority should be caught before sending");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongAuthority {
+                account: token_mint_pubkey,
+                role: "mint authority",
+                expected: Some(authority_pubkey),
+                provided: user_pubkey,
+            }),
+            "Unexpected error: {}",
+            err
+        );
+
+        let result = mint_tokens_with_options(&client, &token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, 10, &options);
+        assert!(result.is_ok(), "Real authority should pass the pre-check: {:?}", result.err());
+
+        // No freeze authority on the default mint; the owner may close its own account
+        assert!(verify_freeze_authority(&client, token_mint_pubkey, &authority_pubkey).is_err());
+        assert!(verify_close_authority(&client, token_account_pubkey, &user_pubkey).is_ok());
+
+        // A wallet isn't a token account at all
+        let err = verify_close_authority(&client, user_pubkey, &user_pubkey).unwrap_err();
+        assert!(
+            matches!(err.downcast_ref::<error::TokenError>(), Some(error::TokenError::WrongProgram { account, .. }) if *account == user_pubkey),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_describe_transaction() {
+        // Built offline, so the blockhash only needs to be well-formed
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (mint_keypair, mint_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let create_account_ix = arch_program::system_instruction::create_account(
+            &payer_pubkey,
+            &mint_pubkey,
+            arch_program::account::MIN_ACCOUNT_LAMPORTS,
+            Mint::LEN as u64,
+            &apl_token::id(),
+        );
+        let initialize_mint_ix = apl_token::instruction::initialize_mint(
+            &apl_token::id(),
+            &mint_pubkey,
+            &payer_pubkey,
+            None,
+            9,
+        ).unwrap();
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[create_account_ix, initialize_mint_ix], Some(payer_pubkey), "00".repeat(32)),
+            vec![payer_keypair, mint_keypair],
+            BITCOIN_NETWORK,
+        );
+
+        let lines = describe::describe_transaction(&transaction);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("system::CreateAccount"), "Unexpected description: {}", lines[0]);
+        assert!(lines[0].contains(&format!("{}[sw]", payer_pubkey)), "Payer should be a writable signer: {}", lines[0]);
+        assert!(lines[1].contains("token::InitializeMint"), "Unexpected description: {}", lines[1]);
+        assert!(lines[1].contains(&mint_pubkey.to_string()), "Mint should be listed: {}", lines[1]);
+    }
+
+    #[test]
+    fn test_diff_balances() {
+        let increased = arch_program::pubkey::Pubkey::new_unique();
+        let decreased = arch_program::pubkey::Pubkey::new_unique();
+        let created = arch_program::pubkey::Pubkey::new_unique();
+        let removed = arch_program::pubkey::Pubkey::new_unique();
+
+        let before = [(increased, 100), (decreased, 500), (removed, 7)];
+        let after = [(increased, 250), (decreased, 0), (created, u64::MAX)];
+
+        let diff = snapshot::diff_balances(&before, &after);
+        let mut expected = vec![
+            (increased, 150),
+            (decreased, -500),
+            (created, u64::MAX as i128),
+            (removed, -7),
+        ];
+        expected.sort_by_key(|(account, _)| *account);
+        assert_eq!(diff, expected);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_ui_amount_round_trips(raw in proptest::prelude::any::<u64>(), decimals in 0u8..=19) {
+            let ui_amount = amount::TokenAmount::new(raw, decimals).to_string();
+            let parsed = apl_token::try_ui_amount_into_amount(ui_amount.clone(), decimals);
+            proptest::prop_assert_eq!(parsed, Ok(raw), "{} did not round-trip at {} decimals", ui_amount, decimals);
+        }
+
+        #[test]
+        fn prop_split_proportional_sums_to_total(
+            total in proptest::prelude::any::<u64>(),
+            weights in proptest::collection::vec(proptest::prelude::any::<u32>(), 1..32),
+        ) {
+            proptest::prop_assume!(weights.iter().any(|weight| *weight > 0));
+
+            let shares = distribute::split_proportional(total, &weights).unwrap();
+            proptest::prop_assert_eq!(shares.len(), weights.len());
+            proptest::prop_assert_eq!(shares.iter().map(|share| *share as u128).sum::<u128>(), total as u128);
+            for (share, weight) in shares.iter().zip(&weights).skip(1) {
+                if *weight == 0 {
+                    proptest::prop_assert_eq!(*share, 0, "Zero weight should get nothing");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mint_supply_overflow() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let options = MintOptions { verify: true, ..Default::default() };
+        mint_tokens_with_options(&client, &token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, u64::MAX - 5, &options).unwrap();
+
+        let err = mint_tokens_with_options(&client, &token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, 10, &options)
+            .expect_err("Overflowing mint should be rejected client-side");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::SupplyOverflow { mint: token_mint_pubkey, supply: u64::MAX - 5, amount: 10 }),
+            "Unexpected error: {}",
+            err
+        );
+        assert_eq!(get_mint_info(token_mint_pubkey).unwrap().supply, u64::MAX - 5, "Supply should be untouched");
+    }
+
+    #[test]
+    fn test_finalize_mint() {
+        let client = setup_test_client();
+
+        // One key holding both roles
+        let (authority_keypair, authority_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (token_mint_keypair, token_mint_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        create_token_mint_if_absent(&client, token_mint_keypair, &authority_keypair, 9, Some(&authority_pubkey)).unwrap();
+
+        let (_, new_freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let result = finalize_mint(&client, &token_mint_pubkey, None, Some(&new_freeze_pubkey), authority_keypair);
+        assert!(result.is_ok(), "Failed to finalize mint: {:?}", result.err());
+
+        let mint_data = Mint::unpack(&read_account_info(token_mint_pubkey).data).unwrap();
+        assert_eq!(mint_data.mint_authority, None.into(), "Mint authority should be removed");
+        assert_eq!(mint_data.freeze_authority, Some(new_freeze_pubkey).into(), "Freeze authority should be handed off");
+    }
+
+    #[test]
+    fn test_has_freeze_authority() {
+        let client = setup_test_client();
+
+        let (_, plain_mint_pubkey) = create_token_mint(&client).unwrap();
+        assert!(!has_freeze_authority(plain_mint_pubkey).unwrap(), "Default mint has no freeze authority");
+
+        let (_, freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (_, freezable_mint_pubkey) = create_token_mint_with_options(&client, 9, Some(&freeze_pubkey)).unwrap();
+        assert!(has_freeze_authority(freezable_mint_pubkey).unwrap(), "Mint created with a freeze authority should report it");
+    }
+
+    #[test]
+    fn test_estimate_fee() {
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let destination = arch_program::pubkey::Pubkey::new_unique();
+        let transfer_ix = arch_program::system_instruction::transfer(&payer_pubkey, &destination, 1_000);
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(payer_pubkey), "00".repeat(32)),
+            vec![payer_keypair],
+            BITCOIN_NETWORK,
+        );
+
+        assert_eq!(estimate_fee(&transaction).unwrap(), TRANSACTION_FEE_LAMPORTS);
+    }
+
+    #[test]
+    fn test_account_exists() {
+        let client = setup_test_client();
+
+        let (_, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        assert!(account_exists(token_account_pubkey), "Created token account should exist");
+        assert!(account_exists(token_mint_pubkey), "Created mint should exist");
+        assert!(!account_exists(user_pubkey), "Funded wallet holds no program data");
+        assert!(!account_exists(arch_program::pubkey::Pubkey::new_unique()), "Random pubkey should not exist");
+    }
+
+    #[test]
+    fn test_send_batch_mixed_results() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let build_mint = |authority: arch_program::pubkey::Pubkey, keypair, amount| {
+            let mint_ix = apl_token::instruction::mint_to(&apl_token::id(), &token_mint_pubkey, &token_account_pubkey, &authority, &[], amount).unwrap();
+            arch_sdk::build_and_sign_transaction(
+                arch_program::sanitized::ArchMessage::new(&[mint_ix], Some(authority), client.get_best_block_hash().unwrap()),
+                vec![keypair],
+                BITCOIN_NETWORK,
+            )
+        };
+
+        // Middle transaction is signed by someone without mint authority
+        let transactions = vec![
+            build_mint(authority_pubkey, authority_keypair, 100),
+            build_mint(user_pubkey, user_keypair, 200),
+            build_mint(authority_pubkey, authority_keypair, 300),
+        ];
+        let result = batch::send_batch(&client, transactions);
+
+        assert_eq!(result.successes.len(), 2, "Valid mints should go through");
+        assert_eq!(result.failures.len(), 1, "Exactly one transaction should fail");
+        assert_eq!(result.failures[0].0, 1, "The failure should be attributed to the second transaction");
+        assert!(
+            matches!(result.failures[0].1, error::TokenError::ProgramFailed { code: Some(0x4), ref reason, .. } if reason == "Owner does not match"),
+            "The program's reason should be surfaced: {}",
+            result.failures[0].1
+        );
+        assert!(!result.is_complete());
+        assert_balance(token_account_pubkey, 400);
+    }
+
+    #[test]
+    fn test_fund_account_faucet_failed() {
+        // Every faucet request fails against a dead endpoint, exhausting the retries
+        let client = ArchRpcClient::new("http://127.0.0.1:9");
+        let (keypair, pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let err = fund_account_with_faucet(&client, &keypair, DEFAULT_FUNDING_LAMPORTS)
+            .expect_err("Funding through an unreachable faucet should fail");
+        match err.downcast_ref::<error::TokenError>() {
+            Some(error::TokenError::FaucetFailed { account, attempts, .. }) => {
+                assert_eq!(*account, pubkey);
+                assert_eq!(*attempts, 5, "All retries should be used before giving up");
+            }
+            other => panic!("Expected FaucetFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_inspect_report() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (payer_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &payer_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let accounts: Vec<_> = create_token_accounts(&client, token_mint_pubkey, 2, payer_keypair)
+            .unwrap()
+            .into_iter()
+            .map(|(_, account)| account)
+            .collect();
+        mint_tokens(&client, &token_mint_pubkey, &accounts[0], &authority_pubkey, authority_keypair, 1_500_000_000).unwrap();
+
+        let report = inspect::inspect(&client, token_mint_pubkey, &accounts).unwrap();
+        assert_eq!(report.mint.supply, 1_500_000_000);
+        assert_eq!(report.accounts.len(), 2);
+
+        let rendered = report.to_string();
+        assert!(rendered.contains("supply:           1.5 (1500000000 raw)"), "Report should show the supply:\n{}", rendered);
+        assert!(rendered.contains("Accounts (2)"), "Report should count the accounts:\n{}", rendered);
+
+        let (_, other_mint) = create_token_mint(&client).unwrap();
+        let other_account = create_token_account(&client, other_mint, payer_keypair).unwrap();
+        let err = inspect::inspect(&client, token_mint_pubkey, &[accounts[0], other_account]).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongMint { account: other_account, expected: token_mint_pubkey, actual: other_mint }),
+        );
+    }
+
+    #[test]
+    fn test_token_amount_parse_exact() {
+        assert_eq!(amount::TokenAmount::parse("1.5", 9).unwrap().raw, 1_500_000_000);
+        assert_eq!(amount::TokenAmount::parse("0.000000001", 9).unwrap().raw, 1);
+        assert_eq!(amount::TokenAmount::parse("42", 0).unwrap().raw, 42);
+        // Trailing zeros beyond the precision carry no value
+        assert_eq!(amount::TokenAmount::parse("2.500000", 2).unwrap().raw, 250);
+    }
+
+    #[test]
+    fn test_token_amount_parse_rejects_over_precise() {
+        let err = amount::TokenAmount::parse("0.0000000001", 9).unwrap_err();
+        assert!(matches!(err, error::TokenError::InvalidAmount { .. }), "Unexpected error: {}", err);
+        assert!(err.to_string().contains("more than 9 decimal places"), "Unexpected error: {}", err);
+
+        assert!(amount::TokenAmount::parse("1.5", 0).is_err(), "Whole-number mints take no fraction");
+        assert!(amount::TokenAmount::parse("1.2.3", 9).is_err());
+        assert!(amount::TokenAmount::parse("-1", 9).is_err());
+        assert!(amount::TokenAmount::parse("", 9).is_err());
+        assert!(amount::TokenAmount::parse("18446744073709551616", 0).is_err(), "Overflow should be rejected");
+    }
+
+    #[test]
+    fn test_transfer_ui() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user1_keypair, user1_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (user2_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user1_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &user2_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let user1_token_account = create_token_account(&client, token_mint_pubkey, user1_keypair).unwrap();
+        let user2_token_account = create_token_account(&client, token_mint_pubkey, user2_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &user1_token_account, &authority_pubkey, authority_keypair, 10_000_000_000).unwrap();
+
+        transfer_ui(&client, &user1_token_account, &user2_token_account, &token_mint_pubkey, &user1_pubkey, user1_keypair, "2.25").unwrap();
+        assert_balance(user2_token_account, 2_250_000_000);
+
+        let result = transfer_ui(&client, &user1_token_account, &user2_token_account, &token_mint_pubkey, &user1_pubkey, user1_keypair, "0.0000000001");
+        assert!(result.is_err(), "Over-precise amount should be rejected");
+        assert_eq!(get_token_balance(user2_token_account).unwrap(), 2_250_000_000, "Nothing should move on rejection");
+    }
+
+    #[test]
+    fn test_bootstrap_token() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 5_000_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let mint = get_mint_info(token_mint_pubkey).unwrap();
+        assert_eq!(mint.decimals, 6);
+        assert_eq!(mint.supply, 5_000_000);
+        assert_balance(authority_token_account, 5_000_000);
+
+        let account_info = read_account_info(authority_token_account);
+        let account = Account::unpack(&account_info.data).unwrap();
+        assert_eq!(account.owner, authority_pubkey);
+        assert_eq!(account.mint, token_mint_pubkey);
+    }
+
+    #[test]
+    fn test_rent_exempt_minimum() {
+        let client = setup_test_client();
+
+        // The runtime charges a flat floor, so every token account size maps
+        // to the same minimum as the old blanket constant
+        for data_len in [Mint::LEN, Account::LEN, Multisig::LEN, 0] {
+            assert_eq!(
+                rent_exempt_minimum(&client, data_len).unwrap(),
+                arch_program::account::MIN_ACCOUNT_LAMPORTS,
+                "Unexpected minimum for {} bytes", data_len
+            );
+        }
+
+        let too_large = arch_program::entrypoint::MAX_PERMITTED_DATA_LENGTH + 1;
+        assert!(rent_exempt_minimum(&client, too_large).is_err(), "Oversized accounts should be rejected");
+    }
+
+    #[test]
+    fn test_transfer_or_create() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 9, 1_000_000_000).unwrap();
+
+        // The recipient has never touched the chain
+        let (_, recipient_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        assert_eq!(find_token_account(&client, &recipient_pubkey, &token_mint_pubkey).unwrap(), None);
+
+        let destination = transfer_or_create(
+            &client, &authority_token_account, &recipient_pubkey, &token_mint_pubkey,
+            authority_keypair, authority_keypair, 300_000_000,
+        ).unwrap();
+        assert_balance(destination, 300_000_000);
+        assert_eq!(find_token_account(&client, &recipient_pubkey, &token_mint_pubkey).unwrap(), Some(destination));
+
+        // A second send reuses the account instead of creating another
+        let again = transfer_or_create(
+            &client, &authority_token_account, &recipient_pubkey, &token_mint_pubkey,
+            authority_keypair, authority_keypair, 200_000_000,
+        ).unwrap();
+        assert_eq!(again, destination);
+        assert_balance(destination, 500_000_000);
+    }
+
+    #[test]
+    fn test_verify_processed_transaction() {
+        // Built offline, so the blockhash only needs to be well-formed
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let build = |lamports: u64| {
+            let transfer_ix = arch_program::system_instruction::transfer(
+                &payer_pubkey,
+                &arch_program::pubkey::Pubkey::new_unique(),
+                lamports,
+            );
+            arch_sdk::build_and_sign_transaction(
+                arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(payer_pubkey), "00".repeat(32)),
+                vec![payer_keypair],
+                BITCOIN_NETWORK,
+            )
+        };
+        let processed = |transaction: arch_sdk::RuntimeTransaction| ProcessedTransaction {
+            runtime_transaction: transaction,
+            status: Status::Processed,
+            bitcoin_txid: None,
+            logs: vec![],
+            rollback_status: arch_sdk::RollbackStatus::NotRolledback,
+        };
+
+        let signed = build(1_000);
+        assert_eq!(verify_processed_transaction(&signed, &processed(signed.clone())), Ok(()));
+
+        let err = verify_processed_transaction(&signed, &processed(build(2_000))).unwrap_err();
+        assert!(
+            matches!(&err, error::TokenError::Mismatch { txid, .. } if *txid == signed.txid()),
+            "Unexpected error: {}", err
+        );
+    }
+
+    #[test]
+    fn test_mint_tokens_verify_inclusion() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 9, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let options = MintOptions { verify_inclusion: true, ..MintOptions::default() };
+        mint_tokens_with_options(
+            &client, &token_mint_pubkey, &authority_token_account, &authority_pubkey,
+            authority_keypair, 1_000, &options,
+        ).unwrap();
+        assert_balance(authority_token_account, 1_000);
+    }
+
+    #[test]
+    fn test_chunk_instructions() {
+        // Built offline, so the blockhash only needs to be well-formed
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let mint_pubkey = arch_program::pubkey::Pubkey::new_unique();
+        let instructions: Vec<_> = (0..300)
+            .map(|i| {
+                apl_token::instruction::mint_to(
+                    &apl_token::id(),
+                    &mint_pubkey,
+                    &arch_program::pubkey::Pubkey::new_unique(),
+                    &payer_pubkey,
+                    &[],
+                    i,
+                ).unwrap()
+            })
+            .collect();
+
+        let messages = batch::chunk_instructions(instructions, payer_pubkey, &"00".repeat(32)).unwrap();
+        assert!(messages.len() >= 2, "300 mints should not fit one transaction");
+        assert_eq!(messages.iter().map(|message| message.instructions.len()).sum::<usize>(), 300);
+
+        for message in messages {
+            let transaction = arch_sdk::build_and_sign_transaction(message, vec![payer_keypair], BITCOIN_NETWORK);
+            transaction.check_tx_size_limit().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_validate_lifecycle() {
+        validate_lifecycle().unwrap();
+    }
+
+    #[test]
+    fn test_get_is_native() {
+        let client = setup_test_client();
+
+        let (_, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 9, 0).unwrap();
+
+        assert_eq!(get_is_native(authority_token_account).unwrap(), None);
+        assert!(get_is_native(token_mint_pubkey).is_err(), "A mint is not a token account");
+    }
+
+    #[test]
+    fn test_burn_and_close() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 9, 750_000_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (destination_keypair, destination_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &destination_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let rent = read_account_info(authority_token_account).lamports;
+        let destination_before = read_account_info(destination_pubkey).lamports;
+
+        burn_and_close(
+            &client,
+            &authority_token_account,
+            &token_mint_pubkey,
+            &authority_pubkey,
+            authority_keypair,
+            &destination_pubkey,
+        ).unwrap();
+
+        let closed = try_read_account_info(authority_token_account)
+            .is_none_or(|info| info.data.iter().all(|byte| *byte == 0));
+        assert!(closed, "Token account should be closed");
+        assert_eq!(get_mint_info(token_mint_pubkey).unwrap().supply, 0, "Balance should have been burned");
+        assert_eq!(read_account_info(destination_pubkey).lamports, destination_before + rent);
+    }
+
+    #[test]
+    fn test_token_amount_parse_rejects_ambiguous_forms() {
+        let reason = |input: &str| match amount::TokenAmount::parse(input, 9) {
+            Err(error::TokenError::InvalidAmount { reason, .. }) => reason,
+            other => panic!("{:?} should be rejected, got {:?}", input, other),
+        };
+
+        assert!(reason("1,000").contains("thousands separators"));
+        assert!(reason("1_000").contains("thousands separators"));
+        assert!(reason("1 000").contains("thousands separators"));
+        assert!(reason("1e9").contains("scientific notation"));
+        assert!(reason("1.5E3").contains("scientific notation"));
+        assert!(reason("+1").contains("signs"));
+        assert!(reason("-1").contains("signs"));
+        assert!(reason(".25").contains("expected digits"));
+        assert!(reason("1.").contains("expected digits"));
+        assert!(reason("0x10").contains("expected digits"));
+        assert!(reason("").contains("empty"));
+    }
+
+    #[test]
+    fn test_get_associated_token_address() {
+        let ata_program_id = arch_program::pubkey::Pubkey::new_unique();
+        let owner = arch_program::pubkey::Pubkey::new_unique();
+        let mint = arch_program::pubkey::Pubkey::new_unique();
+
+        let address = get_associated_token_address(&owner, &mint, &ata_program_id);
+        assert_eq!(address, get_associated_token_address(&owner, &mint, &ata_program_id), "Derivation should be deterministic");
+        assert_ne!(address, get_associated_token_address(&arch_program::pubkey::Pubkey::new_unique(), &mint, &ata_program_id));
+        assert_ne!(address, get_associated_token_address(&owner, &arch_program::pubkey::Pubkey::new_unique(), &ata_program_id));
+    }
+
+    #[test]
+    fn test_rotate_account_owner() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let new_owner = arch_program::pubkey::Pubkey::new_unique();
+        let ata_program_id = arch_program::pubkey::Pubkey::new_unique();
+
+        let is_canonical_ata = rotate_account_owner(
+            &client,
+            &authority_token_account,
+            &token_mint_pubkey,
+            &new_owner,
+            &authority_pubkey,
+            authority_keypair,
+            &ata_program_id,
+        ).unwrap();
+        assert!(!is_canonical_ata, "A migrated keypair account is never the derived ATA");
+
+        assert_owner(authority_token_account, new_owner);
+    }
+
+    #[test]
+    fn test_confirmation_level_depth() {
+        use client::ConfirmationLevel;
+
+        assert_eq!(ConfirmationLevel::default(), ConfirmationLevel::Processed);
+        assert_eq!(ConfirmationLevel::Processed.blocks_required(), 0);
+        assert!(ConfirmationLevel::Confirmed.blocks_required() > 0);
+        assert!(ConfirmationLevel::Finalized.blocks_required() > ConfirmationLevel::Confirmed.blocks_required());
+    }
+
+    #[test]
+    fn test_token_client_send_confirmed() {
+        let client = setup_test_client();
+        let token_client = client::TokenClient::new(setup_test_client())
+            .with_confirmation_timeout(std::time::Duration::from_secs(120));
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let mint_ix = apl_token::instruction::mint_to(
+            &apl_token::id(), &token_mint_pubkey, &authority_token_account, &authority_pubkey, &[], 42,
+        ).unwrap();
+
+        let processed_tx = token_client
+            .send(&[mint_ix], &authority_pubkey, vec![authority_keypair], "mint tokens", client::ConfirmationLevel::Confirmed)
+            .unwrap();
+        assert_eq!(processed_tx.status, Status::Processed);
+        assert_balance(authority_token_account, 42);
+    }
+
+    #[test]
+    fn test_create_token_mint_with_keypair() {
+        let client = setup_test_client();
+
+        let (mint_keypair, expected_mint_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (authority_keypair, authority_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let token_mint_pubkey = create_token_mint_with_keypair(&client, mint_keypair, &authority_keypair, 2, None).unwrap();
+        assert_eq!(token_mint_pubkey, expected_mint_pubkey, "Mint should live at the supplied keypair's address");
+
+        let mint = get_mint_info(token_mint_pubkey).unwrap();
+        assert_eq!(mint.decimals, 2);
+        assert_eq!(mint.mint_authority, Some(authority_pubkey).into());
+    }
+
+    #[test]
+    fn test_dedupe_recipients() {
+        let alice = arch_program::pubkey::Pubkey::new_unique();
+        let bob = arch_program::pubkey::Pubkey::new_unique();
+        let recipients = [(alice, 2), (bob, 1), (alice, 3)];
+
+        assert_eq!(
+            distribute::dedupe_recipients(&recipients, false),
+            Err(error::TokenError::DuplicateRecipient { recipient: alice })
+        );
+        assert_eq!(distribute::dedupe_recipients(&recipients, true).unwrap(), vec![(alice, 5), (bob, 1)]);
+        assert_eq!(distribute::dedupe_recipients(&[(alice, 2), (bob, 1)], false).unwrap(), vec![(alice, 2), (bob, 1)]);
+
+        assert!(distribute::dedupe_recipients(&[(alice, u32::MAX), (alice, 1)], true).is_err(), "Merged weight overflow should be rejected");
+    }
+
+    #[test]
+    fn test_send_no_wait() {
+        let client = setup_test_client();
+        let token_client = client::TokenClient::new(setup_test_client());
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let mint_ix = |amount| apl_token::instruction::mint_to(
+            &apl_token::id(), &token_mint_pubkey, &authority_token_account, &authority_pubkey, &[], amount,
+        ).unwrap();
+
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[mint_ix(100)], Some(authority_pubkey), client.get_best_block_hash().unwrap()),
+            vec![authority_keypair],
+            BITCOIN_NETWORK,
+        );
+        let expected_txid = transaction.txid();
+        let mut txids = poll::send_no_wait(&client, vec![transaction]).unwrap();
+        txids.push(token_client.send_no_wait(&[mint_ix(200)], &authority_pubkey, vec![authority_keypair]).unwrap());
+        assert_eq!(txids[0], expected_txid);
+
+        // Nothing has been confirmed yet; settling is up to the caller
+        for txid in &txids {
+            assert_eq!(client.wait_for_processed_transaction(txid).unwrap().status, Status::Processed);
+        }
+        assert_balance(authority_token_account, 300);
+    }
+
+    #[test]
+    fn test_poll_statuses() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let processed_tx = mint_tokens_detailed(
+            &client, &token_mint_pubkey, &authority_token_account, &authority_pubkey, authority_keypair, 10,
+        ).unwrap();
+        let processed_txid = processed_tx.runtime_transaction.txid();
+        let unknown_txid = "00".repeat(32);
+
+        let statuses = poll::poll_statuses(&client, &[processed_txid.clone(), unknown_txid.clone()]).unwrap();
+        assert_eq!(statuses, vec![
+            (processed_txid, poll::TxState::Processed),
+            (unknown_txid, poll::TxState::Pending),
+        ]);
+    }
+
+    #[test]
+    fn test_burn_tracker_save_and_load() {
+        let mint_a = arch_program::pubkey::Pubkey::new_unique();
+        let mint_b = arch_program::pubkey::Pubkey::new_unique();
+
+        let tracker = burns::BurnTracker::new();
+        tracker.record(mint_a, 100);
+        tracker.record(mint_a, 50);
+        tracker.record(mint_b, 7);
+        assert_eq!(tracker.burned(mint_a), 150);
+        assert_eq!(tracker.burned(arch_program::pubkey::Pubkey::new_unique()), 0);
+
+        let path = std::env::temp_dir().join(format!("burns-{}.json", mint_a));
+        tracker.save(&path).unwrap();
+        let reloaded = burns::BurnTracker::new();
+        reloaded.load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(reloaded.burned(mint_a), 150);
+        assert_eq!(reloaded.burned(mint_b), 7);
+    }
+
+    #[test]
+    fn test_circulating_and_burned() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 0, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        burn_tokens(&client, &authority_token_account, &token_mint_pubkey, &authority_pubkey, authority_keypair, 100).unwrap();
+
+        assert_eq!(burns::circulating_and_burned(token_mint_pubkey).unwrap(), (900, 100));
+    }
+
+    #[test]
+    fn test_short_pubkey() {
+        let pubkey = arch_program::pubkey::Pubkey::from([0xab; 32]);
+        let short = display::short_pubkey(&pubkey);
+
+        assert_eq!(short, "abab…abab");
+        assert_eq!(short.chars().count(), 9, "Four characters from each end around an ellipsis");
+
+        let full = pubkey.to_string();
+        assert!(full.starts_with(&short[..4]) && full.ends_with(&short[short.len() - 4..]));
+    }
+
+    #[test]
+    fn test_freeze_thaw_workflow() {
+        let client = setup_test_client();
+
+        // Setup: a freezable mint with a funded holder and a recipient
+        let (freeze_keypair, freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &freeze_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let (authority_keypair, token_mint_pubkey) = create_token_mint_with_options(&client, 9, Some(&freeze_pubkey)).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (holder_keypair, holder_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (recipient_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &holder_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &recipient_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let holder_account = create_token_account(&client, token_mint_pubkey, holder_keypair).unwrap();
+        let recipient_account = create_token_account(&client, token_mint_pubkey, recipient_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &holder_account, &authority_pubkey, authority_keypair, 1_000).unwrap();
+
+        // Frozen: nothing moves
+        freeze_token_account(&client, &holder_account, &token_mint_pubkey, &freeze_pubkey, freeze_keypair).unwrap();
+        let account_data = Account::unpack(&read_account_info(holder_account).data).unwrap();
+        assert!(account_data.is_frozen(), "Account should be frozen");
+
+        let result = transfer_tokens(&client, &holder_account, &recipient_account, &holder_pubkey, holder_keypair, 100);
+        // Frozen accounts can neither send nor burn
+        assert_program_error(result, apl_token::error::TokenError::AccountFrozen);
+        let result = burn_tokens(&client, &holder_account, &token_mint_pubkey, &holder_pubkey, holder_keypair, 100);
+        assert_program_error(result, apl_token::error::TokenError::AccountFrozen);
+        assert_balance(holder_account, 1_000);
+        assert_supply(token_mint_pubkey, 1_000);
+
+        // Thawed: the same operations go through
+        thaw_token_account(&client, &holder_account, &token_mint_pubkey, &freeze_pubkey, freeze_keypair).unwrap();
+        let account_data = Account::unpack(&read_account_info(holder_account).data).unwrap();
+        assert!(!account_data.is_frozen(), "Account should be thawed");
+
+        transfer_tokens(&client, &holder_account, &recipient_account, &holder_pubkey, holder_keypair, 100).unwrap();
+        burn_tokens(&client, &holder_account, &token_mint_pubkey, &holder_pubkey, holder_keypair, 100).unwrap();
+        assert_balance(holder_account, 800);
+        assert_balance(recipient_account, 100);
+        assert_supply(token_mint_pubkey, 900);
+    }
+
+    #[test]
+    fn test_split_balance() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 0, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let accounts = distribute::split_balance(
+            &client, &authority_token_account, &authority_pubkey, authority_keypair, &token_mint_pubkey, 6, network::NetworkKind::default(),
+        ).unwrap();
+        assert_eq!(accounts.len(), 6);
+        let shares: Vec<u64> = accounts.iter().map(|(_, result)| *result.as_ref().unwrap()).collect();
+        let accounts: Vec<_> = accounts.into_iter().map(|(account, _)| account).collect();
+
+        let balances: Vec<u64> = accounts.iter().map(|account| get_token_balance(*account).unwrap()).collect();
+        assert_eq!(balances, vec![170, 166, 166, 166, 166, 166], "Remainder should go to the first account");
+        assert_eq!(shares, balances);
+        assert_eq!(balances.iter().sum::<u64>(), 1_000);
+        assert_balance(authority_token_account, 0);
+        for account in &accounts {
+            assert_owner(*account, authority_pubkey);
+        }
+    }
+
+    #[test]
+    fn test_send_once_ignores_duplicate_submission() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 0, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let mint_ix = apl_token::instruction::mint_to(
+            &apl_token::id(), &token_mint_pubkey, &authority_token_account, &authority_pubkey, &[], 100,
+        ).unwrap();
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[mint_ix], Some(authority_pubkey), client.get_best_block_hash().unwrap()),
+            vec![authority_keypair],
+            BITCOIN_NETWORK,
+        );
+
+        let first = poll::send_once(&client, transaction.clone()).unwrap();
+        let second = poll::send_once(&client, transaction).unwrap();
+
+        assert_processed(std::slice::from_ref(&first));
+        assert_eq!(first.runtime_transaction.txid(), second.runtime_transaction.txid(), "Rerun should return the prior result");
+        assert_balance(authority_token_account, 100);
+    }
+
+    #[test]
+    fn test_derive_ata_is_stable() {
+        let owner = arch_program::pubkey::Pubkey::from([1; 32]);
+        let mint = arch_program::pubkey::Pubkey::from([2; 32]);
+
+        let address = derive_ata(&owner, &mint);
+        assert_eq!(address, derive_ata(&owner, &mint));
+        assert_eq!(address, get_associated_token_address(&owner, &mint, &ASSOCIATED_TOKEN_PROGRAM_ID));
+        assert_ne!(address, derive_ata(&mint, &owner), "Owner and mint are not interchangeable");
+    }
+
+    #[test]
+    fn test_cli_parse_args() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let owner = arch_program::pubkey::Pubkey::from([1; 32]);
+        let mint = arch_program::pubkey::Pubkey::from([2; 32]);
+
+        assert_eq!(cli::parse_args(args(&[])).unwrap().command, cli::Command::Lifecycle);
+
+        let parsed = cli::parse_args(args(&["--receipts", "audit.jsonl"])).unwrap();
+        assert_eq!(parsed.command, cli::Command::Lifecycle);
+        assert_eq!(parsed.receipts_path.as_deref(), Some("audit.jsonl"));
+        assert!(cli::parse_args(args(&["--receipts"])).is_err(), "--receipts needs a path");
+        let parsed = cli::parse_args(args(&["--label", "launch", "--receipts", "audit.jsonl"])).unwrap();
+        assert_eq!(parsed.label.as_deref(), Some("launch"));
+        assert_eq!(parsed.receipts_path.as_deref(), Some("audit.jsonl"));
+
+        let parsed = cli::parse_args(args(&["ata", "--owner", &owner.to_string(), "--mint", &mint.to_string(), "--full-pubkeys"])).unwrap();
+        assert_eq!(parsed.command, cli::Command::Ata { owner, mint });
+        assert!(parsed.full_pubkeys);
+
+        assert!(cli::parse_args(args(&["ata", "--owner", &owner.to_string()])).is_err(), "Missing --mint should be rejected");
+        assert!(cli::parse_args(args(&["ata", "--owner", "zz", "--mint", &mint.to_string()])).is_err());
+        assert!(cli::parse_args(args(&["bogus"])).is_err());
+        assert_eq!(cli::parse_args(args(&["--list-ops"])).unwrap().command, cli::Command::ListOps);
+
+        let parsed = cli::parse_args(args(&["watch", "--account", &owner.to_string(), "--interval", "2"])).unwrap();
+        assert_eq!(parsed.command, cli::Command::Watch { account: owner, interval: std::time::Duration::from_secs(2) });
+        assert!(matches!(cli::parse_args(args(&["watch", "--account", &owner.to_string()])).unwrap().command, cli::Command::Watch { .. }));
+        assert!(cli::parse_args(args(&["watch", "--account", &owner.to_string(), "--interval", "0"])).is_err());
+        assert!(cli::parse_args(args(&["watch"])).is_err(), "Missing --account should be rejected");
+
+        let parsed = cli::parse_args(args(&["verify-mint", "--mint", &mint.to_string(), "--config", "mint.toml"])).unwrap();
+        assert_eq!(parsed.command, cli::Command::VerifyMint { mint, config: "mint.toml".to_string() });
+        assert!(cli::parse_args(args(&["verify-mint", "--mint", &mint.to_string()])).is_err(), "Missing --config should be rejected");
+
+        assert_eq!(cli::parse_args(args(&["bench", "--count", "50"])).unwrap().command, cli::Command::Bench { count: 50 });
+        assert!(matches!(cli::parse_args(args(&["bench"])).unwrap().command, cli::Command::Bench { .. }));
+        assert!(cli::parse_args(args(&["bench", "--count", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_create_token_mints() {
+        let client = setup_test_client();
+
+        let freeze_pubkey = arch_program::pubkey::Pubkey::new_unique();
+        let specs: Vec<MintSpec> = (0..6)
+            .map(|i| MintSpec { decimals: i, freeze_authority: (i % 2 == 0).then_some(freeze_pubkey) })
+            .collect();
+
+        let mints = create_token_mints(&client, &specs).unwrap();
+        assert_eq!(mints.len(), specs.len());
+
+        let authority_pubkey = arch_pubkey(&mints[0].0);
+        for ((authority_keypair, mint_pubkey), spec) in mints.iter().zip(&specs) {
+            assert_eq!(*authority_keypair, mints[0].0, "All mints should share one authority");
+            let mint = get_mint_info(*mint_pubkey).unwrap();
+            assert_eq!(mint.decimals, spec.decimals);
+            assert_eq!(mint.freeze_authority, spec.freeze_authority.into());
+            assert_eq!(mint.mint_authority, Some(authority_pubkey).into());
+        }
+    }
+
+    #[test]
+    fn test_transaction_receipt_from_processed() {
+        // Built offline, so the blockhash only needs to be well-formed
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let source = arch_program::pubkey::Pubkey::new_unique();
+        let destination = arch_program::pubkey::Pubkey::new_unique();
+        let transfer_ix = apl_token::instruction::transfer(&apl_token::id(), &source, &destination, &owner_pubkey, &[], 250).unwrap();
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(owner_pubkey), "00".repeat(32)),
+            vec![owner_keypair],
+            BITCOIN_NETWORK,
+        );
+        let processed_tx = ProcessedTransaction {
+            runtime_transaction: transaction.clone(),
+            status: Status::Processed,
+            bitcoin_txid: None,
+            logs: vec![],
+            rollback_status: arch_sdk::RollbackStatus::NotRolledback,
+        };
+
+        let receipt = receipts::TransactionReceipt::from_processed("transfer tokens", &processed_tx);
+        assert_eq!(receipt.operation, "transfer tokens");
+        assert_eq!(receipt.signature, transaction.txid());
+        assert_eq!(receipt.amount, Some(250));
+        assert_eq!(receipt.status, Status::Processed);
+        for account in [source, destination, owner_pubkey] {
+            assert!(receipt.accounts_touched.contains(&account), "{} should be listed", account);
+        }
+        assert!(!receipt.accounts_touched.contains(&apl_token::id()), "The program itself is not touched");
+
+        let path = std::env::temp_dir().join(format!("receipts-{}.jsonl", source));
+        receipts::append_jsonl(&path, &[receipt.clone(), receipt]).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<serde_json::Value> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["amount"], 250);
+        assert_eq!(lines[0]["accounts_touched"][0], source.to_string());
+        assert!(lines[0].get("label").is_none(), "Unlabelled receipts omit the field");
+    }
+
+    #[test]
+    fn test_transaction_receipt_label() {
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let transaction = arch_sdk::build_and_sign_transaction(
+            arch_program::sanitized::ArchMessage::new(&[], Some(owner_pubkey), "00".repeat(32)),
+            vec![owner_keypair],
+            BITCOIN_NETWORK,
+        );
+        let processed_tx = ProcessedTransaction {
+            runtime_transaction: transaction,
+            status: Status::Processed,
+            bitcoin_txid: None,
+            logs: vec![],
+            rollback_status: arch_sdk::RollbackStatus::NotRolledback,
+        };
+
+        let plain = receipts::TransactionReceipt::from_processed("mint tokens", &processed_tx);
+        let labelled = plain.clone().with_label("airdrop-round-1");
+
+        let json: serde_json::Value = serde_json::to_value(&labelled).unwrap();
+        assert_eq!(json["label"], "airdrop-round-1");
+
+        let filtered = receipts::filter_by_label(&[plain, labelled.clone()], "airdrop-round-1");
+        assert_eq!(filtered, vec![labelled]);
+    }
+
+    #[test]
+    fn test_create_token_account_twice() {
+        let client = setup_test_client();
+        let (authority_keypair, token_mint_pubkey, _) = bootstrap_token(&client, 6, 0).unwrap();
+        let (token_account_keypair, expected_account, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let token_account =
+            create_token_account_with_keypair(&client, token_mint_pubkey, authority_keypair, token_account_keypair).unwrap();
+        assert_eq!(token_account, expected_account);
+
+        let err = create_token_account_with_keypair(&client, token_mint_pubkey, authority_keypair, token_account_keypair)
+            .expect_err("Creating the same account twice should fail");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::AlreadyInitialized { account: token_account, mint: token_mint_pubkey }),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_oversized_message_does_not_fit() {
+        let payer_pubkey = arch_program::pubkey::Pubkey::new_unique();
+        let blockhash = "00".repeat(32);
+        let oversized = vec![arch_program::instruction::Instruction {
+            program_id: apl_token::id(),
+            accounts: vec![],
+            data: vec![0; arch_sdk::RUNTIME_TX_SIZE_LIMIT],
+        }];
+
+        let message = arch_program::sanitized::ArchMessage::new(&oversized, Some(payer_pubkey), blockhash.clone());
+        assert!(!batch::fits_in_transaction(&message));
+
+        match batch::build_message_checked(&oversized, payer_pubkey, &blockhash) {
+            Err(error::TokenError::TransactionTooLarge { size, max }) => {
+                assert_eq!(max, arch_sdk::RUNTIME_TX_SIZE_LIMIT);
+                assert!(size > max, "Measured size {} should exceed {}", size, max);
+            }
+            other => panic!("Expected TransactionTooLarge, got {:?}", other),
+        }
+
+        assert!(batch::chunk_instructions(oversized, payer_pubkey, &blockhash).is_err());
+    }
+
+    #[test]
+    fn test_transfer_verified() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let user_token_account = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let (from_after, to_after) =
+            transfer_verified(&client, &authority_token_account, &user_token_account, &authority_pubkey, authority_keypair, 300).unwrap();
+        assert_eq!((from_after, to_after), (700, 300));
+        assert_balance(authority_token_account, 700);
+        assert_balance(user_token_account, 300);
+    }
+
+    #[test]
+    fn test_no_faucet_on_mainnet() {
+        use crate::network::NetworkKind;
+
+        let token_client = client::TokenClient::new(setup_test_client()).with_network(NetworkKind::Mainnet);
+        let (keypair, pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        // Rejected before any faucet request is made
+        let err = token_client.fund_with_faucet(&keypair, DEFAULT_FUNDING_LAMPORTS).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::NoFaucet { network: NetworkKind::Mainnet, account: pubkey }),
+            "Unexpected error: {}",
+            err
+        );
+        assert!(err.to_string().contains("manually"), "Error should say how to proceed: {}", err);
+    }
+
+    #[test]
+    fn test_load_token_overview() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let user_token_account = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+        transfer_tokens(&client, &authority_token_account, &user_token_account, &authority_pubkey, authority_keypair, 400).unwrap();
+
+        let overview = overview::load_token_overview(&client, token_mint_pubkey, &[authority_token_account, user_token_account]).unwrap();
+        assert_eq!(overview.supply, 1_000);
+        assert_eq!(overview.decimals, 6);
+        assert_eq!(overview.mint_authority, Some(authority_pubkey));
+        assert_eq!(overview.balances, vec![(authority_token_account, 600), (user_token_account, 400)]);
+        assert_eq!(overview.total_balance(), overview.supply);
+    }
+
+    #[test]
+    fn test_transfer_verify_supply() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let user_token_account = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+
+        let options = TransferOptions { verify_supply: true, ..Default::default() };
+        transfer_tokens_with_options(&client, &authority_token_account, &user_token_account, &authority_pubkey, authority_keypair, 250, &options).unwrap();
+
+        assert_supply(token_mint_pubkey, 1_000);
+        assert_balance(user_token_account, 250);
+    }
+
+    #[test]
+    fn test_close_token_account_for_wrong_mint() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let other_mint = arch_program::pubkey::Pubkey::new_unique();
+
+        let err = close_token_account_for_mint(&client, &authority_token_account, &other_mint, &authority_pubkey, &authority_pubkey, authority_keypair)
+            .expect_err("Closing with the wrong mint should fail");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongMint { account: authority_token_account, expected: other_mint, actual: token_mint_pubkey }),
+            "Unexpected error: {}",
+            err
+        );
+        assert_balance(authority_token_account, 0);
+
+        close_token_account_for_mint(&client, &authority_token_account, &token_mint_pubkey, &authority_pubkey, &authority_pubkey, authority_keypair).unwrap();
+        let closed = try_read_account_info(authority_token_account)
+            .is_none_or(|info| info.data.iter().all(|byte| *byte == 0));
+        assert!(closed, "Token account should be closed");
+    }
+
+    #[test]
+    fn test_mint_to_supply() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 0).unwrap();
+
+        assert_eq!(mint_to_supply(&client, &token_mint_pubkey, &authority_token_account, authority_keypair, 1_000).unwrap(), 1_000);
+        assert_supply(token_mint_pubkey, 1_000);
+
+        assert_eq!(mint_to_supply(&client, &token_mint_pubkey, &authority_token_account, authority_keypair, 1_500).unwrap(), 500);
+        assert_supply(token_mint_pubkey, 1_500);
+
+        let err = mint_to_supply(&client, &token_mint_pubkey, &authority_token_account, authority_keypair, 1_200).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::SupplyAboveTarget { mint: token_mint_pubkey, supply: 1_500, target: 1_200 }),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_token_ops_documented() {
+        for op in ops::TokenOp::ALL {
+            assert!(!op.description().is_empty(), "{} has no description", op);
+            assert!(!op.required_signers().is_empty(), "{} lists no signers", op);
+        }
+    }
+
+    #[test]
+    fn test_send_with_blockhash_retry() {
+        // Stands in for the node: the first hash handed out has expired
+        let mut hashes = ["stale", "fresh"].into_iter();
+        let mut sent_with = Vec::new();
+        let result = poll::send_with_blockhash_retry(
+            || Ok(hashes.next().unwrap().to_string()),
+            |recent_blockhash| {
+                sent_with.push(recent_blockhash.clone());
+                if recent_blockhash == "stale" {
+                    return Err("Blockhash not found".into());
+                }
+                Ok(recent_blockhash)
+            },
+        );
+        assert_eq!(result.unwrap(), "fresh");
+        assert_eq!(sent_with, ["stale", "fresh"], "Transaction should be rebuilt with the new hash");
+
+        // Gives up after the cap, and doesn't retry unrelated errors
+        let mut attempts = 0;
+        let result: Result<(), _> = poll::send_with_blockhash_retry(
+            || Ok("stale".to_string()),
+            |_| {
+                attempts += 1;
+                Err("Blockhash not found".into())
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(attempts, poll::MAX_BLOCKHASH_RETRIES + 1);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = poll::send_with_blockhash_retry(
+            || Ok("fresh".to_string()),
+            |_| {
+                attempts += 1;
+                Err("insufficient funds".into())
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_get_account_summary() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 750).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        assert!(get_account_lamports(authority_pubkey).unwrap() > 0, "Funded wallet should hold lamports");
+
+        let summary = get_account_summary(authority_token_account).unwrap();
+        assert!(summary.lamports > 0, "Token account should carry its rent lamports");
+        assert_eq!(summary.amount, 750);
+        assert_eq!(summary.owner, authority_pubkey);
+        assert_eq!(summary.mint, token_mint_pubkey);
+    }
+
+    #[test]
+    fn test_math_boundaries() {
+        assert_eq!(math::checked_add(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert_eq!(
+            math::checked_add(u64::MAX, 1),
+            Err(error::TokenError::AmountOverflow { expression: format!("{} + 1", u64::MAX) })
+        );
+
+        assert_eq!(math::checked_sub(7, 7).unwrap(), 0);
+        assert_eq!(math::checked_sub(5, 7), Err(error::TokenError::AmountOverflow { expression: "5 - 7".to_string() }));
+
+        assert_eq!(math::scale_by_decimals(1_000, 9).unwrap(), 1_000_000_000_000);
+        assert_eq!(math::scale_by_decimals(42, 0).unwrap(), 42, "Zero decimals leaves the amount as is");
+        assert_eq!(math::scale_by_decimals(18, 18).unwrap(), 18_000_000_000_000_000_000);
+        assert!(math::scale_by_decimals(19, 18).is_err(), "19 * 10^18 exceeds u64::MAX");
+        assert!(math::scale_by_decimals(1, 20).is_err(), "10^20 itself exceeds u64::MAX");
+        assert_eq!(math::scale_by_decimals(0, 20).ok(), None, "The factor alone must fit");
+    }
+
+    #[test]
+    fn test_ensure_rent_exempt() {
+        let client = setup_test_client();
+        let required = rent_exempt_minimum(&client, Account::LEN).unwrap();
+
+        ensure_rent_exempt(&client, required, Account::LEN).unwrap();
+
+        let err = ensure_rent_exempt(&client, required - 1, Account::LEN).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::InsufficientRent { provided: required - 1, required }),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_transfer_tokens_with_payer() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) =
+            bootstrap_token(&client, 6, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        // The authority pays rent, so the owner never receives any lamports
+        let (owner_keypair, owner_token_account) =
+            create_token_accounts(&client, token_mint_pubkey, 1, authority_keypair).unwrap().remove(0);
+        let owner_pubkey = arch_pubkey(&owner_keypair);
+        transfer_tokens(&client, &authority_token_account, &owner_token_account, &authority_pubkey, authority_keypair, 400).unwrap();
+        assert!(get_account_lamports(owner_pubkey).unwrap_or(0) == 0, "Owner should be unfunded");
+
+        let (relayer_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &relayer_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        transfer_tokens_with_payer(&client, &owner_token_account, &authority_token_account, &owner_pubkey, owner_keypair, relayer_keypair, 150).unwrap();
+        assert_balance(owner_token_account, 250);
+        assert_balance(authority_token_account, 750);
+    }
+
+    #[test]
+    fn test_assert_same_mint() {
+        let client = setup_test_client();
+
+        let (authority_keypair, mint_a, account_a) = bootstrap_token(&client, 6, 100).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (_, mint_b, account_b) = bootstrap_token(&client, 6, 100).unwrap();
+        let account_a2 = create_token_account(&client, mint_a, authority_keypair).unwrap();
+
+        assert_eq!(assert_same_mint(account_a, account_a2).unwrap(), mint_a);
+
+        let err = assert_same_mint(account_a, account_b).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::MintMismatch { account: account_b, expected: mint_a, actual: mint_b }),
+            "Unexpected error: {}",
+            err
+        );
+
+        let options = TransferOptions { check_mint: true, ..Default::default() };
+        let err = transfer_tokens_with_options(&client, &account_a, &account_b, &authority_pubkey, authority_keypair, 10, &options).unwrap_err();
+        assert!(matches!(err.downcast_ref::<error::TokenError>(), Some(error::TokenError::MintMismatch { .. })), "Unexpected error: {}", err);
+        assert_balance(account_a, 100);
+    }
+
+    #[test]
+    fn test_launch_fixed_supply() {
+        let client = setup_test_client();
+        let (recipient_keypair, recipient_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let launch = launch_fixed_supply(&client, 6, 21_000_000, &recipient_pubkey).unwrap();
+        assert_eq!(launch.mint_authority, None);
+        assert_supply(launch.mint_pubkey, 21_000_000);
+        assert_balance(launch.token_account, 21_000_000);
+        assert_owner(launch.token_account, recipient_pubkey);
+
+        // Neither the former authority nor the holder can mint more
+        let former_authority = arch_pubkey(&launch.payer_keypair);
+        fund_account_with_faucet(&client, &recipient_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        for (authority_pubkey, authority_keypair) in [(former_authority, launch.payer_keypair), (recipient_pubkey, recipient_keypair)] {
+            let result = mint_tokens(&client, &launch.mint_pubkey, &launch.token_account, &authority_pubkey, authority_keypair, 1);
+            // Minting after launch should fail
+            assert_program_error(result, apl_token::error::TokenError::FixedSupply);
+        }
+        assert_supply(launch.mint_pubkey, 21_000_000);
+    }
+
+    #[test]
+    fn test_arch_pubkey_matches_sdk() {
+        let (keypair, sdk_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        assert_eq!(arch_pubkey(&keypair), sdk_pubkey);
+
+        // The faucet funds the account at the same address
+        let client = setup_test_client();
+        client.create_and_fund_account_with_faucet(&keypair, BITCOIN_NETWORK).unwrap();
+        assert!(get_account_lamports(arch_pubkey(&keypair)).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_simulate_operations() {
+        use ops::TokenOpSpec;
+        use std::collections::HashMap;
+
+        let alice = arch_program::pubkey::Pubkey::new_unique();
+        let bob = arch_program::pubkey::Pubkey::new_unique();
+        let carol = arch_program::pubkey::Pubkey::new_unique();
+
+        let plan = [
+            TokenOpSpec::Mint { account: alice, amount: 1_000 },
+            TokenOpSpec::Transfer { from: alice, to: bob, amount: 400 },
+            TokenOpSpec::Transfer { from: bob, to: carol, amount: 150 },
+            TokenOpSpec::Burn { account: alice, amount: 100 },
+        ];
+        let balances = ops::simulate_operations(HashMap::from([(bob, 50)]), &plan).unwrap();
+        assert_eq!(balances, HashMap::from([(alice, 500), (bob, 300), (carol, 150)]));
+
+        let overdraw = [TokenOpSpec::Transfer { from: alice, to: bob, amount: 1 }];
+        assert_eq!(
+            ops::simulate_operations(HashMap::new(), &overdraw),
+            Err(error::TokenError::InsufficientBalance { account: alice, balance: 0, amount: 1 })
+        );
+
+        let overburn = [TokenOpSpec::Mint { account: bob, amount: 5 }, TokenOpSpec::Burn { account: bob, amount: 6 }];
+        assert_eq!(
+            ops::simulate_operations(HashMap::new(), &overburn),
+            Err(error::TokenError::InsufficientBalance { account: bob, balance: 5, amount: 6 })
+        );
+
+        let overflow = [TokenOpSpec::Mint { account: carol, amount: 1 }];
+        assert!(ops::simulate_operations(HashMap::from([(carol, u64::MAX)]), &overflow).is_err());
+    }
+
+    #[test]
+    fn test_receipts_track_submitted() {
+        let txid = format!("{:064x}", 0x5eed_u64);
+        receipts::record_submitted(&txid);
+
+        let submitted = receipts::submitted();
+        assert!(submitted.contains(&(txid, false)), "Submitted txid without a receipt should be in flight");
+    }
+
+    #[test]
+    fn test_is_mint_authority() {
+        let client = setup_test_client();
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (unrelated_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        assert!(is_mint_authority(token_mint_pubkey, &authority_keypair).unwrap());
+        assert!(!is_mint_authority(token_mint_pubkey, &unrelated_keypair).unwrap());
+    }
+
+    #[test]
+    fn test_onboard_users() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, _) = bootstrap_token(&client, 0, 0).unwrap();
+        let owners: Vec<arch_program::pubkey::Pubkey> =
+            (0..6).map(|_| arch_sdk::generate_new_keypair(BITCOIN_NETWORK).1).collect();
+
+        let results = distribute::onboard_users(
+            &client, &token_mint_pubkey, authority_keypair, authority_keypair, &owners, 25, network::NetworkKind::default(),
+        ).unwrap();
+        assert_eq!(results.len(), owners.len());
+
+        for ((owner, result), expected_owner) in results.iter().zip(&owners) {
+            assert_eq!(owner, expected_owner, "Results should follow the owner order");
+            let account = result.clone().unwrap();
+            assert_balance(account, 25);
+            assert_owner(account, *owner);
+        }
+        assert_supply(token_mint_pubkey, 150);
+    }
+
+    #[test]
+    fn test_summarize_processed() {
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let stub = |lamports: u64, status: Status, rollback_status: arch_sdk::RollbackStatus| {
+            let transfer_ix = arch_program::system_instruction::transfer(
+                &payer_pubkey,
+                &arch_program::pubkey::Pubkey::new_unique(),
+                lamports,
+            );
+            ProcessedTransaction {
+                runtime_transaction: arch_sdk::build_and_sign_transaction(
+                    arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(payer_pubkey), "00".repeat(32)),
+                    vec![payer_keypair],
+                    BITCOIN_NETWORK,
+                ),
+                status,
+                bitcoin_txid: None,
+                logs: vec![],
+                rollback_status,
+            }
+        };
+        let txs = vec![
+            stub(1, Status::Processed, arch_sdk::RollbackStatus::NotRolledback),
+            stub(2, Status::Failed("insufficient funds".to_string()), arch_sdk::RollbackStatus::NotRolledback),
+            stub(3, Status::Processed, arch_sdk::RollbackStatus::Rolledback("reorg".to_string())),
+        ];
+
+        let summary = display::summarize_processed(&txs);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], format!("✅ {} processed", txs[0].runtime_transaction.txid()));
+        assert_eq!(lines[1], format!("❌ {} failed: insufficient funds", txs[1].runtime_transaction.txid()));
+        assert_eq!(lines[2], format!("↩️ {} rolled back: reorg", txs[2].runtime_transaction.txid()));
+
+        assert_eq!(display::summarize_processed(&[]), "");
+    }
+
+    #[test]
+    fn test_create_token_mint_with_supply() {
+        let client = setup_test_client();
+
+        let (_, recipient_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (authority_keypair, token_mint_pubkey, recipient_account) =
+            create_token_mint_with_supply(&client, 6, 5_000_000, &recipient_pubkey).unwrap();
+
+        assert_supply(token_mint_pubkey, 5_000_000);
+        assert_balance(recipient_account, 5_000_000);
+        assert_owner(recipient_account, recipient_pubkey);
+
+        let mint = get_mint_info(token_mint_pubkey).unwrap();
+        assert_eq!(mint.decimals, 6);
+        assert!(is_mint_authority(token_mint_pubkey, &authority_keypair).unwrap(), "Authority should keep minting rights");
+    }
+
+    #[test]
+    fn test_transfer_check_frozen_names_destination() {
+        let client = setup_test_client();
+
+        let (freeze_keypair, freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &freeze_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let (authority_keypair, token_mint_pubkey) = create_token_mint_with_options(&client, 9, Some(&freeze_pubkey)).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (holder_keypair, holder_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (recipient_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &holder_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &recipient_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let holder_account = create_token_account(&client, token_mint_pubkey, holder_keypair).unwrap();
+        let recipient_account = create_token_account(&client, token_mint_pubkey, recipient_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &holder_account, &authority_pubkey, authority_keypair, 1_000).unwrap();
+
+        freeze_token_account(&client, &recipient_account, &token_mint_pubkey, &freeze_pubkey, freeze_keypair).unwrap();
+        assert!(is_account_frozen(recipient_account).unwrap());
+        assert!(!is_account_frozen(holder_account).unwrap());
+
+        let options = TransferOptions { check_frozen: true, ..Default::default() };
+        let err = transfer_tokens_with_options(
+            &client, &holder_account, &recipient_account, &holder_pubkey, holder_keypair, 100, &options,
+        ).expect_err("Transfer into a frozen account should be rejected");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::AccountFrozen { account: recipient_account }),
+            "Unexpected error: {}",
+            err
+        );
+        assert_balance(holder_account, 1_000);
+        assert_balance(recipient_account, 0);
+    }
+
+    #[test]
+    fn test_logger_formats() {
+        use logger::{render, LogFormat};
+
+        let text = "\n📋 Step 1: Creating token mint...\n🪙 Minted 5 tokens\n  abc (settled)";
+
+        let events = render(LogFormat::Json, text);
+        assert_eq!(events.len(), 3, "Blank spacing lines should not become events");
+        for event in &events {
+            let value: serde_json::Value = serde_json::from_str(event).expect("Each event should be valid JSON");
+            assert!(value["message"].is_string(), "Missing message in {}", event);
+        }
+        let minted: serde_json::Value = serde_json::from_str(&events[1]).unwrap();
+        assert_eq!(minted["icon"], "🪙");
+        assert_eq!(minted["message"], "Minted 5 tokens");
+        let plain_line: serde_json::Value = serde_json::from_str(&events[2]).unwrap();
+        assert!(plain_line["icon"].is_null());
+
+        // Only the emoji word is an icon, not every leading non-ASCII letter
+        let events = render(LogFormat::Json, "Übertrag abgeschlossen\n⚠️ Übertrag fehlgeschlagen");
+        let no_icon: serde_json::Value = serde_json::from_str(&events[0]).unwrap();
+        assert!(no_icon["icon"].is_null());
+        assert_eq!(no_icon["message"], "Übertrag abgeschlossen");
+        let warning: serde_json::Value = serde_json::from_str(&events[1]).unwrap();
+        assert_eq!(warning["icon"], "⚠️");
+        assert_eq!(warning["message"], "Übertrag fehlgeschlagen");
+
+        assert_eq!(
+            render(LogFormat::Plain, text),
+            vec!["\nStep 1: Creating token mint...\nMinted 5 tokens\n  abc (settled)".to_string()]
+        );
+        assert_eq!(render(LogFormat::Emoji, text), vec![text.to_string()]);
+
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+        let args = ["--log-format", "plain"].iter().map(|arg| arg.to_string());
+        assert_eq!(cli::parse_args(args).unwrap().log_format, Some(LogFormat::Plain));
+    }
+
+    #[test]
+    fn test_lifecycle_result_keypairs_reusable() {
+        let client = setup_test_client();
+        let result = run_token_lifecycle(&client).unwrap();
+
+        // Lifecycle leaves user1 with 500 and user2 with 400 (9 decimals)
+        assert_owner(result.user1_token_account, arch_pubkey(&result.user1_keypair));
+        assert_owner(result.user2_token_account, arch_pubkey(&result.user2_keypair));
+        assert!(is_mint_authority(result.token_mint, &result.authority_keypair).unwrap());
+
+        transfer_tokens(
+            &client,
+            &result.user2_token_account,
+            &result.user1_token_account,
+            &arch_pubkey(&result.user2_keypair),
+            result.user2_keypair,
+            400_000_000,
+        ).unwrap();
+        assert_balance(result.user1_token_account, 900_000_000);
+        assert_balance(result.user2_token_account, 0);
+    }
+
+    #[test]
+    fn test_burn_from_delegated() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (delegate_keypair, delegate_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        fund_account_with_faucet(&client, &delegate_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account_pubkey = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &token_account_pubkey, &authority_pubkey, authority_keypair, 1_000).unwrap();
+        approve_delegate(&client, &token_account_pubkey, &delegate_pubkey, &user_pubkey, user_keypair, 400).unwrap();
+
+        burn_from_delegated(&client, &token_account_pubkey, &token_mint_pubkey, &delegate_pubkey, delegate_keypair, 150).unwrap();
+        assert_balance(token_account_pubkey, 850);
+        assert_supply(token_mint_pubkey, 850);
+        assert_eq!(get_delegation(token_account_pubkey).unwrap(), Some((delegate_pubkey, 250)));
+
+        let result = burn_from_delegated(&client, &token_account_pubkey, &token_mint_pubkey, &delegate_pubkey, delegate_keypair, 300);
+        // Burning past the allowance should fail
+        assert_program_error(result, apl_token::error::TokenError::InsufficientFunds);
+        assert_balance(token_account_pubkey, 850);
+    }
+
+    #[test]
+    fn test_total_holdings() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, first_account) = bootstrap_token(&client, 0, 300).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let second_account = create_token_account(&client, token_mint_pubkey, authority_keypair).unwrap();
+        mint_tokens(&client, &token_mint_pubkey, &second_account, &authority_pubkey, authority_keypair, 200).unwrap();
+
+        assert_eq!(total_holdings(&[first_account, second_account], token_mint_pubkey).unwrap(), 500);
+        assert_eq!(total_holdings(&[], token_mint_pubkey).unwrap(), 0);
+
+        let (_, other_mint, other_account) = bootstrap_token(&client, 0, 50).unwrap();
+        let err = total_holdings(&[first_account, other_account], token_mint_pubkey).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongMint { account: other_account, expected: token_mint_pubkey, actual: other_mint }),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_build_unsigned_messages() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 0, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let message = offline::build_mint_message(&client, &token_mint_pubkey, &authority_token_account, &authority_pubkey, 700).unwrap();
+        let expected = apl_token::instruction::mint_to(
+            &apl_token::id(), &token_mint_pubkey, &authority_token_account, &authority_pubkey, &[], 700,
+        ).unwrap();
+        assert_eq!(message.instructions.len(), 1);
+        assert_eq!(message.instructions[0].data, expected.data);
+        assert_eq!(message.account_keys[0], authority_pubkey, "Authority should pay");
+        for key in [token_mint_pubkey, authority_token_account, apl_token::id()] {
+            assert!(message.account_keys.contains(&key), "Missing account {}", key);
+        }
+        assert_supply(token_mint_pubkey, 0);
+
+        offline::sign_and_send(&client, message, vec![authority_keypair], network::NetworkKind::default()).unwrap();
+        assert_balance(authority_token_account, 700);
+
+        let (_, other_owner, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let message = offline::build_transfer_message(&client, &authority_token_account, &other_owner, &authority_pubkey, 5).unwrap();
+        let expected = apl_token::instruction::transfer(
+            &apl_token::id(), &authority_token_account, &other_owner, &authority_pubkey, &[], 5,
+        ).unwrap();
+        assert_eq!(message.instructions[0].data, expected.data);
+        assert!(offline::build_transfer_message(&client, &authority_token_account, &authority_token_account, &authority_pubkey, 5).is_err());
+    }
+
+    #[test]
+    fn test_mint_check_mint_rejects_other_mint_account() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, _) = bootstrap_token(&client, 0, 0).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (_, other_mint, other_account) = bootstrap_token(&client, 0, 0).unwrap();
+
+        let options = MintOptions { check_mint: true, ..Default::default() };
+        let err = mint_tokens_with_options(
+            &client, &token_mint_pubkey, &other_account, &authority_pubkey, authority_keypair, 100, &options,
+        ).expect_err("Minting into another mint's account should be rejected");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongMint { account: other_account, expected: token_mint_pubkey, actual: other_mint }),
+            "Unexpected error: {}",
+            err
+        );
+        assert_supply(token_mint_pubkey, 0);
+        assert_balance(other_account, 0);
+    }
+
+    #[test]
+    fn test_close_all() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (user_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        let mut accounts = Vec::new();
+        for amount in [0, 100, 250, 0, 75] {
+            let token_account = create_token_account(&client, token_mint_pubkey, user_keypair).unwrap();
+            if amount > 0 {
+                mint_tokens(&client, &token_mint_pubkey, &token_account, &authority_pubkey, authority_keypair, amount).unwrap();
+            }
+            accounts.push((token_account, user_keypair));
+        }
+        // Not a token account: fails on its own without holding up the rest
+        let bogus = arch_program::pubkey::Pubkey::new_unique();
+        accounts.insert(2, (bogus, user_keypair));
+
+        let results = scope::close_all(&client, &accounts, &authority_pubkey, &token_mint_pubkey, network::NetworkKind::default()).unwrap();
+        assert_eq!(results.len(), accounts.len());
+        for ((account, result), (expected, _)) in results.iter().zip(&accounts) {
+            assert_eq!(account, expected, "Results should follow the input order");
+            if *account == bogus {
+                assert!(result.is_err(), "Unknown account should fail");
+            } else {
+                assert_eq!(result, &Ok(()), "{} should be closed", account);
+                let closed = try_read_account_info(*account).is_none_or(|info| info.data.iter().all(|byte| *byte == 0));
+                assert!(closed, "Token account {} should be closed", account);
+            }
+        }
+        assert_supply(token_mint_pubkey, 0);
+    }
+
+    #[test]
+    fn test_program_failure_reason() {
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let stub = |status: Status, logs: Vec<&str>| {
+            let transfer_ix = arch_program::system_instruction::transfer(
+                &payer_pubkey,
+                &arch_program::pubkey::Pubkey::new_unique(),
+                1,
+            );
+            ProcessedTransaction {
+                runtime_transaction: arch_sdk::build_and_sign_transaction(
+                    arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(payer_pubkey), "00".repeat(32)),
+                    vec![payer_keypair],
+                    BITCOIN_NETWORK,
+                ),
+                status,
+                bitcoin_txid: None,
+                logs: logs.into_iter().map(String::from).collect(),
+                rollback_status: arch_sdk::RollbackStatus::NotRolledback,
+            }
+        };
+
+        assert_eq!(error::program_failure("transfer tokens", &stub(Status::Processed, vec![])), None);
+
+        let failure = error::program_failure(
+            "transfer tokens",
+            &stub(Status::Failed("Error processing Instruction 0: custom program error: 0x1".to_string()), vec![]),
+        )
+        .unwrap();
+        assert_eq!(
+            failure,
+            error::TokenError::ProgramFailed {
+                action: "transfer tokens".to_string(),
+                code: Some(1),
+                reason: "Insufficient funds".to_string(),
+            }
+        );
+        assert_eq!(failure.to_string(), "Failed to transfer tokens: 0x1: Insufficient funds");
+
+        // The code can also only show up in the logs
+        let failure = error::program_failure(
+            "burn tokens",
+            &stub(Status::Failed("program failed".to_string()), vec!["Program log: Error: Account is frozen", "Program failed: custom program error: 0x11"]),
+        )
+        .unwrap();
+        assert!(matches!(failure, error::TokenError::ProgramFailed { code: Some(0x11), ref reason, .. } if reason == "Account is frozen"));
+
+        let failure = error::program_failure("mint tokens", &stub(Status::Failed("missing signature".to_string()), vec![])).unwrap();
+        assert_eq!(failure.to_string(), "Failed to mint tokens: missing signature");
+    }
+
+    #[test]
+    fn test_mint_to_owner() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let (_, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        assert_eq!(find_token_account(&client, &owner_pubkey, &token_mint_pubkey).unwrap(), None);
+
+        // The authority pays for the new owner's account
+        let token_account = mint_to_owner(&client, &token_mint_pubkey, &owner_pubkey, authority_keypair, authority_keypair, 300).unwrap();
+        assert_balance(token_account, 300);
+        assert_owner(token_account, owner_pubkey);
+
+        // A second mint reuses the account
+        let again = mint_to_owner(&client, &token_mint_pubkey, &owner_pubkey, authority_keypair, authority_keypair, 200).unwrap();
+        assert_eq!(again, token_account);
+        assert_balance(token_account, 500);
+        assert_supply(token_mint_pubkey, 500);
+    }
+
+    #[test]
+    fn test_pubkey_serde_round_trip() {
+        use std::collections::BTreeMap;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Balances {
+            #[serde(with = "pubkey_serde::map")]
+            by_account: BTreeMap<arch_program::pubkey::Pubkey, u64>,
+        }
+
+        let (alice, bob) = (arch_program::pubkey::Pubkey::new_unique(), arch_program::pubkey::Pubkey::new_unique());
+        let balances = Balances { by_account: BTreeMap::from([(alice, 10), (bob, 20)]) };
+
+        let json = serde_json::to_value(&balances).unwrap();
+        assert_eq!(json["by_account"][alice.to_string()], 10, "Keys should be the pubkey strings: {}", json);
+        assert_eq!(json["by_account"][bob.to_string()], 20);
+        let parsed: Balances = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, balances);
+
+        let bad = serde_json::json!({ "by_account": { "not-a-pubkey": 1 } });
+        assert!(serde_json::from_value::<Balances>(bad).is_err(), "Invalid keys should be rejected");
+
+        // The output structs use the same form
+        let overview = overview::TokenOverview {
+            mint_pubkey: alice,
+            supply: 30,
+            decimals: 0,
+            mint_authority: Some(bob),
+            freeze_authority: None,
+            balances: vec![(bob, 30)],
+        };
+        let json = serde_json::to_value(&overview).unwrap();
+        assert_eq!(json["mint_pubkey"], alice.to_string());
+        assert_eq!(json["freeze_authority"], serde_json::Value::Null);
+        assert_eq!(json["balances"][0][0], bob.to_string());
+        assert_eq!(serde_json::from_value::<overview::TokenOverview>(json).unwrap(), overview);
+    }
+
+    #[test]
+    fn test_verify_accounting() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey, authority_token_account) = bootstrap_token(&client, 0, 1_000).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (holder_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &holder_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let holder_account = create_token_account(&client, token_mint_pubkey, holder_keypair).unwrap();
+        transfer_tokens(&client, &authority_token_account, &holder_account, &authority_pubkey, authority_keypair, 400).unwrap();
+        burn_tokens(&client, &authority_token_account, &token_mint_pubkey, &authority_pubkey, authority_keypair, 100).unwrap();
+
+        // Listing an account twice doesn't count it twice
+        let report = accounting::verify_accounting(
+            token_mint_pubkey, &[authority_token_account, holder_account, holder_account], 100, Some(1_000),
+        ).unwrap();
+        assert!(report.is_balanced(), "Unexpected report: {:?}", report);
+        assert_eq!(report.sum_balances, 900);
+        assert_eq!(report.unaccounted_minted(), Some(0));
+
+        let report = accounting::verify_accounting(token_mint_pubkey, &[authority_token_account], 100, None).unwrap();
+        assert!(!report.is_balanced());
+        assert_eq!(report.unaccounted(), 400, "The holder's balance should be missing");
+        assert_eq!(report.unaccounted_minted(), None);
+
+        // An unrecorded burn shows up against the tracked minted total
+        let report = accounting::verify_accounting(
+            token_mint_pubkey, &[authority_token_account, holder_account], 0, Some(1_000),
+        ).unwrap();
+        assert!(!report.is_balanced(), "The missing burn should be caught: {:?}", report);
+        assert_eq!(report.unaccounted(), 0);
+        assert_eq!(report.unaccounted_minted(), Some(100));
+    }
+
+    #[test]
+    fn test_get_token_balance_retry_gives_up() {
+        // Never created, so every read comes back empty
+        let missing = arch_program::pubkey::Pubkey::new_unique();
+        let delay = std::time::Duration::from_millis(50);
+
+        let started = std::time::Instant::now();
+        let err = get_token_balance_retry(missing, 3, delay).expect_err("A missing account has no balance");
+        assert!(started.elapsed() >= delay * 2, "Should wait between each of the three reads");
+        assert!(err.to_string().contains("not found"), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_reallocate_account_unsupported() {
+        let client = setup_test_client();
+        let (owner_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let token_account = arch_program::pubkey::Pubkey::new_unique();
+
+        // Rejected before reaching the node
+        let err = reallocate_account(&client, &token_account, &[AccountExtension::ImmutableOwner], owner_keypair, owner_keypair)
+            .expect_err("Reallocation should be rejected");
+        assert!(err.to_string().contains("not supported"), "Unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_approve_many() {
+        let client = setup_test_client();
+
+        let (authority_keypair, token_mint_pubkey) = create_token_mint(&client).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let (_, delegate_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let mut approvals = Vec::new();
+        for (i, amount) in [100, 200, 300].into_iter().enumerate() {
+            let (owner_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+            fund_account_with_faucet(&client, &owner_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+            let token_account = create_token_account(&client, token_mint_pubkey, owner_keypair).unwrap();
+            mint_tokens(&client, &token_mint_pubkey, &token_account, &authority_pubkey, authority_keypair, 1_000).unwrap();
+            approvals.push((token_account, owner_keypair, amount + i as u64));
+        }
+
+        let results = approve_many(&client, &delegate_pubkey, &approvals, network::NetworkKind::default()).unwrap();
+        assert_eq!(results.len(), approvals.len());
+        for ((account, result), (token_account, _, amount)) in results.iter().zip(&approvals) {
+            assert_eq!(account, token_account);
+            assert_eq!(result, &Ok(()));
+            assert_eq!(get_delegation(*token_account).unwrap(), Some((delegate_pubkey, *amount)));
+        }
+    }
+
+    #[test]
+    fn test_require_decimals() {
+        let client = setup_test_client();
+
+        let (_, token_mint_pubkey, _) = bootstrap_token(&client, 6, 0).unwrap();
+        require_decimals(token_mint_pubkey, 6).unwrap();
+
+        let err = require_decimals(token_mint_pubkey, 9).expect_err("A 6-decimal mint should not pass as 9");
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::UnexpectedDecimals { mint: token_mint_pubkey, have: 6, expected: 9 }),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_bundle_lifecycle() {
+        let result = bundle_lifecycle("00".repeat(32), network::NetworkKind::default()).unwrap();
+
+        // Mint, user2's account, mint to user1, transfer, burn
+        assert_eq!(result.bundle.len(), 5);
+        assert!(result.bundle.iter().all(|tx| !tx.is_empty()));
+        assert!(result.step_timings.is_empty());
+    }
+
+    #[test]
+    fn test_block_age() {
+        use std::time::{Duration, SystemTime};
+
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_100);
+        // Seconds and milliseconds since the epoch both read as 100s old
+        assert_eq!(poll::block_age(1_700_000_000, now), Duration::from_secs(100));
+        assert_eq!(poll::block_age(1_700_000_000_000, now), Duration::from_secs(100));
+        // Clock skew doesn't make a block negatively old
+        assert_eq!(poll::block_age(1_700_000_200_000, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_ensure_token_account_idempotent() {
+        let client = setup_test_client();
+        let (authority_keypair, mint_pubkey, _) = bootstrap_token(&client, 9, 0).unwrap();
+        let (_, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let first = ensure_token_account(&client, &mint_pubkey, &owner_pubkey, authority_keypair).unwrap();
+        let second = ensure_token_account(&client, &mint_pubkey, &owner_pubkey, authority_keypair).unwrap();
+        assert_eq!(first, second, "Second call should return the existing account");
+        assert_owner(first, owner_pubkey);
+
+        // Only one account was created for the owner
+        let filters = vec![
+            arch_sdk::AccountFilter::DataContent { offset: 0, bytes: mint_pubkey.serialize().to_vec() },
+            arch_sdk::AccountFilter::DataContent { offset: 32, bytes: owner_pubkey.serialize().to_vec() },
+        ];
+        let accounts = client.get_program_accounts(&apl_token::id(), Some(filters)).unwrap();
+        assert_eq!(accounts.len(), 1);
+    }
+
+    #[test]
+    fn test_classify_failure() {
+        use error::FailureKind;
+
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let stub = |status: Status, logs: Vec<&str>| {
+            let transfer_ix = arch_program::system_instruction::transfer(
+                &payer_pubkey,
+                &arch_program::pubkey::Pubkey::new_unique(),
+                1,
+            );
+            ProcessedTransaction {
+                runtime_transaction: arch_sdk::build_and_sign_transaction(
+                    arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(payer_pubkey), "00".repeat(32)),
+                    vec![payer_keypair],
+                    BITCOIN_NETWORK,
+                ),
+                status,
+                bitcoin_txid: None,
+                logs: logs.into_iter().map(String::from).collect(),
+                rollback_status: arch_sdk::RollbackStatus::NotRolledback,
+            }
+        };
+        let failed = |reason: &str, logs| error::classify_failure(&stub(Status::Failed(reason.to_string()), logs));
+
+        assert_eq!(error::classify_failure(&stub(Status::Processed, vec![])), None);
+        assert_eq!(
+            failed("Error processing Instruction 0: custom program error: 0x1", vec![]),
+            Some(FailureKind::InsufficientFunds)
+        );
+        assert_eq!(
+            failed("program failed", vec!["Program log: Error: owner does not match", "custom program error: 0x4"]),
+            Some(FailureKind::InvalidAuthority)
+        );
+        assert_eq!(failed("custom program error: 0x11", vec![]), Some(FailureKind::AccountFrozen));
+        assert_eq!(failed("Blockhash not found", vec![]), Some(FailureKind::ExpiredBlockhash));
+        assert_eq!(failed("missing required signature for instruction", vec![]), Some(FailureKind::InvalidAuthority));
+        assert_eq!(failed("transfer: insufficient lamports 5, need 10", vec![]), Some(FailureKind::InsufficientFunds));
+        // A token error without a kind of its own stays unknown, with the reason
+        assert_eq!(
+            failed("custom program error: 0x5", vec![]),
+            Some(FailureKind::Unknown("custom program error: 0x5".to_string()))
+        );
+
+        assert!(FailureKind::ExpiredBlockhash.is_retryable());
+        assert!(!FailureKind::InsufficientFunds.is_retryable());
+
+        let frozen = error::TokenError::ProgramFailed {
+            action: "transfer tokens".to_string(),
+            code: Some(0x11),
+            reason: "Account is frozen".to_string(),
+        };
+        assert_eq!(frozen.failure_kind(), Some(FailureKind::AccountFrozen));
+        assert_eq!(error::TokenError::AmountOverflow { expression: "1 - 2".to_string() }.failure_kind(), None);
+    }
+
+    #[test]
+    fn test_transfer_checked_to_owner() {
+        let client = setup_test_client();
+        let (authority_keypair, mint_pubkey, authority_token_account) = bootstrap_token(&client, 6, 10_000_000).unwrap();
+        let (_, recipient_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let destination = transfer_checked_to_owner(
+            &client,
+            &authority_token_account,
+            &recipient_pubkey,
+            &mint_pubkey,
+            authority_keypair,
+            authority_keypair,
+            "1.5",
+        )
+        .unwrap();
+
+        assert_owner(destination, recipient_pubkey);
+        assert_balance(destination, 1_500_000);
+        assert_balance(authority_token_account, 8_500_000);
+    }
+
+    #[test]
+    fn test_token_client_node_override() {
+        let token_client = client::TokenClient::new(setup_test_client());
+
+        // Nothing listens on the alternate address, so only the override fails
+        let alternate = token_client.on_node(Some("http://127.0.0.1:1"), |rpc| rpc.get_block_count());
+        assert!(alternate.is_err(), "Read should have gone to the alternate node");
+
+        let default = token_client.on_node(None, |rpc| rpc.get_block_count());
+        assert!(default.is_ok(), "Read without an override should use the client's node");
+    }
+
+    #[test]
+    fn test_base_account_has_no_extensions() {
+        let client = setup_test_client();
+        let (_, _, token_account) = bootstrap_token(&client, 9, 0).unwrap();
+
+        assert_eq!(account_data_len(token_account).unwrap(), Account::LEN);
+        assert!(!has_extensions(token_account).unwrap());
+    }
+
+    #[test]
+    fn test_group_by_state() {
+        let failed = poll::TxState::Failed("custom program error: 0x1".to_string());
+        let statuses = vec![
+            ("a".to_string(), poll::TxState::Processed),
+            ("b".to_string(), poll::TxState::Pending),
+            ("c".to_string(), failed.clone()),
+            ("d".to_string(), poll::TxState::Processed),
+            ("e".to_string(), poll::TxState::Processing),
+        ];
+
+        let groups = poll::group_by_state(statuses);
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups[&poll::TxState::Processed], vec!["a", "d"]);
+        assert_eq!(groups[&poll::TxState::Pending], vec!["b"]);
+        assert_eq!(groups[&poll::TxState::Processing], vec!["e"]);
+        assert_eq!(groups[&failed], vec!["c"]);
+    }
+
+    #[test]
+    fn test_keypair_import() {
+        // The private key 1, compressed, as mainnet WIF
+        let one = format!("{}01", "00".repeat(31));
+        let from_wif = keypair_from_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn").unwrap();
+        let from_hex = keypair_from_hex(&one).unwrap();
+        assert_eq!(from_wif, from_hex);
+        assert_eq!(hex::encode(from_hex.secret_bytes()), one);
+
+        // Round trip through both formats for a fresh key
+        let (keypair, pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let imported = keypair_from_hex(&hex::encode(keypair.secret_bytes())).unwrap();
+        assert_eq!(arch_pubkey(&imported), pubkey);
+        let wif = bitcoin::PrivateKey::new(keypair.secret_key(), bitcoin::Network::Regtest).to_wif();
+        assert_eq!(keypair_from_wif(&wif).unwrap(), keypair);
+
+        assert!(keypair_from_wif("not a key").unwrap_err().to_string().starts_with("Invalid WIF key"));
+        assert_eq!(keypair_from_hex("abcd").unwrap_err().to_string(), "Invalid hex key: expected 32 bytes, got 2");
+        assert!(keypair_from_hex(&"zz".repeat(32)).is_err());
+        // Zero isn't a valid secp256k1 secret
+        assert!(keypair_from_hex(&"00".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_estimate_batch_rent() {
+        let client = setup_test_client();
+        let minimum = rent_exempt_minimum(&client, Account::LEN).unwrap();
+
+        assert_eq!(estimate_batch_rent(&client, 0, Account::LEN).unwrap(), 0);
+        assert_eq!(estimate_batch_rent(&client, 25, Account::LEN).unwrap(), 25 * minimum);
+        assert!(estimate_batch_rent(&client, usize::MAX, Account::LEN).is_err());
+    }
+
+    #[test]
+    fn test_onboard_users_checks_payer_balance() {
+        let client = setup_test_client();
+        let (authority_keypair, mint_pubkey, _) = bootstrap_token(&client, 9, 0).unwrap();
+        // Never funded, so it can't pay any rent
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (_, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let err = distribute::onboard_users(&client, &mint_pubkey, authority_keypair, payer_keypair, &[owner_pubkey], 10, network::NetworkKind::default()).unwrap_err();
+        let required = estimate_batch_rent(&client, 1, Account::LEN).unwrap();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::InsufficientLamports { payer: payer_pubkey, balance: 0, required })
+        );
+    }
+
+    #[test]
+    fn test_fixture_balances() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(6).holder(1_500_000).holder(0).holder(42).build(&client);
+
+        assert_eq!(fixture.holders.len(), 3);
+        for (holder, expected) in fixture.holders.iter().zip([1_500_000, 0, 42]) {
+            assert_owner(holder.token_account, holder.pubkey);
+            assert_balance(holder.token_account, expected);
+        }
+        assert_supply(fixture.mint, 1_500_042);
+        assert_eq!(get_mint_info(fixture.mint).unwrap().decimals, 6);
+        assert!(is_mint_authority(fixture.mint, &fixture.authority_keypair).unwrap());
+    }
+
+    #[test]
+    fn test_get_freeze_authority() {
+        let client = setup_test_client();
+
+        let (_, mint_without) = create_token_mint(&client).unwrap();
+        assert_eq!(get_freeze_authority(mint_without).unwrap(), None);
+
+        let (_, freeze_authority, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (_, mint_with) = create_token_mint_with_options(&client, 9, Some(&freeze_authority)).unwrap();
+        assert_eq!(get_freeze_authority(mint_with).unwrap(), Some(freeze_authority));
+    }
+
+    #[test]
+    fn test_transfer_event() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(1_000).holder(0).build(&client);
+        let [sender, recipient] = &fixture.holders[..] else { unreachable!() };
+
+        let event = transfer_tokens_with_event(
+            &client,
+            &sender.token_account,
+            &recipient.token_account,
+            &sender.pubkey,
+            sender.keypair,
+            400,
+        )
+        .unwrap();
+
+        assert_eq!(event.from, sender.token_account);
+        assert_eq!(event.to, recipient.token_account);
+        assert_eq!(event.mint, fixture.mint);
+        assert_eq!(event.amount, 400);
+        let processed_tx = client.get_processed_transaction(&event.signature).unwrap().expect("Event signature should exist");
+        assert_eq!(event.block_hash, processed_tx.runtime_transaction.message.recent_blockhash);
+    }
+
+    #[test]
+    fn test_transfer_event_from_processed() {
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let [from, to, mint, other_mint] = [(); 4].map(|_| arch_program::pubkey::Pubkey::new_unique());
+        let processed = |instruction: arch_program::instruction::Instruction| ProcessedTransaction {
+            runtime_transaction: arch_sdk::build_and_sign_transaction(
+                arch_program::sanitized::ArchMessage::new(&[instruction], Some(owner_pubkey), "ab".repeat(32)),
+                vec![owner_keypair],
+                BITCOIN_NETWORK,
+            ),
+            status: Status::Processed,
+            bitcoin_txid: None,
+            logs: vec![],
+            rollback_status: arch_sdk::RollbackStatus::NotRolledback,
+        };
+
+        let transfer = processed(apl_token::instruction::transfer(&apl_token::id(), &from, &to, &owner_pubkey, &[], 75).unwrap());
+        let event = events::TransferEvent::from_processed(mint, &transfer).unwrap();
+        assert_eq!(
+            event,
+            events::TransferEvent {
+                from,
+                to,
+                mint,
+                amount: 75,
+                signature: transfer.runtime_transaction.txid(),
+                block_hash: "ab".repeat(32),
+            }
+        );
+
+        // A checked transfer names its own mint
+        let checked = processed(
+            apl_token::instruction::transfer_checked(&apl_token::id(), &from, &mint, &to, &owner_pubkey, &[], 5, 9).unwrap(),
+        );
+        assert_eq!(events::TransferEvent::from_processed(other_mint, &checked).unwrap().mint, mint);
+
+        let burn = processed(apl_token::instruction::burn(&apl_token::id(), &from, &mint, &owner_pubkey, &[], 5).unwrap());
+        assert_eq!(events::TransferEvent::from_processed(mint, &burn), None);
+    }
+
+    #[test]
+    fn test_freeze_with_unrelated_key() {
+        let client = setup_test_client();
+        let (freeze_keypair, freeze_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (_, mint_pubkey) = create_token_mint_with_options(&client, 9, Some(&freeze_pubkey)).unwrap();
+        let (holder_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &holder_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let token_account = create_token_account(&client, mint_pubkey, holder_keypair).unwrap();
+
+        let (stranger_keypair, stranger_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let expected = error::TokenError::WrongAuthority {
+            account: mint_pubkey,
+            role: "freeze authority",
+            expected: Some(freeze_pubkey),
+            provided: stranger_pubkey,
+        };
+
+        let err = freeze_token_account(&client, &token_account, &mint_pubkey, &stranger_pubkey, stranger_keypair).unwrap_err();
+        assert_eq!(err.downcast_ref::<error::TokenError>(), Some(&expected));
+        assert!(!is_account_frozen(token_account).unwrap());
+
+        freeze_token_account(&client, &token_account, &mint_pubkey, &freeze_pubkey, freeze_keypair).unwrap();
+        let err = thaw_token_account(&client, &token_account, &mint_pubkey, &stranger_pubkey, stranger_keypair).unwrap_err();
+        assert_eq!(err.downcast_ref::<error::TokenError>(), Some(&expected));
+        assert!(is_account_frozen(token_account).unwrap());
+    }
+
+    #[test]
+    fn test_top_up_lamports() {
+        let client = setup_test_client();
+        let (authority_keypair, _, token_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let before = get_account_lamports(token_account).unwrap();
+
+        let added = top_up_lamports(&client, &token_account, authority_keypair, before + 5_000).unwrap();
+        assert_eq!(added, 5_000);
+        assert_eq!(get_account_lamports(token_account).unwrap(), before + 5_000);
+
+        // Already at the target: nothing is sent
+        assert_eq!(top_up_lamports(&client, &token_account, authority_keypair, before).unwrap(), 0);
+        assert_eq!(get_account_lamports(token_account).unwrap(), before + 5_000);
+    }
+
+    #[test]
+    fn test_filter_valid_token_accounts() {
+        let client = setup_test_client();
+        let (_, mint_pubkey, token_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let (_, other_mint, other_account) = bootstrap_token(&client, 9, 0).unwrap();
+        let random = arch_program::pubkey::Pubkey::new_unique();
+
+        let candidates = [token_account, mint_pubkey, random, other_account, other_mint];
+        assert_eq!(
+            filter_valid_token_accounts(&candidates, mint_pubkey),
+            vec![(token_account, true), (mint_pubkey, false), (random, false), (other_account, false), (other_mint, false)]
+        );
+    }
+
+    #[test]
+    fn test_partial_sign() {
+        use arch_program::sanitize::Sanitize as _;
+
+        let (owner_keypair, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (payer_keypair, payer_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let transfer_ix = apl_token::instruction::transfer(
+            &apl_token::id(),
+            &arch_program::pubkey::Pubkey::new_unique(),
+            &arch_program::pubkey::Pubkey::new_unique(),
+            &owner_pubkey,
+            &[],
+            10,
+        )
+        .unwrap();
+        let message = arch_program::sanitized::ArchMessage::new(&[transfer_ix], Some(payer_pubkey), "00".repeat(32));
+
+        // The relayer signs first; the owner is still needed
+        let (mut transaction, missing) = offline::partial_sign(message.clone(), &[payer_keypair], network::NetworkKind::default());
+        assert_eq!(missing, vec![owner_pubkey]);
+
+        // Signing with nobody new changes nothing
+        assert_eq!(offline::add_signatures(&mut transaction, &[], network::NetworkKind::default()), vec![owner_pubkey]);
+
+        let payer_signature = transaction.signatures[0].clone();
+        assert!(offline::add_signatures(&mut transaction, &[owner_keypair, payer_keypair], network::NetworkKind::default()).is_empty());
+        // Signatures already collected are kept
+        assert_eq!(transaction.signatures[0], payer_signature);
+        assert!(transaction.signatures.iter().all(|signature| signature.0 != [0; 64]));
+        assert_eq!(transaction.message, message);
+        transaction.sanitize().unwrap();
+    }
+
+    #[test]
+    fn test_delegate_approve_transfer_revoke_cycle() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(1_000).holder(0).build(&client);
+        let [owner, recipient] = &fixture.holders[..] else { unreachable!() };
+        let (delegate_keypair, delegate_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &delegate_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        // Approve the delegate for part of the balance
+        approve_delegate(&client, &owner.token_account, &delegate_pubkey, &owner.pubkey, owner.keypair, 300).unwrap();
+        assert_eq!(get_delegation(owner.token_account).unwrap(), Some((delegate_pubkey, 300)));
+
+        // The delegate spends the allowance in two transfers
+        transfer_tokens(&client, &owner.token_account, &recipient.token_account, &delegate_pubkey, delegate_keypair, 200).unwrap();
+        assert_eq!(get_delegation(owner.token_account).unwrap(), Some((delegate_pubkey, 100)));
+        transfer_tokens(&client, &owner.token_account, &recipient.token_account, &delegate_pubkey, delegate_keypair, 100).unwrap();
+        assert_balance(owner.token_account, 700);
+        assert_balance(recipient.token_account, 300);
+
+        // Re-approve, then go over the limit
+        approve_delegate(&client, &owner.token_account, &delegate_pubkey, &owner.pubkey, owner.keypair, 50).unwrap();
+        let result = transfer_tokens(&client, &owner.token_account, &recipient.token_account, &delegate_pubkey, delegate_keypair, 51);
+        assert_program_error(result, apl_token::error::TokenError::InsufficientFunds);
+        assert_balance(owner.token_account, 700);
+
+        // Once revoked, the delegate can't move anything
+        revoke_delegate(&client, &owner.token_account, &owner.pubkey, owner.keypair).unwrap();
+        assert_eq!(get_delegation(owner.token_account).unwrap(), None);
+        let result = transfer_tokens(&client, &owner.token_account, &recipient.token_account, &delegate_pubkey, delegate_keypair, 1);
+        assert_program_error(result, apl_token::error::TokenError::OwnerMismatch);
+        assert_balance(owner.token_account, 700);
+        assert_balance(recipient.token_account, 300);
+    }
+
+    #[test]
+    fn test_remaining_allowance() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(500).holder(0).build(&client);
+        let [owner, recipient] = &fixture.holders[..] else { unreachable!() };
+        let (delegate_keypair, delegate_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &delegate_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+
+        assert_eq!(remaining_allowance(owner.token_account).unwrap(), 0);
+
+        approve_delegate(&client, &owner.token_account, &delegate_pubkey, &owner.pubkey, owner.keypair, 100).unwrap();
+        transfer_tokens(&client, &owner.token_account, &recipient.token_account, &delegate_pubkey, delegate_keypair, 40).unwrap();
+        assert_eq!(remaining_allowance(owner.token_account).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_mint_ui() {
+        let client = setup_test_client();
+        let (authority_keypair, mint_pubkey, token_account) = bootstrap_token(&client, 9, 0).unwrap();
+
+        mint_ui(&client, &mint_pubkey, &token_account, authority_keypair, "1000").unwrap();
+        assert_balance(token_account, 1_000_000_000_000);
+
+        // Too precise for the mint: rejected before anything is sent
+        let err = mint_ui(&client, &mint_pubkey, &token_account, authority_keypair, "0.0000000001").unwrap_err();
+        assert!(matches!(err.downcast_ref::<error::TokenError>(), Some(error::TokenError::InvalidAmount { .. })));
+        assert_supply(mint_pubkey, 1_000_000_000_000);
+    }
+
+    #[test]
+    fn test_get_mint_info_on_token_account() {
+        let client = setup_test_client();
+        let (_, _, token_account) = bootstrap_token(&client, 9, 0).unwrap();
+
+        let err = get_mint_info(token_account).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WrongAccountSize { account: token_account, got: Account::LEN, expected: Mint::LEN })
+        );
+    }
+
+    #[test]
+    fn test_transfer_and_report() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(1_000).holder(0).build(&client);
+        let [sender, recipient] = &fixture.holders[..] else { unreachable!() };
+
+        let remaining =
+            transfer_and_report(&client, &sender.token_account, &recipient.token_account, &sender.pubkey, sender.keypair, 350).unwrap();
+        assert_eq!(remaining, 1_000 - 350);
+        assert_balance(sender.token_account, remaining);
+    }
+
+    #[test]
+    fn test_create_token_account3() {
+        let client = setup_test_client();
+        let (authority_keypair, mint_pubkey, _) = bootstrap_token(&client, 9, 0).unwrap();
+        // The owner is never funded and never signs
+        let (_, owner_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let token_account = create_token_account3(&client, &mint_pubkey, &owner_pubkey, authority_keypair).unwrap();
+        assert_owner(token_account, owner_pubkey);
+        assert_balance(token_account, 0);
+        assert_eq!(Account::unpack(&read_account_info(token_account).data).unwrap().mint, mint_pubkey);
+    }
+
+    #[test]
+    fn test_verify_balances() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(100).holder(250).build(&client);
+        let [first, second] = &fixture.holders[..] else { unreachable!() };
+
+        assert_eq!(
+            accounting::verify_balances(&[(first.token_account, 100), (second.token_account, 250)]).unwrap(),
+            vec![(first.token_account, 100), (second.token_account, 250)]
+        );
+
+        let err = accounting::verify_balances(&[(first.token_account, 100), (second.token_account, 300)]).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::BalanceMismatches { mismatches: vec![(second.token_account, 300, 250)] })
+        );
+    }
+
+    #[test]
+    fn test_network_defaults() {
+        use network::{NetworkDefaults, NetworkKind};
+
+        assert_eq!(
+            NetworkKind::Regtest.defaults(),
+            NetworkDefaults { decimals: 9, faucet_lamports: Some(DEFAULT_FUNDING_LAMPORTS) }
+        );
+        assert_eq!(NetworkKind::Testnet.defaults(), NetworkDefaults { decimals: 9, faucet_lamports: Some(100_000_000) });
+        assert_eq!(NetworkKind::Signet.defaults(), NetworkKind::Testnet.defaults());
+        assert_eq!(NetworkKind::Mainnet.defaults(), NetworkDefaults { decimals: 9, faucet_lamports: None });
+        for network in [NetworkKind::Mainnet, NetworkKind::Testnet, NetworkKind::Regtest, NetworkKind::Signet] {
+            assert_eq!(network.has_faucet(), network.defaults().faucet_lamports.is_some());
+        }
+    }
+
+    #[test]
+    fn test_receipt_to_human() {
+        let from = arch_program::pubkey::Pubkey::new_unique();
+        let to = arch_program::pubkey::Pubkey::new_unique();
+        let receipt = receipts::TransactionReceipt {
+            operation: "transfer tokens".to_string(),
+            signature: "ab".repeat(32),
+            accounts_touched: vec![from, to],
+            amount: Some(1_500_000_000),
+            status: Status::Processed,
+            label: None,
+        };
+
+        let line = receipt.to_human(Some(9));
+        assert!(line.starts_with("✅ transfer tokens: 1.5 tokens ["), "{}", line);
+        assert!(line.contains(&display::short_pubkey(&from)) && line.contains(&display::short_pubkey(&to)), "{}", line);
+        assert!(line.ends_with(&format!("({})", "ab".repeat(32))), "{}", line);
+        assert!(receipt.to_string().contains("transfer tokens: 1500000000 base units"));
+
+        let failed = receipts::TransactionReceipt { status: Status::Failed("insufficient funds".to_string()), ..receipt }.with_label("airdrop");
+        assert!(failed.to_string().starts_with("❌ transfer tokens"));
+        assert!(failed.to_string().ends_with("failed: insufficient funds #airdrop"));
+    }
+
+    #[test]
+    fn test_transfer_and_revoke() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(1_000).holder(0).build(&client);
+        let [owner, recipient] = &fixture.holders[..] else { unreachable!() };
+
+        let (_, delegate_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        approve_delegate(&client, &owner.token_account, &delegate_pubkey, &owner.pubkey, owner.keypair, 400).unwrap();
+        assert_eq!(get_delegation(owner.token_account).unwrap(), Some((delegate_pubkey, 400)));
+
+        transfer_and_revoke(&client, &owner.token_account, &recipient.token_account, &owner.pubkey, owner.keypair, 300).unwrap();
+        assert_balance(owner.token_account, 700);
+        assert_balance(recipient.token_account, 300);
+        assert_eq!(get_delegation(owner.token_account).unwrap(), None, "Delegation should be cleared with the transfer");
+    }
+
+    #[test]
+    fn test_check_remaining_rent() {
+        let client = setup_test_client();
+        let account = arch_program::pubkey::Pubkey::new_unique();
+        let minimum = rent_exempt_minimum(&client, 0).unwrap();
+
+        assert_eq!(check_remaining_rent(&client, account, minimum + 500, 500, 0).unwrap(), minimum);
+        // Taking everything leaves nothing to strand
+        assert_eq!(check_remaining_rent(&client, account, minimum + 500, minimum + 500, 0).unwrap(), 0);
+
+        let err = check_remaining_rent(&client, account, minimum + 500, 501, 0).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::InsufficientRent { provided: minimum - 1, required: minimum })
+        );
+        let err = check_remaining_rent(&client, account, 100, 101, 0).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::WithdrawExceedsBalance { account, balance: 100, withdraw: 101 })
+        );
+        assert_eq!(err.to_string(), format!("Cannot withdraw 101 lamports from {}, which holds only 100", account));
+    }
+
+    #[test]
+    fn test_reclaim_lamports_keeps_rent() {
+        let client = setup_test_client();
+        let (user_keypair, user_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (_, destination_pubkey, _) = generate_new_keypair(BITCOIN_NETWORK);
+        fund_account_with_faucet(&client, &user_keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        let balance = read_account_info(user_pubkey).lamports;
+        let minimum = rent_exempt_minimum(&client, 0).unwrap();
+
+        let err = reclaim_lamports(&client, user_keypair, &destination_pubkey, balance - minimum + 1).unwrap_err();
+        assert!(matches!(err.downcast_ref::<error::TokenError>(), Some(error::TokenError::InsufficientRent { .. })));
+        assert_eq!(read_account_info(user_pubkey).lamports, balance, "Nothing should be sent when the guard fires");
+
+        let remaining = reclaim_lamports(&client, user_keypair, &destination_pubkey, 1_000).unwrap();
+        assert!(remaining >= minimum);
+    }
+
+    #[test]
+    fn test_plan_launch() {
+        let alice = arch_program::pubkey::Pubkey::new_unique();
+        let bob = arch_program::pubkey::Pubkey::new_unique();
+
+        let plan = launch::plan_launch(2, 1_000, &[(alice, 600), (bob, 400)]).unwrap();
+        assert_eq!(plan.recipients, vec![(alice, 600), (bob, 400)]);
+        let printed = plan.to_string();
+        assert!(printed.starts_with("Launch plan: 10 tokens to 2 recipients"), "{}", printed);
+        assert!(printed.contains("3. Create token account for"), "{}", printed);
+
+        assert_eq!(
+            launch::plan_launch(2, 1_000, &[(alice, 600), (bob, 300)]).unwrap_err(),
+            error::TokenError::AllocationMismatch { total_supply: 1_000, allocated: 900 }
+        );
+        assert_eq!(
+            launch::plan_launch(2, 1_000, &[(alice, 600), (alice, 400)]).unwrap_err(),
+            error::TokenError::DuplicateRecipient { recipient: alice }
+        );
+    }
+
+    #[test]
+    fn test_execute_plan() {
+        let client = setup_test_client();
+        let (_, alice, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (_, bob, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let plan = launch::plan_launch(6, 5_000, &[(alice, 3_000), (bob, 2_000)]).unwrap();
+
+        let launched = launch::execute_plan(&client, &plan).unwrap();
+        assert_supply(launched.mint_pubkey, 5_000);
+        let [(_, alice_account), (_, bob_account)] = launched.accounts[..] else { unreachable!() };
+        assert_balance(alice_account, 3_000);
+        assert_balance(bob_account, 2_000);
+        assert_owner(alice_account, alice);
+    }
+
+    #[test]
+    fn test_decimals_cache() {
+        let cache = amount::DecimalsCache::new();
+        let mint = arch_program::pubkey::Pubkey::new_unique();
+        let fetches = std::cell::Cell::new(0);
+        let fetch = |_| {
+            fetches.set(fetches.get() + 1);
+            Ok(6)
+        };
+
+        for _ in 0..3 {
+            assert_eq!(cache.decimals_with(mint, fetch).unwrap(), 6);
+        }
+        assert_eq!(fetches.get(), 1, "The mint should only be read once");
+
+        assert!(cache.decimals_with(arch_program::pubkey::Pubkey::new_unique(), |_| Err("offline".into())).is_err());
+        assert_eq!(cache.decimals_with(mint, fetch).unwrap(), 6);
+        assert_eq!(fetches.get(), 1);
+
+        cache.invalidate(mint);
+        assert_eq!(cache.decimals_with(mint, fetch).unwrap(), 6);
+        assert_eq!(fetches.get(), 2, "An invalidated mint should be read again");
+    }
+
+    #[test]
+    fn test_check_multisig_signers() {
+        let multisig_pubkey = arch_program::pubkey::Pubkey::new_unique();
+        let [a, b, c] = [(); 3].map(|_| arch_program::pubkey::Pubkey::new_unique());
+        let mut multisig = Multisig { m: 2, n: 3, is_initialized: true, ..Multisig::default() };
+        multisig.signers[..3].copy_from_slice(&[a, b, c]);
+
+        assert!(check_multisig_signers(&multisig_pubkey, &multisig, &[a, c]).is_ok());
+        assert!(check_multisig_signers(&multisig_pubkey, &multisig, &[c, b, a]).is_ok());
+        assert_eq!(
+            check_multisig_signers(&multisig_pubkey, &multisig, &[a]),
+            Err(error::TokenError::NotEnoughSigners { multisig: multisig_pubkey, required: 2, provided: 1 })
+        );
+        // The same signer twice still counts once
+        assert_eq!(
+            check_multisig_signers(&multisig_pubkey, &multisig, &[a, a]),
+            Err(error::TokenError::NotEnoughSigners { multisig: multisig_pubkey, required: 2, provided: 1 })
+        );
+        let outsider = arch_program::pubkey::Pubkey::new_unique();
+        assert_eq!(
+            check_multisig_signers(&multisig_pubkey, &multisig, &[a, outsider]),
+            Err(error::TokenError::NotMultisigSigner { multisig: multisig_pubkey, signer: outsider })
+        );
+    }
+
+    #[test]
+    fn test_transfer_to_and_from_multisig_account() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(1_000).build(&client);
+        let [holder] = &fixture.holders[..] else { unreachable!() };
+
+        let signers: Vec<_> = (0..3).map(|_| generate_new_keypair(BITCOIN_NETWORK)).collect();
+        for (keypair, _, _) in &signers {
+            fund_account_with_faucet(&client, keypair, DEFAULT_FUNDING_LAMPORTS).unwrap();
+        }
+        let signer_pubkeys: Vec<_> = signers.iter().map(|(_, pubkey, _)| *pubkey).collect();
+        let multisig_pubkey = create_multisig(&client, signers[0].0, &signer_pubkeys, 2).unwrap();
+        let multisig_account = create_token_account3(&client, &fixture.mint, &multisig_pubkey, signers[0].0).unwrap();
+        assert_owner(multisig_account, multisig_pubkey);
+
+        // Into the multisig's account, signed by the holder alone
+        transfer_tokens(&client, &holder.token_account, &multisig_account, &holder.pubkey, holder.keypair, 600).unwrap();
+        assert_balance(multisig_account, 600);
+
+        // Out of it, one signer short of the threshold
+        let err = transfer_tokens_multisig(&client, &multisig_account, &holder.token_account, &multisig_pubkey, &[signers[1].0], 100)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::NotEnoughSigners { multisig: multisig_pubkey, required: 2, provided: 1 })
+        );
+        assert_balance(multisig_account, 600);
+
+        transfer_tokens_multisig(&client, &multisig_account, &holder.token_account, &multisig_pubkey, &[signers[1].0, signers[2].0], 100)
+            .unwrap();
+        assert_balance(multisig_account, 500);
+        assert_balance(holder.token_account, 500);
+    }
+
+    #[test]
+    fn test_raw_mint_requires_authority() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(0).holder(0).build(&client);
+        let [holder] = &fixture.holders[..] else { unreachable!() };
+
+        let build_mint = |authority_keypair: bitcoin::key::Keypair, amount| {
+            let authority_pubkey = arch_pubkey(&authority_keypair);
+            let mint_ix = apl_token::instruction::mint_to(
+                &apl_token::id(), &fixture.mint, &holder.token_account, &authority_pubkey, &[], amount,
+            ).unwrap();
+            arch_sdk::build_and_sign_transaction(
+                arch_program::sanitized::ArchMessage::new(&[mint_ix], Some(authority_pubkey), client.get_best_block_hash().unwrap()),
+                vec![authority_keypair],
+                BITCOIN_NETWORK,
+            )
+        };
+
+        send_expect_processed(&client, vec![build_mint(fixture.authority_keypair, 100)]);
+        send_expect_failure(&client, vec![build_mint(holder.keypair, 200)], error::FailureKind::InvalidAuthority);
+        assert_balance(holder.token_account, 100);
+    }
+
+    #[test]
+    fn test_diff_mint_config() {
+        let authority = arch_program::pubkey::Pubkey::new_unique();
+        let mint = Mint { decimals: 6, mint_authority: Some(authority).into(), is_initialized: true, ..Mint::default() };
+        let config: mint_config::MintConfig = toml::from_str(&format!("decimals = 6\nmint_authority = \"{}\"\n", authority)).unwrap();
+
+        assert_eq!(config.freeze_authority, None, "A missing authority means none");
+        assert!(mint_config::diff_mint_config(&config, &mint).is_empty());
+
+        let mismatched = mint_config::MintConfig { decimals: 9, freeze_authority: Some(authority), ..config };
+        let diffs = mint_config::diff_mint_config(&mismatched, &mint);
+        assert_eq!(diffs.iter().map(|diff| diff.field).collect::<Vec<_>>(), ["decimals", "freeze_authority"]);
+        assert_eq!(diffs[0].to_string(), "decimals: expected 9, found 6");
+        assert_eq!(diffs[1].actual, "none");
+
+        assert!(toml::from_str::<mint_config::MintConfig>("decimals = 6\nsupply = 5\n").is_err(), "Unknown fields should be rejected");
+    }
+
+    #[test]
+    fn test_verify_mint_config() {
+        let client = setup_test_client();
+        let (authority_keypair, mint_pubkey) = create_token_mint_with_options(&client, 6, None).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+
+        let path = std::env::temp_dir().join(format!("mint-config-{}.toml", mint_pubkey));
+        std::fs::write(&path, format!("decimals = 6\nmint_authority = \"{}\"\n", authority_pubkey)).unwrap();
+        assert_eq!(mint_config::verify_mint_config(mint_pubkey, &path).unwrap(), vec![]);
+
+        std::fs::write(&path, format!("decimals = 9\nmint_authority = \"{}\"\n", authority_pubkey)).unwrap();
+        let diffs = mint_config::verify_mint_config(mint_pubkey, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            diffs,
+            vec![mint_config::ConfigDiff { field: "decimals", expected: "9".to_string(), actual: "6".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_derive_seeded_address() {
+        let (owner_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let (other_owner_keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+        let mint = arch_program::pubkey::Pubkey::new_unique();
+
+        let savings = derive_seeded_address(&owner_keypair, &mint, "savings").unwrap();
+        assert_eq!(derive_seeded_address(&owner_keypair, &mint, "savings").unwrap(), savings, "Same seed should give the same address");
+        assert_ne!(derive_seeded_address(&owner_keypair, &mint, "spending").unwrap(), savings);
+        assert_ne!(derive_seeded_address(&owner_keypair, &arch_program::pubkey::Pubkey::new_unique(), "savings").unwrap(), savings);
+        assert_ne!(derive_seeded_address(&other_owner_keypair, &mint, "savings").unwrap(), savings);
+    }
+
+    #[test]
+    fn test_create_token_account_with_seed() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(0).build(&client);
+        let [owner] = &fixture.holders[..] else { unreachable!() };
+
+        let savings = create_token_account_with_seed(&client, fixture.mint, owner.keypair, "savings").unwrap();
+        assert_eq!(savings, derive_seeded_address(&owner.keypair, &fixture.mint, "savings").unwrap());
+        assert_owner(savings, owner.pubkey);
+        assert_balance(savings, 0);
+
+        let spending = create_token_account_with_seed(&client, fixture.mint, owner.keypair, "spending").unwrap();
+        assert_ne!(spending, savings);
+
+        let err = create_token_account_with_seed(&client, fixture.mint, owner.keypair, "savings").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<error::TokenError>(),
+            Some(&error::TokenError::AlreadyInitialized { account: savings, mint: fixture.mint })
+        );
+    }
+
+    #[test]
+    fn test_bench_percentile() {
+        let ms = std::time::Duration::from_millis;
+        let sorted: Vec<_> = (1..=20).map(ms).collect();
+
+        assert_eq!(bench::percentile(&sorted, 50.0), ms(10));
+        assert_eq!(bench::percentile(&sorted, 95.0), ms(19));
+        assert_eq!(bench::percentile(&sorted, 100.0), ms(20));
+        assert_eq!(bench::percentile(&sorted, 0.0), ms(1), "Lowest rank is the first sample");
+        assert_eq!(bench::percentile(&[ms(7)], 95.0), ms(7));
+        assert_eq!(bench::percentile(&[], 50.0), std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bench_op_report() {
+        let ms = std::time::Duration::from_millis;
+        // Unsorted on purpose; one of the five failed and has no latency
+        let report = bench::OpReport::new("transfer", 5, vec![ms(400), ms(100), ms(300), ms(200)], ms(2_000));
+
+        assert_eq!(report.operation, "transfer");
+        assert_eq!((report.attempted, report.succeeded), (5, 4));
+        assert_eq!(report.elapsed_ms, 2_000.0);
+        assert_eq!(report.ops_per_sec, 2.0);
+        assert_eq!(report.p50_ms, 200.0);
+        assert_eq!(report.p95_ms, 400.0);
+
+        let empty = bench::OpReport::new("burn", 3, vec![], std::time::Duration::ZERO);
+        assert_eq!((empty.ops_per_sec, empty.p50_ms), (0.0, 0.0));
+
+        let json = serde_json::to_value(bench::BenchReport { count: 5, operations: vec![report.clone()] }).unwrap();
+        assert_eq!(json["operations"][0]["p95_ms"], 400.0);
+        assert_eq!(serde_json::from_value::<bench::BenchReport>(json).unwrap().operations, vec![report]);
+    }
+
+    #[test]
+    fn test_validate_airdrop_csv() {
+        let alice = arch_program::pubkey::Pubkey::new_unique();
+        let bob = arch_program::pubkey::Pubkey::new_unique();
+        let path = std::env::temp_dir().join(format!("airdrop-{}.csv", alice));
+
+        std::fs::write(&path, format!("pubkey,amount\n{},1.5\n\n# second wave\n{},0.25\n", alice, bob)).unwrap();
+        let plan = distribute::validate_airdrop_csv(&path, 2, 200).unwrap();
+        assert_eq!(plan.recipients, vec![(alice, 150), (bob, 25)]);
+        assert_eq!(plan.total, 175);
+
+        std::fs::write(&path, format!("{},1\nnot-a-pubkey,2\n{},3\n{},-4\n{},1\n", alice, bob, alice, alice)).unwrap();
+        let errors = distribute::validate_airdrop_csv(&path, 0, 100).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<_> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [Some(2), Some(4), Some(5)], "Unexpected errors: {:?}", errors);
+        assert!(errors[0].reason.contains("Invalid pubkey"), "{}", errors[0]);
+        assert!(errors[1].reason.contains("signs are not allowed"), "{}", errors[1]);
+        assert_eq!(errors[2].to_string(), format!("line 5: {} is already listed on line 1", alice));
+
+        let errors = distribute::parse_airdrop_csv(&format!("{},60\n{},60\n", alice, bob), 0, 100).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, None, "An over-budget total belongs to no single line");
+        assert!(distribute::parse_airdrop_csv(&format!("{},0\n", alice), 0, 100).is_err(), "Zero amounts should be rejected");
+        assert!(distribute::parse_airdrop_csv(&format!("{},1,extra\n", alice), 0, 100).is_err());
+        assert!(distribute::parse_airdrop_csv("pubkey,amount\n", 0, 100).is_err(), "An empty list is not a plan");
+    }
+
+    #[test]
+    fn test_get_token_balance_at() {
+        let client = setup_test_client();
+        let fixture = TestFixture::builder(9).holder(1_234).build(&client);
+        let [holder] = &fixture.holders[..] else { unreachable!() };
+
+        // Blocks can land between reads, so retry until one read sees a single block
+        let balance = (0..5)
+            .find_map(|_| get_token_balance_at(&client, holder.token_account, &client.get_best_block_hash().unwrap()).ok())
+            .expect("The best block should be readable");
+        assert_eq!(balance, get_token_balance(holder.token_account).unwrap());
+
+        let past = client.get_block_hash(0).unwrap();
+        let err = get_token_balance_at(&client, holder.token_account, &past).unwrap_err();
+        assert!(
+            matches!(err.downcast_ref::<error::TokenError>(), Some(error::TokenError::HistoricalStateUnavailable { block_hash, .. }) if *block_hash == past),
+            "Unexpected error: {}", err
+        );
+    }
+
+    #[test]
+    fn test_token_client_program_failure_is_typed() {
+        let token_client = client::TokenClient::new(setup_test_client());
+        let (authority_keypair, token_mint_pubkey, token_account) = bootstrap_token(&token_client, 9, 100).unwrap();
+        let authority_pubkey = arch_pubkey(&authority_keypair);
+        let destination = create_token_account(&token_client, token_mint_pubkey, authority_keypair).unwrap();
+
+        let result = token_client.transfer_tokens(&token_account, &destination, &authority_pubkey, authority_keypair, 1_000);
+        assert_program_error(result, apl_token::error::TokenError::InsufficientFunds);
+    }
+
+    #[test]
+    fn test_chunk_instruction_groups() {
+        // Each group is three transfers signed by its own owner and must not be split
+        let owners: Vec<_> = (0..60).map(|_| arch_program::pubkey::Pubkey::new_unique()).collect();
+        let groups: Vec<Vec<_>> = owners
+            .iter()
+            .map(|owner| {
+                (0..3)
+                    .map(|i| {
+                        apl_token::instruction::transfer(
+                            &apl_token::id(),
+                            &arch_program::pubkey::Pubkey::new_unique(),
+                            &arch_program::pubkey::Pubkey::new_unique(),
+                            owner,
+                            &[],
+                            i + 1,
+                        ).unwrap()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let chunks = batch::chunk_instruction_groups(groups, None, &"00".repeat(32)).unwrap();
+        assert!(chunks.len() >= 2, "60 groups should not fit one transaction");
+        let ranges: Vec<_> = chunks.iter().map(|(_, range)| range.clone()).collect();
+        assert_eq!(ranges.first().unwrap().start, 0);
+        assert_eq!(ranges.last().unwrap().end, owners.len());
+        assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start), "Ranges should cover every group in order");
+
+        for (message, range) in &chunks {
+            assert_eq!(message.instructions.len(), 3 * range.len());
+            assert_eq!(message.account_keys[0], owners[range.start], "The first owner in each message pays");
+            assert!(batch::fits_in_transaction(message));
+        }
+
+        let result = batch::BatchResult {
+            successes: vec![],
+            failures: vec![(1, error::TokenError::TransactionFailed { reason: "boom".to_string() })],
+        };
+        assert!(result.group_failure(&ranges, ranges[0].start).is_none());
+        assert!(result.group_failure(&ranges, ranges[1].start).is_some());
+        assert!(result.group_failure(&ranges, ranges[1].end - 1).is_some());
+    }
+
+    #[test]
+    fn test_fund_account_with_faucet_default() {
+        let client = setup_test_client();
+        let (keypair, _, _) = generate_new_keypair(BITCOIN_NETWORK);
+
+        let balance = fund_account_with_faucet_default(&client, &keypair).unwrap();
+        assert!(balance >= network::NetworkKind::default().defaults().faucet_lamports.unwrap());
+    }
+
+    #[test]
+    fn test_insufficient_lamports_display_does_not_underflow() {
+        let payer = arch_program::pubkey::Pubkey::new_unique();
+        let message = error::TokenError::InsufficientLamports { payer, balance: 500, required: 100 }.to_string();
+        assert!(message.ends_with("fund it with 0 more first"), "{}", message);
+
+        let message = error::TokenError::InsufficientLamports { payer, balance: 100, required: 500 }.to_string();
+        assert!(message.ends_with("fund it with 400 more first"), "{}", message);
     }
-} 
\ No newline at end of file
+}